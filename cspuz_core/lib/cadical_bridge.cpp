@@ -36,6 +36,16 @@ int32_t CaDiCaL_Solve(CaDiCaL::Solver* solver) {
     return 0;
 }
 
+int32_t CaDiCaL_SolveWithAssumptions(CaDiCaL::Solver* solver, const int32_t* lits, int32_t n_lits) {
+    // Assumptions are cleared by CaDiCaL after `solve`.
+    for (int i = 0; i < n_lits; ++i) {
+        solver->assume(to_cadical_lit(lits[i]));
+    }
+    int res = solver->solve();
+    if (res == 10) return 1;
+    return 0;
+}
+
 int32_t CaDiCaL_GetModelValueVar(CaDiCaL::Solver* solver, int32_t var) {
     int res = solver->val(var + 1);
     return (res > 0) ? 1 : 0;
@@ -59,6 +59,28 @@ int32_t Glucose_Solve(Glucose::Solver* solver) {
     return solver->solve();
 }
 
+int32_t Glucose_SolveLimited(Glucose::Solver* solver, int64_t conflict_budget) {
+    Glucose::vec<Glucose::Lit> assumptions;
+    solver->setConfBudget(conflict_budget);
+    Glucose::lbool res = solver->solveLimited(assumptions);
+    solver->budgetOff();
+    if (res == l_True) {
+        return 1;
+    } else if (res == l_False) {
+        return 0;
+    } else {
+        return -1;
+    }
+}
+
+int32_t Glucose_SolveWithAssumptions(Glucose::Solver* solver, const int32_t* lits, int32_t n_lits) {
+    Glucose::vec<Glucose::Lit> assumptions;
+    for (int i = 0; i < n_lits; ++i) {
+        assumptions.push(Glucose::Lit{lits[i]});
+    }
+    return solver->solve(assumptions);
+}
+
 int32_t Glucose_NumVar(const Glucose::Solver* solver) {
     return solver->nVars();
 }
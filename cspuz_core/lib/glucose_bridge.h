@@ -31,6 +31,8 @@ int32_t Glucose_NewVar(Glucose::Solver* solver);
 int32_t Glucose_NewNamedVar(Glucose::Solver* solver, const char* name);
 int32_t Glucose_AddClause(Glucose::Solver* solver, int32_t* lits, int32_t n_lits);
 int32_t Glucose_Solve(Glucose::Solver* solver);
+int32_t Glucose_SolveLimited(Glucose::Solver* solver, int64_t conflict_budget);
+int32_t Glucose_SolveWithAssumptions(Glucose::Solver* solver, const int32_t* lits, int32_t n_lits);
 int32_t Glucose_NumVar(const Glucose::Solver* solver);
 int32_t Glucose_GetModelValueVar(const Glucose::Solver* solver, int32_t var);
 void Glucose_SetPolarity(Glucose::Solver* solver, int32_t var, int32_t polarity);
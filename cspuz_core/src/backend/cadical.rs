@@ -12,6 +12,7 @@ extern "C" {
     fn CaDiCaL_DestroySolver(solver: *mut Opaque);
     fn CaDiCaL_AddClause(solver: *mut Opaque, lits: *const i32, n_lits: i32);
     fn CaDiCaL_Solve(solver: *mut Opaque) -> i32;
+    fn CaDiCaL_SolveWithAssumptions(solver: *mut Opaque, lits: *const i32, n_lits: i32) -> i32;
     fn CaDiCaL_GetModelValueVar(solver: *mut Opaque, var: i32) -> i32;
     fn CaDiCaL_AddActiveVerticesConnected(
         solver: *mut Opaque,
@@ -25,6 +26,7 @@ extern "C" {
 pub struct Solver {
     ptr: *mut Opaque,
     num_var: i32,
+    num_clause: usize,
 }
 
 const NUM_VAR_MAX: i32 = 0x3fffffff;
@@ -34,6 +36,7 @@ impl Solver {
         Solver {
             ptr: unsafe { CaDiCaL_CreateSolver() },
             num_var: 0,
+            num_clause: 0,
         }
     }
 
@@ -48,6 +51,10 @@ impl Solver {
         self.num_var
     }
 
+    pub fn num_clause(&self) -> usize {
+        self.num_clause
+    }
+
     pub fn all_vars(&self) -> Vec<Var> {
         (0..self.num_var()).map(Var).collect()
     }
@@ -59,6 +66,7 @@ impl Solver {
             assert!(0 <= c && c < 2 * self.num_var);
         }
         unsafe { CaDiCaL_AddClause(self.ptr, clause.as_ptr(), clause.len() as i32) };
+        self.num_clause += 1;
     }
 
     pub fn add_active_vertices_connected(&mut self, lits: &[Lit], edges: &[(usize, usize)]) {
@@ -102,6 +110,17 @@ impl Solver {
         res != 0
     }
 
+    /// Solves the problem assuming that all literals in `assumptions` are true.
+    /// The assumptions are not kept after this call.
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        assert!(assumptions.len() <= i32::MAX as usize);
+        let assumptions = unsafe { std::mem::transmute::<&[Lit], &[i32]>(assumptions) };
+        let res = unsafe {
+            CaDiCaL_SolveWithAssumptions(self.ptr, assumptions.as_ptr(), assumptions.len() as i32)
+        };
+        res != 0
+    }
+
     pub(crate) unsafe fn model(&self) -> Model<'_> {
         Model { solver: self }
     }
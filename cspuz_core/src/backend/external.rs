@@ -28,6 +28,10 @@ impl Solver {
         self.num_vars
     }
 
+    pub fn num_clause(&self) -> usize {
+        self.clauses.len()
+    }
+
     pub fn all_vars(&self) -> Vec<Var> {
         (0..self.num_var()).map(Var).collect()
     }
@@ -46,9 +50,20 @@ impl Solver {
     }
 
     pub fn solve_without_model(&mut self) -> bool {
+        self.solve_without_model_under_assumptions(&[])
+    }
+
+    /// Solves the problem assuming that all literals in `assumptions` are true.
+    /// The assumptions are passed to the external solver as unit clauses and are not kept after this call.
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
         let mut description = String::new();
-        description.push_str(&format!("p cnf {} {}\n", self.num_vars, self.clauses.len()));
-        for clause in &self.clauses {
+        description.push_str(&format!(
+            "p cnf {} {}\n",
+            self.num_vars,
+            self.clauses.len() + assumptions.len()
+        ));
+        let units = assumptions.iter().map(std::slice::from_ref);
+        for clause in self.clauses.iter().map(|c| &c[..]).chain(units) {
             for l in clause {
                 let n = (l.var().0 + 1) * if l.is_negated() { -1 } else { 1 };
                 description.push_str(&(n.to_string()));
@@ -1,6 +1,7 @@
 use std::ffi::{c_void, CString};
 use std::ops::Drop;
 use std::os::raw::c_char;
+use std::time::Instant;
 
 use crate::propagators::graph_division::{GraphDivision, GraphDivisionOptions};
 use crate::propagators::order_encoding_linear;
@@ -20,6 +21,8 @@ extern "C-unwind" {
     fn Glucose_NewNamedVar(solver: *mut Opaque, name: *const c_char) -> i32;
     fn Glucose_AddClause(solver: *mut Opaque, lits: *const Lit, n_lits: i32) -> i32;
     fn Glucose_Solve(solver: *mut Opaque) -> i32;
+    fn Glucose_SolveLimited(solver: *mut Opaque, conflict_budget: i64) -> i32;
+    fn Glucose_SolveWithAssumptions(solver: *mut Opaque, lits: *const Lit, n_lits: i32) -> i32;
     fn Glucose_NumVar(solver: *mut Opaque) -> i32;
     fn Glucose_GetModelValueVar(solver: *mut Opaque, var: i32) -> i32;
     fn Glucose_SetPolarity(solver: *mut Opaque, var: i32, polarity: i32);
@@ -74,6 +77,7 @@ pub struct Solver {
     // https://doc.rust-lang.org/reference/type-layout.html#r-layout.pointer.unsized
     #[allow(clippy::vec_box)]
     custom_constraints: Vec<Box<Box<dyn CustomPropagator<GlucoseSolverManipulator>>>>,
+    num_clause: usize,
 }
 
 const NUM_VAR_MAX: i32 = 0x3fffffff;
@@ -83,6 +87,7 @@ impl Solver {
         Solver {
             ptr: unsafe { Glucose_CreateSolver() },
             custom_constraints: vec![],
+            num_clause: 0,
         }
     }
 
@@ -103,6 +108,14 @@ impl Solver {
         unsafe { Glucose_NumVar(self.ptr) }
     }
 
+    pub fn num_clause(&self) -> usize {
+        self.num_clause
+    }
+
+    pub fn num_custom_constraint(&self) -> usize {
+        self.custom_constraints.len()
+    }
+
     pub fn all_vars(&self) -> Vec<Var> {
         (0..self.num_var()).map(Var).collect()
     }
@@ -115,6 +128,7 @@ impl Solver {
     pub fn add_clause(&mut self, clause: &[Lit]) -> bool {
         assert!(clause.len() <= i32::MAX as usize);
         let res = unsafe { Glucose_AddClause(self.ptr, clause.as_ptr(), clause.len() as i32) };
+        self.num_clause += 1;
         res != 0
     }
 
@@ -339,6 +353,34 @@ impl Solver {
         res != 0
     }
 
+    /// Solves the problem assuming that all literals in `assumptions` are true.
+    /// The assumptions are not kept after this call.
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        assert!(assumptions.len() <= i32::MAX as usize);
+        let res = unsafe {
+            Glucose_SolveWithAssumptions(self.ptr, assumptions.as_ptr(), assumptions.len() as i32)
+        };
+        res != 0
+    }
+
+    /// Solves the problem, giving up once `deadline` has passed.
+    /// The deadline is only checked every `SOLVE_CONFLICT_BUDGET` conflicts, so the actual
+    /// running time may slightly exceed it.
+    /// Returns `None` if the solver gave up before determining satisfiability.
+    pub fn solve_without_model_with_deadline(&mut self, deadline: Instant) -> Option<bool> {
+        const SOLVE_CONFLICT_BUDGET: i64 = 1000;
+
+        loop {
+            if Instant::now() >= deadline {
+                return None;
+            }
+            let res = unsafe { Glucose_SolveLimited(self.ptr, SOLVE_CONFLICT_BUDGET) };
+            if res >= 0 {
+                return Some(res != 0);
+            }
+        }
+    }
+
     pub(crate) unsafe fn model(&self) -> Model<'_> {
         Model { solver: self }
     }
@@ -23,6 +23,8 @@ pub struct Config {
     pub graph_division_mode: GraphDivisionMode,
     pub optimize_polarity: bool,
     pub verbose: bool,
+    /// If set, the SAT solver gives up once this instant has passed.
+    pub deadline: Option<std::time::Instant>,
 }
 
 thread_local! {
@@ -63,6 +65,7 @@ impl Config {
             graph_division_mode: GraphDivisionMode::Cpp,
             optimize_polarity: false,
             verbose: false,
+            deadline: None,
         }
     }
 
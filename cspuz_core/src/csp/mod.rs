@@ -407,6 +407,10 @@ impl CSP {
         self.constraints.push(stmt);
     }
 
+    pub fn num_int_vars(&self) -> usize {
+        self.vars.int_var.len()
+    }
+
     pub fn is_inconsistent(&self) -> bool {
         self.inconsistent
     }
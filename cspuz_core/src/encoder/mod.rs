@@ -227,7 +227,12 @@ impl EncodeMap {
         }
     }
 
-    fn convert_bool_lit(&mut self, norm_vars: &NormCSPVars, sat: &mut SAT, lit: BoolLit) -> Lit {
+    pub(crate) fn convert_bool_lit(
+        &mut self,
+        norm_vars: &NormCSPVars,
+        sat: &mut SAT,
+        lit: BoolLit,
+    ) -> Lit {
         let var_lit = self.convert_bool_var(norm_vars, sat, lit.var);
         if lit.negated {
             !var_lit
@@ -517,7 +522,7 @@ pub fn encode(norm: &mut NormCSP, sat: &mut SAT, map: &mut EncodeMap, config: &C
                     &dom_lits,
                     &edges,
                     &edge_lits,
-                    config.graph_division_mode,
+                    opts.mode.unwrap_or(config.graph_division_mode),
                     &opts,
                 );
             }
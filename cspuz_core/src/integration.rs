@@ -11,6 +11,7 @@ use super::normalizer::{normalize, NormalizeMap};
 use super::sat::{SATModel, SAT};
 use crate::domain::Domain;
 use std::cell::Cell;
+use std::time::Instant;
 
 #[derive(Clone, Debug)]
 pub struct PerfStats {
@@ -65,6 +66,20 @@ impl PerfStats {
     }
 }
 
+/// Size of the SAT instance produced from a CSP instance.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InstanceStats {
+    /// Number of SAT variables.
+    pub num_vars: usize,
+    /// Number of clauses added to the SAT solver. Native constraints handled by custom propagators
+    /// (e.g. connectivity) are not counted.
+    pub num_clauses: usize,
+    /// Number of integer variables in the CSP instance.
+    pub num_int_vars: usize,
+    /// Number of constraints handled by custom propagators implemented in Rust.
+    pub num_custom_constraints: usize,
+}
+
 pub struct IntegratedSolver<'a> {
     csp: CSP,
     normalize_map: NormalizeMap,
@@ -72,6 +87,7 @@ pub struct IntegratedSolver<'a> {
     encode_map: EncodeMap,
     sat: SAT,
     already_used: bool,
+    timed_out: bool,
     config: Config,
     perf_stats: Option<&'a PerfStats>,
 }
@@ -89,6 +105,7 @@ impl<'a> IntegratedSolver<'a> {
             encode_map: EncodeMap::new(),
             sat: SAT::new_with_backend(config.backend),
             already_used: false,
+            timed_out: false,
             config,
             perf_stats: None,
         };
@@ -196,7 +213,17 @@ impl<'a> IntegratedSolver<'a> {
             return None;
         }
         let start = std::time::Instant::now();
-        let solver_result = if self.sat.solve_without_model() {
+        let is_sat = match self.config.deadline {
+            Some(deadline) => match self.sat.solve_without_model_with_deadline(deadline) {
+                Some(is_sat) => is_sat,
+                None => {
+                    self.timed_out = true;
+                    false
+                }
+            },
+            None => self.sat.solve_without_model(),
+        };
+        let solver_result = if is_sat {
             Some(unsafe { self.sat.model() })
         } else {
             None
@@ -231,6 +258,89 @@ impl<'a> IntegratedSolver<'a> {
         }
     }
 
+    /// Same as `solve`, but solves the problem assuming that each variable in `assumptions` takes the given value.
+    ///
+    /// The assumptions are passed to the SAT solver as assumption literals, so they are discarded after this call
+    /// and the learnt clauses are kept for subsequent calls. `config.deadline` is not taken into account.
+    pub fn solve_under_assumptions(
+        &mut self,
+        assumptions: &[(BoolVar, bool)],
+    ) -> Option<Model<'_>> {
+        if !self.encode() {
+            return None;
+        }
+
+        let mut sat_assumptions = vec![];
+        for &(var, value) in assumptions {
+            let norm_lit = match self.normalize_map.get_bool_var_raw(var) {
+                ConvertedBoolVar::Lit(norm_lit) => norm_lit,
+                ConvertedBoolVar::Removed => match self.csp.get_bool_var_status(var) {
+                    BoolVarStatus::Fixed(v) if v != value => return None,
+                    _ => continue,
+                },
+                ConvertedBoolVar::NotConverted => {
+                    // The variable does not appear in any constraint, so it is converted here
+                    // (just like prenormalize vars) to make the assumption visible in the model.
+                    self.normalize_map
+                        .convert_bool_var(&self.csp.vars, &mut self.norm, var)
+                }
+            };
+            let sat_lit =
+                self.encode_map
+                    .convert_bool_lit(&self.norm.vars, &mut self.sat, norm_lit);
+            sat_assumptions.push(if value { sat_lit } else { !sat_lit });
+        }
+
+        let start = std::time::Instant::now();
+        let is_sat = self
+            .sat
+            .solve_without_model_under_assumptions(&sat_assumptions);
+        if let Some(perf_stats) = self.perf_stats {
+            perf_stats
+                .time_sat_solver
+                .set(perf_stats.time_sat_solver() + start.elapsed().as_secs_f64());
+        }
+        let solver_stats = self.sat.stats();
+        if let Some(perf_stats) = self.perf_stats {
+            if let Some(decisions) = solver_stats.decisions {
+                perf_stats.decisions.set(decisions);
+            }
+            if let Some(propagations) = solver_stats.propagations {
+                perf_stats.propagations.set(propagations);
+            }
+            if let Some(conflicts) = solver_stats.conflicts {
+                perf_stats.conflicts.set(conflicts);
+            }
+        }
+
+        if !is_sat {
+            return None;
+        }
+        Some(Model {
+            csp: &self.csp,
+            normalize_map: &self.normalize_map,
+            norm_csp: &self.norm,
+            encode_map: &self.encode_map,
+            model: unsafe { self.sat.model() },
+        })
+    }
+
+    /// Encodes the constraints added so far and returns the size of the resulting SAT instance.
+    pub fn instance_stats(&mut self) -> InstanceStats {
+        self.encode();
+        InstanceStats {
+            num_vars: self.sat.num_var(),
+            num_clauses: self.sat.num_clause(),
+            num_int_vars: self.csp.num_int_vars(),
+            num_custom_constraints: self.sat.num_custom_constraint(),
+        }
+    }
+
+    /// Returns whether a previous call to `solve` gave up because `config.deadline` had passed.
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
     /// Enumerate all the valid assignments of the CSP problem.
     /// Since this function may modify the problem instance, this consumes `self` to avoid further operations.
     pub fn enumerate_valid_assignments(self) -> Vec<Assignment> {
@@ -247,9 +357,33 @@ impl<'a> IntegratedSolver<'a> {
     }
 
     pub fn decide_irrefutable_facts(
+        self,
+        bool_vars: &[BoolVar],
+        int_vars: &[IntVar],
+    ) -> Option<Assignment> {
+        self.decide_irrefutable_facts_impl(bool_vars, int_vars, None)
+    }
+
+    /// Same as `decide_irrefutable_facts`, but gives up refining the facts once `deadline` has passed.
+    ///
+    /// Candidate facts taken from the first answer are confirmed one by one, by checking that the
+    /// opposite value is unsatisfiable. If `deadline` is reached before every candidate is decided,
+    /// only the facts confirmed so far are returned, so the result is a subset of the irrefutable facts.
+    /// The deadline is checked between SAT solver calls; a single call is never interrupted.
+    pub fn decide_irrefutable_facts_with_deadline(
+        self,
+        bool_vars: &[BoolVar],
+        int_vars: &[IntVar],
+        deadline: Instant,
+    ) -> Option<Assignment> {
+        self.decide_irrefutable_facts_impl(bool_vars, int_vars, Some(deadline))
+    }
+
+    fn decide_irrefutable_facts_impl(
         mut self,
         bool_vars: &[BoolVar],
         int_vars: &[IntVar],
+        deadline: Option<Instant>,
     ) -> Option<Assignment> {
         let mut assignment = Assignment::new();
         match self.solve() {
@@ -263,6 +397,10 @@ impl<'a> IntegratedSolver<'a> {
             }
             None => return None,
         }
+        if let Some(deadline) = deadline {
+            return Some(self.confirm_candidates(assignment, deadline));
+        }
+
         let mut iterations = 1;
         loop {
             let mut refutation = vec![];
@@ -326,7 +464,13 @@ impl<'a> IntegratedSolver<'a> {
                         .iter()
                         .for_each(|&v| assert!(assignment.remove_int(v).is_some()));
                 }
-                None => break,
+                None => {
+                    if self.timed_out {
+                        // The remaining candidates are not confirmed to be irrefutable.
+                        return None;
+                    }
+                    break;
+                }
             }
         }
 
@@ -337,6 +481,98 @@ impl<'a> IntegratedSolver<'a> {
         Some(assignment)
     }
 
+    /// Confirms each fact in `candidates` until all of them are decided or `deadline` has passed,
+    /// and returns the facts confirmed so far.
+    ///
+    /// A candidate is confirmed if the instance is unsatisfiable when the variable takes another value.
+    /// Otherwise, the answer found refutes the candidate as well as every other candidate it disagrees with.
+    fn confirm_candidates(&mut self, mut candidates: Assignment, deadline: Instant) -> Assignment {
+        let mut confirmed = Assignment::new();
+        let mut iterations = 1;
+
+        let bool_candidates = candidates
+            .bool_iter()
+            .map(|(&v, &b)| (v, b))
+            .collect::<Vec<_>>();
+        let int_candidates = candidates
+            .int_iter()
+            .map(|(&v, &i)| (v, i))
+            .collect::<Vec<_>>();
+
+        for (v, b) in bool_candidates {
+            if Instant::now() >= deadline {
+                break;
+            }
+            if candidates.get_bool(v).is_none() {
+                continue;
+            }
+            iterations += 1;
+            let refutation = if b { !v.expr() } else { v.expr() };
+            if !self.refute_candidates(refutation, &mut candidates) {
+                confirmed.set_bool(v, b);
+            }
+        }
+        for (v, i) in int_candidates {
+            if Instant::now() >= deadline {
+                break;
+            }
+            if candidates.get_int(v).is_none() {
+                continue;
+            }
+            iterations += 1;
+            let refutation = v.expr().ne(IntExpr::Const(i));
+            if !self.refute_candidates(refutation, &mut candidates) {
+                confirmed.set_int(v, i);
+            }
+        }
+
+        if let Some(perf_stats) = self.perf_stats {
+            perf_stats.iterations.set(iterations);
+        }
+
+        confirmed
+    }
+
+    /// Looks for an answer satisfying `refutation`. If one is found, removes the candidates which
+    /// disagree with it from `candidates` and returns `true`.
+    fn refute_candidates(&mut self, refutation: BoolExpr, candidates: &mut Assignment) -> bool {
+        // `refutation` is enabled only while `act` is assumed, so that it can be retired afterwards.
+        let act = self.new_bool_var();
+        self.add_expr(act.expr().imp(refutation));
+
+        let erased = self.solve_under_assumptions(&[(act, true)]).map(|model| {
+            let bool_erased = candidates
+                .bool_iter()
+                .filter_map(|(&v, &b)| {
+                    if model.get_bool(v) != b {
+                        Some(v)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            let int_erased = candidates
+                .int_iter()
+                .filter_map(|(&v, &i)| if model.get_int(v) != i { Some(v) } else { None })
+                .collect::<Vec<_>>();
+            (bool_erased, int_erased)
+        });
+        self.add_expr(!act.expr());
+
+        match erased {
+            Some((bool_erased, int_erased)) => {
+                bool_erased.iter().for_each(|&v| {
+                    candidates.remove_bool(v);
+                });
+                int_erased.iter().for_each(|&v| {
+                    candidates.remove_int(v);
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn answer_iter(self, bool_vars: &[BoolVar], int_vars: &[IntVar]) -> AnswerIterator<'a> {
         AnswerIterator {
             solver: self,
@@ -1058,6 +1294,29 @@ mod tests {
         assert!(propagations.iter().any(|&p| p != propagations[0]));
     }
 
+    #[test]
+    fn test_integration_deadline() {
+        let config = Config {
+            deadline: Some(Instant::now()),
+            ..Config::default()
+        };
+
+        let mut solver = IntegratedSolver::with_config(config);
+        let a = solver.new_int_var(Domain::range(0, 5));
+        let b = solver.new_int_var(Domain::range(0, 5));
+        solver.add_expr((a.expr() + b.expr()).ge(IntExpr::Const(4)));
+        solver.add_expr((a.expr() - b.expr()).le(IntExpr::Const(2)));
+        assert!(solver.solve().is_none());
+        assert!(solver.is_timed_out());
+
+        let mut solver = IntegratedSolver::with_config(config);
+        let a = solver.new_int_var(Domain::range(0, 5));
+        let b = solver.new_int_var(Domain::range(0, 5));
+        solver.add_expr((a.expr() + b.expr()).ge(IntExpr::Const(4)));
+        solver.add_expr((a.expr() - b.expr()).le(IntExpr::Const(2)));
+        assert!(solver.decide_irrefutable_facts(&[], &[a, b]).is_none());
+    }
+
     #[test]
     fn test_integration_exhaustive_bool1() {
         let mut tester = IntegrationTester::new();
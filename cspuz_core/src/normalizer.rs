@@ -79,7 +79,7 @@ impl NormalizeMap {
         }
     }
 
-    fn convert_bool_var(
+    pub(crate) fn convert_bool_var(
         &mut self,
         _csp_vars: &CSPVars,
         norm: &mut NormCSP,
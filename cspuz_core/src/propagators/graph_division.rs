@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use crate::sat::{CustomPropagator, Lit, SolverManipulator};
+use crate::sat::{CustomPropagator, GraphDivisionMode, Lit, SolverManipulator};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum EdgeState {
@@ -103,6 +103,8 @@ enum Reason {
 #[derive(Clone, Copy)]
 pub struct GraphDivisionOptions {
     pub disallow_adjacent_same_size_regions: bool,
+    /// Encoding used for this constraint. If `None`, `Config::graph_division_mode` is used.
+    pub mode: Option<GraphDivisionMode>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -110,6 +112,7 @@ impl Default for GraphDivisionOptions {
     fn default() -> Self {
         GraphDivisionOptions {
             disallow_adjacent_same_size_regions: false,
+            mode: None,
         }
     }
 }
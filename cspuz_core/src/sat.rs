@@ -1,4 +1,5 @@
 use std::ops::Not;
+use std::time::Instant;
 
 #[cfg(feature = "backend-cadical")]
 use crate::backend::cadical;
@@ -217,6 +218,24 @@ impl SAT {
         }
     }
 
+    pub fn num_clause(&self) -> usize {
+        match self {
+            SAT::Glucose(solver) => solver.num_clause(),
+            #[cfg(feature = "backend-external")]
+            SAT::External(solver) => solver.num_clause(),
+            #[cfg(feature = "backend-cadical")]
+            SAT::CaDiCaL(solver) => solver.num_clause(),
+        }
+    }
+
+    pub fn num_custom_constraint(&self) -> usize {
+        #[allow(unreachable_patterns)]
+        match self {
+            SAT::Glucose(solver) => solver.num_custom_constraint(),
+            _ => 0,
+        }
+    }
+
     pub fn all_vars(&self) -> Vec<Var> {
         match self {
             SAT::Glucose(solver) => solver.all_vars(),
@@ -458,6 +477,43 @@ impl SAT {
         }
     }
 
+    /// Same as `solve_without_model`, but assumes that all literals in `assumptions` are true.
+    /// Unlike clauses, the assumptions are not kept after this call.
+    pub fn solve_without_model_under_assumptions(&mut self, assumptions: &[Lit]) -> bool {
+        match self {
+            SAT::Glucose(solver) => solver.solve_without_model_under_assumptions(assumptions),
+            #[cfg(feature = "backend-external")]
+            SAT::External(solver) => solver.solve_without_model_under_assumptions(assumptions),
+            #[cfg(feature = "backend-cadical")]
+            SAT::CaDiCaL(solver) => solver.solve_without_model_under_assumptions(assumptions),
+        }
+    }
+
+    /// Same as `solve_without_model`, but returns `None` if `deadline` passes before the
+    /// satisfiability is determined. Backends other than Glucose do not support interruption,
+    /// so the deadline is only checked before solving.
+    pub fn solve_without_model_with_deadline(&mut self, deadline: Instant) -> Option<bool> {
+        match self {
+            SAT::Glucose(solver) => solver.solve_without_model_with_deadline(deadline),
+            #[cfg(feature = "backend-external")]
+            SAT::External(solver) => {
+                if Instant::now() >= deadline {
+                    None
+                } else {
+                    Some(solver.solve_without_model())
+                }
+            }
+            #[cfg(feature = "backend-cadical")]
+            SAT::CaDiCaL(solver) => {
+                if Instant::now() >= deadline {
+                    None
+                } else {
+                    Some(solver.solve_without_model())
+                }
+            }
+        }
+    }
+
     pub(crate) unsafe fn model(&self) -> SATModel<'_> {
         match self {
             SAT::Glucose(solver) => SATModel::Glucose(solver.model()),
@@ -1,5 +1,7 @@
+use crate::items::Arrow;
 use crate::solver::{
-    all, any, Array0DImpl, Array1DImpl, CSPBoolExpr, CSPIntExpr, Operand, Solver, Value,
+    all, any, Array0DImpl, Array1DImpl, Array2DImpl, CSPBoolExpr, CSPIntExpr, Operand, Solver,
+    Value,
 };
 
 /// Adds a constraint that, if `condition` is true (or not present),
@@ -123,6 +125,161 @@ fn partitions(sum: i32, n: i32, value_low: i32, value_high: i32) -> Vec<Vec<bool
     ret
 }
 
+/// Specification of a run (a maximal block of consecutive true cells) in `line_runs_spec`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RunSpec {
+    /// A run of exactly `n` cells. `Fixed(0)` is ignored.
+    Fixed(i32),
+    /// A run of at least `n` cells. `n` must be positive; an optional run cannot be specified.
+    AtLeast(i32),
+    /// One or more runs of arbitrary lengths.
+    Unknown,
+}
+
+/// Adds a constraint that the runs of true cells in `line` match `specs` in order.
+///
+/// Consecutive runs are separated by at least one false cell, and an empty `specs` means that all cells are false.
+/// The constraint is encoded by simulating an automaton recognizing the valid lines over the cells.
+///
+/// Panics if `specs` contains `RunSpec::AtLeast(n)` with `n < 1`.
+pub fn line_runs_spec<T>(solver: &mut Solver, line: T, specs: &[RunSpec])
+where
+    T: IntoIterator,
+    T::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
+{
+    let cells: Vec<Value<Array0DImpl<CSPBoolExpr>>> =
+        line.into_iter().map(|x| Value(x.as_expr_array())).collect();
+    let n = cells.len();
+
+    // Transitions (source, destination, cell value) of an NFA whose initial state is 0.
+    // State 0 corresponds to the leading false cells.
+    let mut transitions = vec![(0, 0, false)];
+    let mut num_states = 1;
+    let mut last = 0;
+    for &spec in specs {
+        if let RunSpec::AtLeast(len) = spec {
+            assert!(len >= 1, "RunSpec::AtLeast requires a positive length");
+        }
+        if spec == RunSpec::Fixed(0) {
+            continue;
+        }
+        if last != 0 {
+            let gap = num_states;
+            num_states += 1;
+            transitions.push((last, gap, false));
+            transitions.push((gap, gap, false));
+            last = gap;
+        }
+        match spec {
+            RunSpec::Fixed(len) | RunSpec::AtLeast(len) => {
+                for _ in 0..len.max(1) {
+                    transitions.push((last, num_states, true));
+                    last = num_states;
+                    num_states += 1;
+                }
+                if let RunSpec::AtLeast(_) = spec {
+                    transitions.push((last, last, true));
+                }
+            }
+            RunSpec::Unknown => {
+                let run = num_states;
+                let gap = num_states + 1;
+                num_states += 2;
+                transitions.push((last, run, true));
+                transitions.push((run, run, true));
+                transitions.push((run, gap, false));
+                transitions.push((gap, gap, false));
+                transitions.push((gap, run, true));
+                last = run;
+            }
+        }
+    }
+    let accepting = if last == 0 {
+        vec![0]
+    } else {
+        let trailing = num_states;
+        num_states += 1;
+        transitions.push((last, trailing, false));
+        transitions.push((trailing, trailing, false));
+        vec![last, trailing]
+    };
+
+    // state[i] is the state of the automaton after reading the first i cells
+    let state = &solver.int_var_1d(n + 1, 0, num_states - 1);
+    solver.add_expr(state.at(0).eq(0));
+    solver.add_expr(any(accepting
+        .iter()
+        .map(|&s| state.at(n).eq(s))
+        .collect::<Vec<_>>()));
+    for (i, cell) in cells.iter().enumerate() {
+        for s in 0..num_states {
+            let cands = transitions
+                .iter()
+                .filter(|&&(src, _, _)| src == s)
+                .map(|&(_, dest, b)| state.at(i + 1).eq(dest) & cell.iff(b))
+                .collect::<Vec<_>>();
+            solver.add_expr(state.at(i).eq(s).imp(any(cands)));
+        }
+    }
+}
+
+/// Returns an expression which is true iff the first value in `line` different from `empty` is `value`.
+///
+/// This is the "first visible from a direction" pattern of puzzles like Easy As ABC;
+/// use `reverse()` on the line to look from the other end. The expression is false if all values are `empty`.
+pub fn first_non_empty_eq<T>(line: T, empty: i32, value: i32) -> Value<Array0DImpl<CSPBoolExpr>>
+where
+    T: IntoIterator,
+    T::Item: Operand<Output = Array0DImpl<CSPIntExpr>>,
+{
+    let cells: Vec<Value<Array0DImpl<CSPIntExpr>>> =
+        line.into_iter().map(|x| Value(x.as_expr_array())).collect();
+
+    let mut cands = vec![];
+    for i in 0..cells.len() {
+        let leading_empty = all(cells[..i].iter().map(|c| c.eq(empty)).collect::<Vec<_>>());
+        cands.push(leading_empty & cells[i].eq(value));
+    }
+    any(cands)
+}
+
+/// Adds a constraint that the orthogonal neighbor of `pos` in the direction `dir` has a value
+/// strictly greater than all the other orthogonal neighbors of `pos` in `grid`.
+///
+/// This is the arrow clue of puzzles like Makaro. The value at `pos` itself is not constrained.
+/// `Arrow::Unspecified` adds no constraint.
+/// Returns false, without adding any constraint, if `dir` points outside of the grid.
+pub fn arrow_points_to_max<T>(solver: &mut Solver, grid: T, pos: (usize, usize), dir: Arrow) -> bool
+where
+    T: Operand<Output = Array2DImpl<CSPIntExpr>>,
+{
+    let grid = grid.as_expr_array_value();
+    let (h, w) = grid.shape();
+    let (y, x) = pos;
+
+    let target = match dir {
+        Arrow::Unspecified => return true,
+        Arrow::Up if y > 0 => (y - 1, x),
+        Arrow::Down if y < h - 1 => (y + 1, x),
+        Arrow::Left if x > 0 => (y, x - 1),
+        Arrow::Right if x < w - 1 => (y, x + 1),
+        _ => return false,
+    };
+
+    for (dy, dx) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let (ny, nx) = (y as i32 + dy, x as i32 + dx);
+        if ny < 0 || ny >= h as i32 || nx < 0 || nx >= w as i32 {
+            continue;
+        }
+        let neighbor = (ny as usize, nx as usize);
+        if neighbor != target {
+            solver.add_expr(grid.at(target).gt(grid.at(neighbor)));
+        }
+    }
+
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +344,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_arrow_points_to_max() {
+        let setup = |solver: &mut Solver| {
+            let grid = solver.int_var_2d((3, 3), 0, 3);
+            solver.add_expr(grid.at((0, 1)).eq(1));
+            solver.add_expr(grid.at((1, 0)).eq(2));
+            solver.add_expr(grid.at((2, 1)).eq(1));
+            grid
+        };
+
+        {
+            let mut solver = Solver::new();
+            let grid = &setup(&mut solver);
+            assert!(arrow_points_to_max(&mut solver, grid, (1, 1), Arrow::Right));
+            let answer = solver.solve();
+            assert!(answer.is_some());
+            assert_eq!(answer.unwrap().get(&grid.at((1, 2))), 3);
+        }
+        {
+            let mut solver = Solver::new();
+            let grid = &setup(&mut solver);
+            assert!(arrow_points_to_max(&mut solver, grid, (1, 1), Arrow::Up));
+            assert!(solver.solve().is_none());
+        }
+        {
+            let mut solver = Solver::new();
+            let grid = &setup(&mut solver);
+            assert!(!arrow_points_to_max(&mut solver, grid, (0, 1), Arrow::Up));
+        }
+    }
+
     #[test]
     fn test_partitions() {
         let result = partitions(9, 3, 1, 7);
@@ -197,4 +385,63 @@ mod tests {
         ];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_line_runs_spec() {
+        let count = |specs: &[RunSpec]| {
+            let mut solver = Solver::new();
+            let line = &solver.bool_var_1d(6);
+            solver.add_answer_key_bool(line);
+            line_runs_spec(&mut solver, line, specs);
+            solver.answer_iter().count()
+        };
+
+        assert_eq!(count(&[]), 1);
+        assert_eq!(count(&[RunSpec::Fixed(0)]), 1);
+        assert_eq!(count(&[RunSpec::Fixed(2), RunSpec::Fixed(1)]), 6);
+        assert_eq!(count(&[RunSpec::Fixed(6)]), 1);
+        assert_eq!(count(&[RunSpec::Fixed(4), RunSpec::Fixed(2)]), 0);
+        assert_eq!(count(&[RunSpec::AtLeast(3)]), 10);
+        assert_eq!(count(&[RunSpec::AtLeast(2), RunSpec::Fixed(1)]), 10);
+        assert_eq!(count(&[RunSpec::Unknown]), 63);
+        assert_eq!(count(&[RunSpec::Fixed(1), RunSpec::Unknown]), 26);
+    }
+
+    #[test]
+    #[should_panic(expected = "RunSpec::AtLeast requires a positive length")]
+    fn test_line_runs_spec_at_least_zero() {
+        let mut solver = Solver::new();
+        let line = &solver.bool_var_1d(6);
+        line_runs_spec(&mut solver, line, &[RunSpec::AtLeast(0)]);
+    }
+
+    #[test]
+    fn test_first_non_empty_eq() {
+        {
+            let mut solver = Solver::new();
+            let line = &solver.int_var_1d(4, 0, 2);
+            solver.add_answer_key_int(line);
+            solver.add_expr(first_non_empty_eq(line, 0, 2));
+            // 27 (first) + 9 (second) + 3 (third) + 1 (fourth)
+            assert_eq!(solver.answer_iter().count(), 40);
+        }
+        {
+            let mut solver = Solver::new();
+            let line = &solver.int_var_1d(4, 0, 2);
+            solver.add_answer_key_int(line);
+            solver.add_expr(line.at(0).eq(0));
+            solver.add_expr(line.at(1).eq(0));
+            solver.add_expr(line.at(2).eq(1));
+            solver.add_expr(first_non_empty_eq(line, 0, 1));
+            solver.add_expr(first_non_empty_eq(line.reverse(), 0, 2));
+            assert_eq!(solver.answer_iter().count(), 1);
+        }
+        {
+            let mut solver = Solver::new();
+            let line = &solver.int_var_1d(3, 0, 2);
+            solver.add_expr(line.eq(0));
+            solver.add_expr(first_non_empty_eq(line, 0, 1));
+            assert!(solver.solve().is_none());
+        }
+    }
 }
@@ -1,9 +1,9 @@
 use std::ops::Index;
 
 use super::solver::{
-    count_true, Array0DImpl, Array2DImpl, BoolVar, BoolVarArray1D, BoolVarArray2D, CSPBoolExpr,
-    CSPIntExpr, FromModel, FromOwnedPartialModel, GraphDivisionOptions, Model, Operand,
-    OwnedPartialModel, Solver, Value,
+    any, count_true, Array0DImpl, Array2DImpl, BoolExpr, BoolVar, BoolVarArray1D, BoolVarArray2D,
+    CSPBoolExpr, CSPIntExpr, FromModel, FromOwnedPartialModel, GraphDivisionMode,
+    GraphDivisionOptions, IntVar, Model, Operand, OwnedPartialModel, Solver, Value,
 };
 
 /// A struct for representing an undirected graph.
@@ -123,6 +123,26 @@ fn infer_graph_from_2d_array(shape: (usize, usize)) -> Graph {
     graph
 }
 
+/// Returns the graph on the cells of a grid of the given shape in which diagonally adjacent cells are connected.
+/// The cell `(y, x)` is the vertex `y * w + x`.
+///
+/// This is the graph used by [`diagonally_connected_2d`].
+pub fn infer_diagonal_graph_from_2d_array(shape: (usize, usize)) -> Graph {
+    let (h, w) = shape;
+    let mut graph = Graph::new(h * w);
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 && x < w - 1 {
+                graph.add_edge(y * w + x, (y + 1) * w + (x + 1));
+            }
+            if y < h - 1 && x > 0 {
+                graph.add_edge(y * w + x, (y + 1) * w + (x - 1));
+            }
+        }
+    }
+    graph
+}
+
 /// A struct for maintaining "edges" of a grid, including those on the outer border.
 ///
 /// Suppose we have a H * W grid. Then, each cell is surrounded by 2 horizontal edges and 2 vertical edges.
@@ -187,6 +207,86 @@ impl<T> InnerGridEdges<Vec<Vec<T>>> {
     }
 }
 
+/// A pair of orthogonally adjacent cells.
+pub type CellPair = ((usize, usize), (usize, usize));
+
+/// Precomputed adjacency information of a H * W grid.
+///
+/// This is useful for code which repeatedly enumerates orthogonally adjacent cells,
+/// such as custom constraints, to avoid redoing offset computations and bounds checks.
+pub struct GridTopology {
+    height: usize,
+    width: usize,
+    neighbors: Vec<Vec<(usize, usize)>>,
+    inner_edges: Vec<CellPair>,
+}
+
+impl GridTopology {
+    pub fn new(height: usize, width: usize) -> GridTopology {
+        let mut neighbors = vec![vec![]; height * width];
+        for y in 0..height {
+            for x in 0..width {
+                let list = &mut neighbors[y * width + x];
+                if y > 0 {
+                    list.push((y - 1, x));
+                }
+                if x > 0 {
+                    list.push((y, x - 1));
+                }
+                if y < height - 1 {
+                    list.push((y + 1, x));
+                }
+                if x < width - 1 {
+                    list.push((y, x + 1));
+                }
+            }
+        }
+
+        let mut inner_edges = vec![];
+        for y in 0..height.saturating_sub(1) {
+            for x in 0..width {
+                inner_edges.push(((y, x), (y + 1, x)));
+            }
+        }
+        for y in 0..height {
+            for x in 0..width.saturating_sub(1) {
+                inner_edges.push(((y, x), (y, x + 1)));
+            }
+        }
+
+        GridTopology {
+            height,
+            width,
+            neighbors,
+            inner_edges,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the cells orthogonally adjacent to `cell`, in the order of up, left, down and right.
+    pub fn neighbors(&self, cell: (usize, usize)) -> &[(usize, usize)] {
+        let (y, x) = cell;
+        assert!(y < self.height && x < self.width);
+        &self.neighbors[y * self.width + x]
+    }
+
+    /// Returns the pairs of cells on both sides of each inner edge.
+    ///
+    /// The edges are in the same order as the flattened `horizontal` and then `vertical`
+    /// of an `InnerGridEdges` of this grid, so that the `i`-th edge of `InnerGridEdges` can be
+    /// mapped to its cells by this list.
+    pub fn inner_edges(&self) -> &[CellPair] {
+        &self.inner_edges
+    }
+}
+
 /// Returns the list of connected components (rooms) from the given `InnerGridEdges`.
 ///
 /// Two cells are considered (directly) connected if they share an edge and are not separated by a border
@@ -213,51 +313,201 @@ impl<T> InnerGridEdges<Vec<Vec<T>>> {
 /// assert_eq!(rooms.len(), 4);
 /// ```
 pub fn borders_to_rooms(borders: &InnerGridEdges<Vec<Vec<bool>>>) -> Vec<Vec<(usize, usize)>> {
+    borders_to_rooms_with_ids(borders).0
+}
+
+/// Rooms of a grid together with the id of the room containing each cell.
+pub type RoomsWithIds = (Vec<Vec<(usize, usize)>>, Vec<Vec<usize>>);
+
+/// Same as `borders_to_rooms`, but additionally returns the id of the room containing each cell.
+///
+/// The id of a room is its index in the returned room list.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::{InnerGridEdges, borders_to_rooms_with_ids};
+/// let borders = InnerGridEdges {
+///     horizontal: vec![
+///         vec![true, false],
+///     ],
+///     vertical: vec![
+///         vec![false],
+///         vec![true],
+///     ],
+/// };
+/// let (rooms, room_id) = borders_to_rooms_with_ids(&borders);
+/// assert_eq!(rooms.len(), 2);
+/// assert_eq!(room_id, vec![vec![0, 0], vec![1, 0]]);
+/// ```
+pub fn borders_to_rooms_with_ids(borders: &InnerGridEdges<Vec<Vec<bool>>>) -> RoomsWithIds {
     fn visit(
         y: usize,
         x: usize,
         borders: &InnerGridEdges<Vec<Vec<bool>>>,
-        visited: &mut Vec<Vec<bool>>,
+        id: usize,
+        room_id: &mut Vec<Vec<usize>>,
         room: &mut Vec<(usize, usize)>,
     ) {
-        if visited[y][x] {
+        if room_id[y][x] != usize::MAX {
             return;
         }
-        visited[y][x] = true;
+        room_id[y][x] = id;
         room.push((y, x));
         if y > 0 && !borders.horizontal[y - 1][x] {
-            visit(y - 1, x, borders, visited, room);
+            visit(y - 1, x, borders, id, room_id, room);
         }
         if y < borders.horizontal.len() && !borders.horizontal[y][x] {
-            visit(y + 1, x, borders, visited, room);
+            visit(y + 1, x, borders, id, room_id, room);
         }
         if x > 0 && !borders.vertical[y][x - 1] {
-            visit(y, x - 1, borders, visited, room);
+            visit(y, x - 1, borders, id, room_id, room);
         }
         if x < borders.vertical[0].len() && !borders.vertical[y][x] {
-            visit(y, x + 1, borders, visited, room);
+            visit(y, x + 1, borders, id, room_id, room);
         }
     }
 
     let height = borders.vertical.len();
     let width = borders.vertical[0].len() + 1;
 
-    let mut visited = vec![vec![false; width]; height];
+    let mut room_id = vec![vec![usize::MAX; width]; height];
     let mut ret = vec![];
     for y in 0..height {
         for x in 0..width {
-            if visited[y][x] {
+            if room_id[y][x] != usize::MAX {
                 continue;
             }
             let mut room = vec![];
-            visit(y, x, borders, &mut visited, &mut room);
+            visit(y, x, borders, ret.len(), &mut room_id, &mut room);
             ret.push(room);
         }
     }
 
+    (ret, room_id)
+}
+
+/// Returns the list of edge-adjacent rooms of each room in the grid divided by `borders`.
+///
+/// Rooms are numbered as in `borders_to_rooms`, and each list is sorted in ascending order.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::{InnerGridEdges, room_adjacency};
+/// let borders = InnerGridEdges {
+///     horizontal: vec![
+///         vec![true, false],
+///     ],
+///     vertical: vec![
+///         vec![false],
+///         vec![true],
+///     ],
+/// };
+/// assert_eq!(room_adjacency(&borders), vec![vec![1], vec![0]]);
+/// ```
+pub fn room_adjacency(borders: &InnerGridEdges<Vec<Vec<bool>>>) -> Vec<Vec<usize>> {
+    let (rooms, room_id) = borders_to_rooms_with_ids(borders);
+    let height = room_id.len();
+    let width = room_id[0].len();
+
+    let mut ret = vec![vec![]; rooms.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let a = room_id[y][x];
+            if y < height - 1 && room_id[y + 1][x] != a {
+                ret[a].push(room_id[y + 1][x]);
+                ret[room_id[y + 1][x]].push(a);
+            }
+            if x < width - 1 && room_id[y][x + 1] != a {
+                ret[a].push(room_id[y][x + 1]);
+                ret[room_id[y][x + 1]].push(a);
+            }
+        }
+    }
+    for adj in &mut ret {
+        adj.sort();
+        adj.dedup();
+    }
+
     ret
 }
 
+/// Returns the grid vertices on the cycle formed by `is_line` in the order of traversal, or `None` if
+/// `is_line` is not fully determined or does not form exactly one cycle.
+///
+/// The traversal starts from the first vertex on the cycle in row-major order and proceeds to its right
+/// neighbor first. The starting vertex is not repeated at the end, so the length of the result equals the
+/// number of edges on the cycle.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::{GridEdges, trace_single_cycle};
+/// let is_line = GridEdges {
+///     horizontal: vec![vec![Some(true)], vec![Some(true)]],
+///     vertical: vec![vec![Some(true), Some(true)]],
+/// };
+/// assert_eq!(
+///     trace_single_cycle(&is_line),
+///     Some(vec![(0, 0), (0, 1), (1, 1), (1, 0)])
+/// );
+/// ```
+pub fn trace_single_cycle(is_line: &BoolGridEdgesIrrefutableFacts) -> Option<Vec<(usize, usize)>> {
+    let height = is_line.horizontal.len();
+    // An empty grid has no cycle
+    let width = is_line.horizontal.first()?.len() + 1;
+
+    let mut adjacent = vec![vec![vec![]; width]; height];
+    let mut num_edges = 0;
+    for y in 0..height {
+        for x in 0..(width - 1) {
+            if is_line.horizontal[y][x]? {
+                adjacent[y][x].push((y, x + 1));
+                adjacent[y][x + 1].push((y, x));
+                num_edges += 1;
+            }
+        }
+    }
+    for y in 0..(height - 1) {
+        for x in 0..width {
+            if is_line.vertical[y][x]? {
+                adjacent[y][x].push((y + 1, x));
+                adjacent[y + 1][x].push((y, x));
+                num_edges += 1;
+            }
+        }
+    }
+
+    if adjacent
+        .iter()
+        .flatten()
+        .any(|a| !a.is_empty() && a.len() != 2)
+    {
+        return None;
+    }
+    let start = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (y, x)))
+        .find(|&(y, x)| !adjacent[y][x].is_empty())?;
+
+    let mut ret = vec![start];
+    let mut prev = start;
+    let mut cur = adjacent[start.0][start.1][0];
+    while cur != start {
+        ret.push(cur);
+        let next = adjacent[cur.0][cur.1]
+            .iter()
+            .copied()
+            .find(|&p| p != prev)
+            .unwrap();
+        prev = cur;
+        cur = next;
+    }
+
+    // Other cycles are not reachable from `start`
+    if ret.len() != num_edges {
+        return None;
+    }
+    Some(ret)
+}
+
 pub type BoolGridEdges = GridEdges<BoolVarArray2D>;
 pub type BoolGridEdgesModel = GridEdges<Vec<Vec<bool>>>;
 pub type BoolGridEdgesIrrefutableFacts = GridEdges<Vec<Vec<Option<bool>>>>;
@@ -532,6 +782,110 @@ where
     active_vertices_connected(solver, is_active, &graph)
 }
 
+/// Adds a constraint that "active" cells in the given 2D grid are connected via diagonally adjacent cells.
+///
+/// This is similar to `active_vertices_connected_2d`, but two cells are adjacent iff they share only a corner.
+/// Orthogonally adjacent cells are not considered adjacent.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::diagonally_connected_2d;
+/// # use cspuz_rs::solver::Solver;
+/// let mut solver = Solver::new();
+/// let is_active = &solver.bool_var_2d((2, 3));
+/// solver.add_expr(is_active.at((0, 0)));
+/// solver.add_expr(is_active.at((0, 2)));
+/// solver.add_expr(!is_active.at((0, 1)));
+///
+/// diagonally_connected_2d(&mut solver, is_active);
+///
+/// let answer = solver.solve();
+/// assert!(answer.is_some());
+/// let answer = answer.unwrap();
+/// assert_eq!(answer.get(&is_active.at((1, 1))), true);
+/// ```
+pub fn diagonally_connected_2d<T>(solver: &mut Solver, is_active: T)
+where
+    T: Operand<Output = Array2DImpl<CSPBoolExpr>>,
+{
+    let is_active = is_active.as_expr_array_value();
+    let graph = infer_diagonal_graph_from_2d_array(is_active.shape());
+    active_vertices_connected(solver, is_active, &graph)
+}
+
+/// Returns an int variable equal to the number of connected components formed by "active" vertices in the given graph.
+///
+/// Each active vertex is labeled with the id of a "root" vertex of its component. Adjacent active vertices share the label,
+/// and every non-root active vertex has an active neighbor closer to the root, so that each component has exactly one root.
+pub fn count_connected_components<T>(solver: &mut Solver, is_active: T, graph: &Graph) -> IntVar
+where
+    T: IntoIterator,
+    <T as IntoIterator>::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
+{
+    let is_active: Vec<Value<Array0DImpl<CSPBoolExpr>>> = is_active
+        .into_iter()
+        .map(|x| x.as_expr_array_value())
+        .collect::<Vec<_>>();
+    let n = graph.n_vertices();
+    assert_eq!(is_active.len(), n);
+
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in &graph.edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+
+    let label = &solver.int_var_1d(n, 0, n as i32 - 1);
+    let dist = &solver.int_var_1d(n, 0, n as i32 - 1);
+    let is_root = &solver.bool_var_1d(n);
+
+    for u in 0..n {
+        solver.add_expr(is_root.at(u).iff(&is_active[u] & label.at(u).eq(u as i32)));
+        solver.add_expr(is_root.at(u).imp(dist.at(u).eq(0)));
+        solver.add_expr((!&is_active[u]).imp(label.at(u).eq(0) & dist.at(u).eq(0)));
+
+        let mut closer = vec![];
+        for &v in &adj[u] {
+            if u < v {
+                solver.add_expr((&is_active[u] & &is_active[v]).imp(label.at(u).eq(label.at(v))));
+            }
+            closer.push(&is_active[v] & dist.at(v).lt(dist.at(u)));
+        }
+        solver.add_expr((&is_active[u] & !is_root.at(u)).imp(any(closer)));
+    }
+
+    let num_components = solver.int_var(0, n as i32);
+    solver.add_expr(num_components.eq(is_root.count_true()));
+    num_components
+}
+
+/// Returns an int variable equal to the number of connected components formed by "active" cells in the given 2D grid.
+///
+/// This is a 2D version of `count_connected_components`, like `active_vertices_connected_2d`.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::count_connected_components_2d;
+/// # use cspuz_rs::solver::Solver;
+/// let mut solver = Solver::new();
+/// let is_active = &solver.bool_var_2d((1, 3));
+/// let num_components = count_connected_components_2d(&mut solver, is_active);
+/// solver.add_expr(num_components.eq(2));
+///
+/// let answer = solver.solve();
+/// assert!(answer.is_some());
+/// let answer = answer.unwrap();
+/// assert_eq!(answer.get(is_active), vec![vec![true, false, true]]);
+/// ```
+pub fn count_connected_components_2d<T>(solver: &mut Solver, is_active: T) -> IntVar
+where
+    T: Operand<Output = Array2DImpl<CSPBoolExpr>>,
+{
+    let is_active = is_active.as_expr_array_value();
+    let graph = infer_graph_from_2d_array(is_active.shape());
+    count_connected_components(solver, is_active, &graph)
+}
+
 /// Adds a constraint that "active" vertices in the given graph are connected via "active" edges.
 ///
 /// In other words, for any two active vertices, there is a path between them that consists only of active vertices
@@ -764,6 +1118,100 @@ pub fn single_cycle_grid_edges(solver: &mut Solver, grid_frame: &BoolGridEdges)
     is_passed_flat.reshape_as_2d((height + 1, width + 1))
 }
 
+/// Adds a constraint that `grid_frame` forms a single cycle passing through all grid vertices where `must_visit` is `true`.
+///
+/// This is `single_cycle_grid_edges` with the additional requirement on `must_visit`, which has the same shape as
+/// the grid vertices. Vertices where `must_visit` is `false` may or may not be on the cycle.
+/// The cycle may still be empty if `must_visit` is `false` everywhere.
+/// Returns a 2D array representing whether each grid vertex is on the cycle.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::{BoolGridEdges, single_cycle_covering};
+/// # use cspuz_rs::solver::Solver;
+/// let mut solver = Solver::new();
+/// let edges = BoolGridEdges::new(&mut solver, (1, 2));
+/// let must_visit = &solver.bool_var_2d((2, 3));
+/// solver.add_expr(must_visit.at((0, 2)));
+/// let _ = single_cycle_covering(&mut solver, &edges, must_visit);
+/// solver.add_expr(!edges.horizontal.at((0, 0)));
+///
+/// let answer = solver.solve();
+/// assert!(answer.is_some());
+/// let answer = answer.unwrap();
+/// assert_eq!(answer.get(&edges.vertical.at((0, 2))), true);
+/// assert_eq!(answer.get(&edges.vertical.at((0, 0))), false);
+/// ```
+pub fn single_cycle_covering<T>(
+    solver: &mut Solver,
+    grid_frame: &BoolGridEdges,
+    must_visit: T,
+) -> BoolVarArray2D
+where
+    T: Operand<Output = Array2DImpl<CSPBoolExpr>>,
+{
+    let must_visit = must_visit.as_expr_array_value();
+    let (height, width) = grid_frame.base_shape();
+    assert_eq!(must_visit.shape(), (height + 1, width + 1));
+
+    let is_passed = single_cycle_grid_edges(solver, grid_frame);
+    solver.add_expr(must_visit.imp(&is_passed));
+    is_passed
+}
+
+/// Adds a constraint that `grid_frame` forms a single open path between the two grid vertices in `endpoints`.
+///
+/// Unlike `single_cycle_grid_edges`, the vertices in `endpoints` have exactly one adjacent edge in the subset,
+/// while the other vertices have 0 or 2. Both endpoints must lie on the border of the grid.
+/// Returns a 2D array representing whether each grid vertex is on the path.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::{BoolGridEdges, single_path_grid_edges};
+/// # use cspuz_rs::solver::Solver;
+/// let mut solver = Solver::new();
+/// let edges = BoolGridEdges::new(&mut solver, (1, 2));
+/// solver.add_expr(edges.horizontal.at((0, 0)));
+/// let _ = single_path_grid_edges(&mut solver, &edges, &[(0, 0), (1, 2)]);
+///
+/// let answer = solver.solve();
+/// assert!(answer.is_some());
+/// let answer = answer.unwrap();
+/// assert_eq!(answer.get(&edges.vertical.at((0, 0))), false);
+/// ```
+pub fn single_path_grid_edges(
+    solver: &mut Solver,
+    grid_frame: &BoolGridEdges,
+    endpoints: &[(usize, usize)],
+) -> BoolVarArray2D {
+    let (height, width) = grid_frame.base_shape();
+    assert_eq!(endpoints.len(), 2);
+    assert_ne!(endpoints[0], endpoints[1]);
+    for &(y, x) in endpoints {
+        assert!(y == 0 || y == height || x == 0 || x == width);
+    }
+
+    let is_passed = &solver.bool_var_2d((height + 1, width + 1));
+    for y in 0..=height {
+        for x in 0..=width {
+            let degree = grid_frame.vertex_neighbors((y, x)).count_true();
+            if endpoints.contains(&(y, x)) {
+                solver.add_expr(is_passed.at((y, x)));
+                solver.add_expr(degree.eq(1));
+            } else {
+                solver.add_expr(degree.eq(is_passed.at((y, x)).ite(2, 0)));
+            }
+        }
+    }
+
+    // The active edges are connected and no vertex has degree more than 2, so they form a single path.
+    let (edges, graph) = grid_frame.representation();
+    let line_graph = graph.line_graph();
+    active_vertices_connected(solver, edges, &line_graph);
+
+    is_passed.clone()
+}
+
 /// Adds a constraint that `edges` represents a division of a 2D grid and `sizes` represents the sizes
 /// of the region in which each cell belongs.
 ///
@@ -812,6 +1260,35 @@ where
     graph_division_2d_with_options(solver, sizes, edges, GraphDivisionOptions::default());
 }
 
+/// Same as `graph_division_2d`, but encodes the constraint with the given `mode`
+/// instead of the mode set by `Solver::set_graph_division_mode` or `Config::graph_division_mode`
+/// (which is `GraphDivisionMode::Cpp` by default).
+///
+/// Which mode is faster depends on the puzzle and the instance size,
+/// so this allows puzzle authors to pick the encoding per constraint.
+/// The `pentominous_10x10` group of the `solver_configs` benchmark in `cspuz_rs_puzzles` compares the modes
+/// (`cargo bench -p cspuz_rs_puzzles --bench solver_configs -- pentominous_10x10`).
+/// `GraphDivisionMode::Cpp`, which propagates the division inside the SAT solver, stays the default;
+/// `GraphDivisionMode::Rust` runs the same propagation as a custom constraint on the Rust side.
+pub fn graph_division_2d_with_mode<T>(
+    solver: &mut Solver,
+    sizes: &T,
+    edges: &BoolInnerGridEdges,
+    mode: GraphDivisionMode,
+) where
+    T: Operand<Output = Array2DImpl<CSPIntExpr>> + Clone,
+{
+    graph_division_2d_with_options(
+        solver,
+        sizes,
+        edges,
+        GraphDivisionOptions {
+            mode: Some(mode),
+            ..GraphDivisionOptions::default()
+        },
+    );
+}
+
 pub fn graph_division_2d_with_options<T>(
     solver: &mut Solver,
     sizes: &T,
@@ -959,6 +1436,108 @@ pub fn crossable_single_cycle_grid_edges(
     (is_passed, is_cross)
 }
 
+/// Adds a constraint that no 2x2 block of cells in `grid` is entirely true.
+///
+/// Grids with fewer than 2 rows or columns are left unconstrained.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::no_2x2_block;
+/// # use cspuz_rs::solver::Solver;
+/// let mut solver = Solver::new();
+/// let is_black = &solver.bool_var_2d((2, 3));
+/// solver.add_expr(is_black.slice_fixed_y((0, ..)));
+/// solver.add_expr(is_black.at((1, 1)));
+///
+/// no_2x2_block(&mut solver, is_black);
+///
+/// let answer = solver.solve();
+/// assert!(answer.is_some());
+/// let answer = answer.unwrap();
+/// assert_eq!(answer.get(is_black), vec![
+///     vec![true, true, true],
+///     vec![false, true, false],
+/// ]);
+/// ```
+pub fn no_2x2_block<T>(solver: &mut Solver, grid: T)
+where
+    T: Operand<Output = Array2DImpl<CSPBoolExpr>>,
+{
+    let grid = grid.as_expr_array_value();
+    let (h, w) = grid.shape();
+    if h < 2 || w < 2 {
+        return;
+    }
+    solver.add_expr(!grid.conv2d_and((2, 2)));
+}
+
+/// Adds a constraint that no 2x2 block of cells in `grid` is entirely false.
+///
+/// This is the complement of `no_2x2_block`. Grids with fewer than 2 rows or columns are left unconstrained.
+pub fn no_2x2_empty<T>(solver: &mut Solver, grid: T)
+where
+    T: Operand<Output = Array2DImpl<CSPBoolExpr>>,
+{
+    let grid = grid.as_expr_array_value();
+    let (h, w) = grid.shape();
+    if h < 2 || w < 2 {
+        return;
+    }
+    solver.add_expr(grid.conv2d_or((2, 2)));
+}
+
+/// Adds a constraint that following the chosen successors from any vertex reaches `sink` without a cycle.
+///
+/// `successors[u]` lists the candidates `(v, cond)` for the successor of vertex `u`, where `v` is chosen iff `cond`
+/// holds. Each vertex other than `sink` must choose at least one successor, and `sink` must choose none.
+/// This is encoded by assigning each vertex a rank which strictly decreases along every chosen successor.
+///
+/// # Examples
+/// ```
+/// # use cspuz_rs::graph::flows_to_sink;
+/// # use cspuz_rs::solver::Solver;
+/// let mut solver = Solver::new();
+/// let to_right = &solver.bool_var_1d(3);
+/// let successors = (0..3)
+///     .map(|i| {
+///         let mut ret = vec![];
+///         if i > 0 {
+///             ret.push((i - 1, !to_right.at(i)));
+///         }
+///         if i < 2 {
+///             ret.push((i + 1, to_right.at(i).expr()));
+///         }
+///         ret
+///     })
+///     .collect::<Vec<_>>();
+///
+/// flows_to_sink(&mut solver, &successors, 2);
+///
+/// let answer = solver.solve();
+/// assert!(answer.is_some());
+/// let answer = answer.unwrap();
+/// assert_eq!(answer.get(&to_right.at(0)), true);
+/// assert_eq!(answer.get(&to_right.at(1)), true);
+/// ```
+pub fn flows_to_sink(solver: &mut Solver, successors: &[Vec<(usize, BoolExpr)>], sink: usize) {
+    let n = successors.len();
+    let rank = &solver.int_var_1d(n, 0, n as i32 - 1);
+    solver.add_expr(rank.at(sink).eq(0));
+
+    for u in 0..n {
+        if u == sink {
+            for (_, cond) in &successors[u] {
+                solver.add_expr(!cond);
+            }
+            continue;
+        }
+        solver.add_expr(any(successors[u].iter().map(|(_, cond)| cond)));
+        for (v, cond) in &successors[u] {
+            solver.add_expr(cond.imp(rank.at(*v).lt(rank.at(u))));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1004,4 +1583,295 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_borders_to_rooms_with_ids() {
+        // 0 0 1
+        // 2 0 1
+        // 2 2 2
+        let borders = InnerGridEdges {
+            horizontal: vec![vec![true, false, false], vec![false, true, true]],
+            vertical: vec![vec![false, true], vec![true, true], vec![false, false]],
+        };
+        let (rooms, room_id) = borders_to_rooms_with_ids(&borders);
+        assert_eq!(
+            rooms,
+            vec![
+                vec![(0, 0), (0, 1), (1, 1)],
+                vec![(0, 2), (1, 2)],
+                vec![(1, 0), (2, 0), (2, 1), (2, 2)],
+            ]
+        );
+        assert_eq!(room_id, vec![vec![0, 0, 1], vec![2, 0, 1], vec![2, 2, 2]]);
+    }
+
+    #[test]
+    fn test_room_adjacency() {
+        // 0 1 2
+        // 0 1 2
+        let borders = InnerGridEdges {
+            horizontal: vec![vec![false, false, false]],
+            vertical: vec![vec![true, true], vec![true, true]],
+        };
+        assert_eq!(room_adjacency(&borders), vec![vec![1], vec![0, 2], vec![1]]);
+
+        // 0 0 1
+        // 2 0 1
+        // 2 2 2
+        let borders = InnerGridEdges {
+            horizontal: vec![vec![true, false, false], vec![false, true, true]],
+            vertical: vec![vec![false, true], vec![true, true], vec![false, false]],
+        };
+        assert_eq!(
+            room_adjacency(&borders),
+            vec![vec![1, 2], vec![0, 2], vec![0, 1]]
+        );
+    }
+
+    #[test]
+    fn test_trace_single_cycle() {
+        let to_facts = |a: &[&[i32]]| {
+            a.iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&v| if v < 0 { None } else { Some(v == 1) })
+                        .collect()
+                })
+                .collect::<Vec<Vec<_>>>()
+        };
+
+        // +-+-+
+        // |   |
+        // + +-+
+        // | |
+        // +-+
+        let is_line = GridEdges {
+            horizontal: to_facts(&[&[1, 1], &[0, 1], &[1, 0]]),
+            vertical: to_facts(&[&[1, 0, 1], &[1, 1, 0]]),
+        };
+        let cycle = trace_single_cycle(&is_line);
+        assert_eq!(
+            cycle,
+            Some(vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (1, 1),
+                (2, 1),
+                (2, 0),
+                (1, 0)
+            ])
+        );
+        let num_edges = is_line
+            .horizontal
+            .iter()
+            .chain(&is_line.vertical)
+            .flatten()
+            .filter(|&&e| e == Some(true))
+            .count();
+        assert_eq!(cycle.unwrap().len(), num_edges);
+
+        // undetermined edge
+        let is_line = GridEdges {
+            horizontal: to_facts(&[&[1, 1], &[0, -1], &[1, 0]]),
+            vertical: to_facts(&[&[1, 0, 1], &[1, 1, 0]]),
+        };
+        assert_eq!(trace_single_cycle(&is_line), None);
+
+        // two cycles
+        let is_line = GridEdges {
+            horizontal: to_facts(&[&[1, 0, 1], &[1, 0, 1]]),
+            vertical: to_facts(&[&[1, 1, 1, 1]]),
+        };
+        assert_eq!(trace_single_cycle(&is_line), None);
+
+        // no cycle
+        let is_line = GridEdges {
+            horizontal: to_facts(&[&[0, 0], &[0, 0]]),
+            vertical: to_facts(&[&[0, 0, 0]]),
+        };
+        assert_eq!(trace_single_cycle(&is_line), None);
+
+        // a path is not a cycle
+        let is_line = GridEdges {
+            horizontal: to_facts(&[&[1, 1], &[0, 0]]),
+            vertical: to_facts(&[&[0, 0, 1]]),
+        };
+        assert_eq!(trace_single_cycle(&is_line), None);
+
+        // empty grid
+        let is_line = GridEdges {
+            horizontal: vec![],
+            vertical: vec![],
+        };
+        assert_eq!(trace_single_cycle(&is_line), None);
+    }
+
+    #[test]
+    fn test_grid_topology() {
+        let topology = GridTopology::new(2, 3);
+        assert_eq!(topology.height(), 2);
+        assert_eq!(topology.width(), 3);
+
+        assert_eq!(topology.neighbors((0, 0)), &[(1, 0), (0, 1)]);
+        assert_eq!(topology.neighbors((0, 1)), &[(0, 0), (1, 1), (0, 2)]);
+        assert_eq!(topology.neighbors((1, 2)), &[(0, 2), (1, 1)]);
+
+        for y in 0..2 {
+            for x in 0..3 {
+                for &(ny, nx) in topology.neighbors((y, x)) {
+                    assert_eq!(y.abs_diff(ny) + x.abs_diff(nx), 1);
+                    assert!(topology.neighbors((ny, nx)).contains(&(y, x)));
+                }
+            }
+        }
+
+        assert_eq!(
+            topology.inner_edges(),
+            &[
+                ((0, 0), (1, 0)),
+                ((0, 1), (1, 1)),
+                ((0, 2), (1, 2)),
+                ((0, 0), (0, 1)),
+                ((0, 1), (0, 2)),
+                ((1, 0), (1, 1)),
+                ((1, 1), (1, 2)),
+            ]
+        );
+
+        let single = GridTopology::new(1, 1);
+        assert!(single.neighbors((0, 0)).is_empty());
+        assert!(single.inner_edges().is_empty());
+    }
+
+    #[test]
+    fn test_graph_division_2d_with_mode() {
+        for mode in [GraphDivisionMode::Cpp, GraphDivisionMode::Rust] {
+            let mut solver = Solver::new();
+            let sizes = &solver.int_var_2d((2, 2), 2, 2);
+            let edges = &BoolInnerGridEdges::new(&mut solver, (2, 2));
+            solver.add_answer_key_bool(&edges.horizontal);
+            solver.add_answer_key_bool(&edges.vertical);
+            graph_division_2d_with_mode(&mut solver, sizes, edges, mode);
+
+            // Two vertical dominoes or two horizontal dominoes
+            assert_eq!(solver.answer_iter().count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_diagonally_connected_2d() {
+        let is_satisfiable = |pattern: [[bool; 2]; 2]| {
+            let mut solver = Solver::new();
+            let is_active = &solver.bool_var_2d((2, 2));
+            diagonally_connected_2d(&mut solver, is_active);
+            for y in 0..2 {
+                for x in 0..2 {
+                    solver.add_expr(is_active.at((y, x)).iff(pattern[y][x]));
+                }
+            }
+            solver.solve().is_some()
+        };
+
+        assert!(is_satisfiable([[true, false], [false, true]]));
+        assert!(is_satisfiable([[false, true], [true, false]]));
+        assert!(!is_satisfiable([[true, true], [false, false]]));
+        assert!(!is_satisfiable([[true, false], [true, false]]));
+    }
+
+    #[test]
+    fn test_count_connected_components_2d() {
+        let pattern = [
+            [true, true, false, false],
+            [true, false, false, true],
+            [false, false, true, true],
+            [false, false, true, false],
+        ];
+
+        let mut solver = Solver::new();
+        let is_active = &solver.bool_var_2d((4, 4));
+        let num_components = &count_connected_components_2d(&mut solver, is_active);
+        for y in 0..4 {
+            for x in 0..4 {
+                solver.add_expr(is_active.at((y, x)).iff(pattern[y][x]));
+            }
+        }
+
+        let answer = solver.solve();
+        assert!(answer.is_some());
+        assert_eq!(answer.unwrap().get(num_components), 2);
+    }
+
+    #[test]
+    fn test_single_path_grid_edges() {
+        let count = |avoid_center: bool| {
+            let mut solver = Solver::new();
+            let edges = BoolGridEdges::new(&mut solver, (2, 2));
+            solver.add_answer_key_bool(&edges.horizontal);
+            solver.add_answer_key_bool(&edges.vertical);
+            let is_passed = &single_path_grid_edges(&mut solver, &edges, &[(0, 0), (2, 2)]);
+            if avoid_center {
+                solver.add_expr(!is_passed.at((1, 1)));
+            }
+            solver.answer_iter().count()
+        };
+
+        // Self-avoiding paths between opposite corners of a 3x3 lattice
+        assert_eq!(count(false), 12);
+        assert_eq!(count(true), 2);
+    }
+
+    #[test]
+    fn test_flows_to_sink() {
+        // Vertices 0 and 1 point to each other, so neither of them reaches the sink 2.
+        let mut solver = Solver::new();
+        let b = &solver.bool_var_1d(2);
+        let successors = vec![vec![(1, b.at(0).expr())], vec![(0, b.at(1).expr())], vec![]];
+        flows_to_sink(&mut solver, &successors, 2);
+        assert!(solver.solve().is_none());
+
+        // Once 1 may point to the sink instead, the cycle is broken.
+        let mut solver = Solver::new();
+        let b = &solver.bool_var_1d(3);
+        let successors = vec![
+            vec![(1, b.at(0).expr())],
+            vec![(0, b.at(1).expr()), (2, b.at(2).expr())],
+            vec![],
+        ];
+        flows_to_sink(&mut solver, &successors, 2);
+        solver.add_expr(b.at(1) ^ b.at(2));
+        let answer = solver.solve();
+        assert!(answer.is_some());
+        let answer = answer.unwrap();
+        assert!(!answer.get(&b.at(1)));
+        assert!(answer.get(&b.at(2)));
+    }
+
+    #[test]
+    fn test_no_2x2_block() {
+        let mut solver = Solver::new();
+        let grid = &solver.bool_var_2d((3, 3));
+        no_2x2_block(&mut solver, grid);
+        solver.add_expr(grid.at((1, 1)));
+        solver.add_expr(grid.at((1, 2)));
+        solver.add_expr(grid.at((2, 1)));
+        solver.add_expr(grid.at((2, 2)));
+        assert!(solver.solve().is_none());
+
+        let mut solver = Solver::new();
+        let grid = &solver.bool_var_2d((3, 3));
+        no_2x2_empty(&mut solver, grid);
+        solver.add_expr(!grid.at((0, 0)));
+        solver.add_expr(!grid.at((0, 1)));
+        solver.add_expr(!grid.at((1, 0)));
+        solver.add_expr(!grid.at((1, 1)));
+        assert!(solver.solve().is_none());
+
+        let mut solver = Solver::new();
+        let grid = &solver.bool_var_2d((1, 3));
+        no_2x2_block(&mut solver, grid);
+        solver.add_expr(grid);
+        assert!(solver.solve().is_some());
+    }
 }
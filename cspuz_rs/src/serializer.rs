@@ -249,6 +249,18 @@ impl<T> Choice<T> {
     }
 }
 
+/// Builds a [`Choice`] from a list of combinators, boxing each of them.
+///
+/// `choice![A, B]` is equivalent to `Choice::new(vec![Box::new(A), Box::new(B)])`.
+#[macro_export]
+macro_rules! choice {
+    ($($c:expr),* $(,)?) => {
+        $crate::serializer::Choice::new(::std::vec![$(::std::boxed::Box::new($c)),*])
+    };
+}
+
+pub use crate::choice;
+
 impl<T> Combinator<T> for Choice<T> {
     fn serialize(&self, ctx: &Context, input: &[T]) -> Option<(usize, Vec<u8>)> {
         self.choices
@@ -298,6 +310,54 @@ impl<T: Clone + PartialEq> Combinator<T> for Dict<T> {
     }
 }
 
+/// Combinator for a clue which is either a non-negative integer encoded by `base` or a "hole" encoded by
+/// `marker`. A hole is represented by `Some(-1)`.
+///
+/// Empty cells (`None`) are not handled, so this is usually put in a `Choice` together with `Spaces`.
+pub struct ClueOrHole<C> {
+    base: C,
+    marker: Vec<u8>,
+}
+
+impl<C> ClueOrHole<C> {
+    pub fn new<A>(base: C, marker: A) -> ClueOrHole<C>
+    where
+        Vec<u8>: From<A>,
+    {
+        ClueOrHole {
+            base,
+            marker: Vec::<u8>::from(marker),
+        }
+    }
+}
+
+impl<C> Combinator<Option<i32>> for ClueOrHole<C>
+where
+    C: Combinator<i32>,
+{
+    fn serialize(&self, ctx: &Context, input: &[Option<i32>]) -> Option<(usize, Vec<u8>)> {
+        match *input.first()? {
+            Some(-1) => Some((1, self.marker.clone())),
+            Some(n) if n >= 0 => {
+                let (_, data) = self.base.serialize(ctx, &[n])?;
+                Some((1, data))
+            }
+            _ => None,
+        }
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Option<i32>>)> {
+        if input.starts_with(&self.marker) {
+            return Some((self.marker.len(), vec![Some(-1)]));
+        }
+        let (n_read, data) = self.base.deserialize(ctx, input)?;
+        if data.iter().any(|&n| n < 0) {
+            return None;
+        }
+        Some((n_read, data.into_iter().map(Some).collect()))
+    }
+}
+
 pub struct MaybeSkip<C>(Vec<u8>, C);
 
 impl<C> MaybeSkip<C> {
@@ -367,6 +427,67 @@ where
     }
 }
 
+/// Combinator for a pair of a header and a body separated by `separator` (e.g. `3/...`).
+///
+/// The header is deserialized first, so it must not consume the separator itself.
+pub struct HeaderThen<H, B> {
+    header: H,
+    separator: Vec<u8>,
+    body: B,
+}
+
+impl<H, B> HeaderThen<H, B> {
+    pub fn new<A>(header: H, separator: A, body: B) -> HeaderThen<H, B>
+    where
+        Vec<u8>: From<A>,
+    {
+        HeaderThen {
+            header,
+            separator: Vec::<u8>::from(separator),
+            body,
+        }
+    }
+}
+
+impl<H, B, P, Q> Combinator<(P, Q)> for HeaderThen<H, B>
+where
+    H: Combinator<P>,
+    B: Combinator<Q>,
+    P: Clone,
+    Q: Clone,
+{
+    fn serialize(&self, ctx: &Context, input: &[(P, Q)]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let (p, q) = input[0].clone();
+        let (_, mut ret) = self.header.serialize(ctx, &[p])?;
+        ret.extend(self.separator.clone());
+        let (_, app) = self.body.serialize(ctx, &[q])?;
+        ret.extend(app);
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<(P, Q)>)> {
+        let mut sequencer = Sequencer::new(input);
+        let p = sequencer.deserialize_one_elem(ctx, &self.header)?;
+
+        let rest = &input[sequencer.n_read()..];
+        if !rest.starts_with(&self.separator) {
+            return None;
+        }
+        let rest = &rest[self.separator.len()..];
+
+        let mut sequencer_body = Sequencer::new(rest);
+        let q = sequencer_body.deserialize_one_elem(ctx, &self.body)?;
+
+        Some((
+            sequencer.n_read() + self.separator.len() + sequencer_body.n_read(),
+            vec![(p, q)],
+        ))
+    }
+}
+
 pub struct Spaces<T: Clone + PartialEq> {
     space: T,
     minimum: i32,
@@ -656,9 +777,13 @@ impl Combinator<i32> for MultiDigit {
         let mut v = 0;
         let mut n_read = 0;
         for i in 0..self.num_digits {
-            if i >= input.len() || !(0 <= input[i] && input[i] < self.base) {
+            if i >= input.len() {
                 break;
             }
+            if !(0 <= input[i] && input[i] < self.base) {
+                // Packing the preceding digits alone would silently drop this value
+                return None;
+            }
             n_read += 1;
             v += input[i] * self.base.pow((self.num_digits - 1 - i) as u32);
         }
@@ -951,6 +1076,179 @@ where
     }
 }
 
+/// Grid combinator which serializes only the cells where `mask` is `true`, in row-major order.
+///
+/// Masked-out cells are not present in the serialized data; they are deserialized as
+/// `masked_value`, and serialization fails unless they hold `masked_value`.
+pub struct MaskedGrid<S, T> {
+    base_serializer: S,
+    mask: Vec<Vec<bool>>,
+    masked_value: T,
+}
+
+impl<S, T> MaskedGrid<S, T> {
+    pub fn new(base_serializer: S, mask: Vec<Vec<bool>>, masked_value: T) -> MaskedGrid<S, T> {
+        MaskedGrid {
+            base_serializer,
+            mask,
+            masked_value,
+        }
+    }
+
+    fn num_active_cells(&self) -> usize {
+        self.mask
+            .iter()
+            .map(|row| row.iter().filter(|&&b| b).count())
+            .sum()
+    }
+
+    fn masked_context(&self, ctx: &Context) -> Context {
+        let height = self.mask.len();
+        let width = if height > 0 { self.mask[0].len() } else { 0 };
+        Context {
+            height: Some(height),
+            width: Some(width),
+            ..*ctx
+        }
+    }
+}
+
+impl<S, T> Combinator<Vec<Vec<T>>> for MaskedGrid<S, T>
+where
+    S: Combinator<T>,
+    T: Clone + PartialEq,
+{
+    fn serialize(&self, ctx: &Context, input: &[Vec<Vec<T>>]) -> Option<(usize, Vec<u8>)> {
+        if input.len() == 0 {
+            return None;
+        }
+
+        let data = &input[0];
+        if data.len() != self.mask.len() {
+            return None;
+        }
+
+        let mut input_flat = vec![];
+        for (row, mask_row) in data.iter().zip(&self.mask) {
+            if row.len() != mask_row.len() {
+                return None;
+            }
+            for (v, &active) in row.iter().zip(mask_row) {
+                if active {
+                    input_flat.push(v.clone());
+                } else if *v != self.masked_value {
+                    return None;
+                }
+            }
+        }
+
+        let ctx = self.masked_context(ctx);
+        let seq_combinator = Seq::new(&self.base_serializer, self.num_active_cells());
+        let (n_read, ret) = seq_combinator.serialize(&ctx, &[input_flat])?;
+        if n_read == 1 {
+            Some((1, ret))
+        } else {
+            None
+        }
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Vec<Vec<T>>>)> {
+        let ctx = self.masked_context(ctx);
+        let n_active = self.num_active_cells();
+
+        let seq_combinator = Seq::new(&self.base_serializer, n_active);
+        let (n_read, ret_flat) = seq_combinator.deserialize(&ctx, input)?;
+        assert_eq!(ret_flat.len(), 1);
+        let ret_flat = ret_flat.into_iter().next().unwrap();
+        if ret_flat.len() != n_active {
+            return None;
+        }
+
+        let mut active_cells = ret_flat.into_iter();
+        let ret = self
+            .mask
+            .iter()
+            .map(|mask_row| {
+                mask_row
+                    .iter()
+                    .map(|&active| {
+                        if active {
+                            active_cells.next().unwrap()
+                        } else {
+                            self.masked_value.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        Some((n_read, vec![ret]))
+    }
+}
+
+/// Grid combinator for boolean grids which encodes the positions of `true` cells by run lengths.
+///
+/// Cells are scanned in row-major order. Each `true` cell is written as the number of `false` cells
+/// preceding it (since the previous `true` cell) in base 36, where each `.` stands for 36 additional
+/// `false` cells. Trailing `false` cells are omitted. The size of the grid is taken from the context.
+pub struct BooleanRunLength;
+
+impl Combinator<Vec<Vec<bool>>> for BooleanRunLength {
+    fn serialize(&self, ctx: &Context, input: &[Vec<Vec<bool>>]) -> Option<(usize, Vec<u8>)> {
+        if input.len() == 0 {
+            return None;
+        }
+
+        let data = &input[0];
+        let height = ctx.height.unwrap();
+        assert_eq!(data.len(), height);
+        let width = ctx.width.unwrap();
+
+        let mut ret = vec![];
+        let mut n_spaces = 0;
+        for row in data {
+            assert_eq!(row.len(), width);
+            for &b in row {
+                if !b {
+                    n_spaces += 1;
+                    continue;
+                }
+                while n_spaces >= 36 {
+                    ret.push('.' as u8);
+                    n_spaces -= 36;
+                }
+                ret.push(to_base36(n_spaces));
+                n_spaces = 0;
+            }
+        }
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Vec<Vec<bool>>>)> {
+        let height = ctx.height.unwrap();
+        let width = ctx.width.unwrap();
+
+        let mut ret = vec![vec![false; width]; height];
+        let mut pos = 0;
+        let mut n_read = 0;
+        for &c in input {
+            if c == '.' as u8 {
+                pos += 36;
+            } else if let Some(n) = from_base36(c) {
+                pos += n as usize;
+                if pos >= height * width {
+                    return None;
+                }
+                ret[pos / width][pos % width] = true;
+                pos += 1;
+            } else {
+                break;
+            }
+            n_read += 1;
+        }
+        Some((n_read, vec![ret]))
+    }
+}
+
 pub struct Size<S> {
     base_serializer: S,
     offset: i32,
@@ -1674,10 +1972,17 @@ pub fn get_kudamono_url_info_detailed<'a>(url: &'a str) -> Option<BTreeMap<Strin
     Some(ret)
 }
 
+/// Parses a Kudamono dimension string of the form `{width}x{height}` into `(width, height)`.
+///
+/// Returns `None` if the string is malformed or either dimension is zero, so that callers can
+/// safely compute `width - 1` and `height - 1`.
 pub fn parse_kudamono_dimension(dim: &str) -> Option<(usize, usize)> {
     let x = dim.find("x")?;
     let width = dim[..x].parse::<usize>().ok()?;
     let height = dim[(x + 1)..].parse::<usize>().ok()?;
+    if width == 0 || height == 0 {
+        return None;
+    }
     Some((width, height))
 }
 
@@ -1783,6 +2088,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_clue_or_hole() {
+        let ctx = &Context::new();
+        let combinator = ClueOrHole::new(HexInt, ".");
+
+        assert_eq!(combinator.serialize(ctx, &[]), None);
+        assert_eq!(
+            combinator.serialize(ctx, &[Some(-1), Some(3)]),
+            Some((1, Vec::from(".")))
+        );
+        assert_eq!(
+            combinator.serialize(ctx, &[Some(12), Some(-1)]),
+            Some((1, Vec::from("c")))
+        );
+        assert_eq!(combinator.serialize(ctx, &[None]), None);
+        assert_eq!(combinator.serialize(ctx, &[Some(-2)]), None);
+
+        assert_eq!(combinator.deserialize(ctx, "".as_bytes()), None);
+        assert_eq!(
+            combinator.deserialize(ctx, ".c".as_bytes()),
+            Some((1, vec![Some(-1)]))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "c.".as_bytes()),
+            Some((1, vec![Some(12)]))
+        );
+        assert_eq!(combinator.deserialize(ctx, "g".as_bytes()), None);
+    }
+
     #[test]
     fn test_spaces() {
         let ctx = &Context::new();
@@ -1993,6 +2327,41 @@ mod tests {
         assert_eq!(combinator.deserialize(ctx, "(42".as_bytes()), None);
     }
 
+    #[test]
+    fn test_header_then() {
+        let ctx = &Context::new();
+        let combinator = HeaderThen::new(DecInt, "/", HexInt);
+
+        assert_eq!(combinator.serialize(ctx, &[]), None);
+        assert_eq!(
+            combinator.serialize(ctx, &[(12, 10), (3, 4)]),
+            Some((1, Vec::from("12/a")))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "12/a3".as_bytes()),
+            Some((4, vec![(12, 10)]))
+        );
+        assert_eq!(combinator.deserialize(ctx, "".as_bytes()), None);
+        assert_eq!(combinator.deserialize(ctx, "12a".as_bytes()), None);
+        assert_eq!(combinator.deserialize(ctx, "12/".as_bytes()), None);
+        assert_eq!(combinator.deserialize(ctx, "/a".as_bytes()), None);
+
+        // The body is not required to be delimited by a separator.
+        let combinator = HeaderThen::new(DecInt, ",", UnlimitedSeq::new(HexInt));
+        assert_eq!(
+            combinator.serialize(ctx, &[(3, vec![1, 2, 15])]),
+            Some((1, Vec::from("3,12f")))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "3,12f".as_bytes()),
+            Some((5, vec![(3, vec![1, 2, 15])]))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "3,".as_bytes()),
+            Some((2, vec![(3, vec![])]))
+        );
+    }
+
     #[test]
     fn test_multi_digit() {
         let ctx = &Context::new();
@@ -2007,11 +2376,9 @@ mod tests {
             combinator.serialize(ctx, &[2, 0]),
             Some((2, Vec::from("i")))
         );
-        assert_eq!(
-            combinator.serialize(ctx, &[1, 0, 3]),
-            Some((2, Vec::from("9")))
-        );
+        assert_eq!(combinator.serialize(ctx, &[1, 0, 3]), None);
         assert_eq!(combinator.serialize(ctx, &[3, 1, 0]), None);
+        assert_eq!(combinator.serialize(ctx, &[0, -1, 0]), None);
 
         assert_eq!(combinator.deserialize(ctx, "".as_bytes()), None);
         assert_eq!(
@@ -2029,6 +2396,82 @@ mod tests {
         assert_eq!(combinator.deserialize(ctx, "r".as_bytes()), None);
     }
 
+    #[test]
+    fn test_multi_digit_out_of_range() {
+        let ctx = &Context::sized(2, 3);
+
+        let combinator = MultiDigit::new(12, 1);
+        assert_eq!(combinator.serialize(ctx, &[11]), Some((1, Vec::from("b"))));
+        assert_eq!(combinator.serialize(ctx, &[12]), None);
+
+        let combinator = ContextBasedGrid::new(MultiDigit::new(2, 5));
+        assert_eq!(
+            combinator.serialize(ctx, &[vec![vec![1, 0, 1], vec![0, 1, 1]]]),
+            Some((1, Vec::from("lg")))
+        );
+        assert_eq!(
+            combinator.serialize(ctx, &[vec![vec![1, 0, 1], vec![0, 2, 1]]]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_masked_grid() {
+        let ctx = &Context::new();
+        let combinator = MaskedGrid::new(
+            HexInt,
+            vec![vec![true, false, true], vec![true, true, false]],
+            -1,
+        );
+
+        let grid = vec![vec![1, -1, 2], vec![3, 12, -1]];
+        assert_eq!(
+            combinator.serialize(ctx, &[grid.clone()]),
+            Some((1, Vec::from("123c")))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "123cx".as_bytes()),
+            Some((4, vec![grid]))
+        );
+
+        // masked-out cells must hold the masked value
+        assert_eq!(
+            combinator.serialize(ctx, &[vec![vec![1, 0, 2], vec![3, 12, -1]]]),
+            None
+        );
+        assert_eq!(combinator.deserialize(ctx, "123".as_bytes()), None);
+    }
+
+    #[test]
+    fn test_boolean_run_length() {
+        let ctx = &Context::sized(3, 20);
+        let combinator = BooleanRunLength;
+
+        let mut grid = vec![vec![false; 20]; 3];
+        grid[0][0] = true;
+        grid[0][12] = true;
+        grid[2][19] = true;
+        assert_eq!(
+            combinator.serialize(ctx, &[grid.clone()]),
+            Some((1, Vec::from("0b.a")))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "0b.a/".as_bytes()),
+            Some((4, vec![grid]))
+        );
+
+        // trailing `false` cells are omitted
+        let grid = vec![vec![false; 20]; 3];
+        assert_eq!(
+            combinator.serialize(ctx, &[grid.clone()]),
+            Some((1, vec![]))
+        );
+        assert_eq!(combinator.deserialize(ctx, &[]), Some((0, vec![grid])));
+
+        // the position of a `true` cell must be within the grid
+        assert_eq!(combinator.deserialize(ctx, ".o".as_bytes()), None);
+    }
+
     #[test]
     fn test_optionalize() {
         let ctx = &Context::new();
@@ -2093,6 +2536,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_choice_macro() {
+        let ctx = &Context::new();
+        let combinator = choice![Optionalize::new(HexInt), Spaces::new(None, 'g'),];
+
+        assert_eq!(
+            combinator.serialize(ctx, &[None, None, Some(5)]),
+            Some((2, Vec::from("h")))
+        );
+        assert_eq!(
+            combinator.deserialize(ctx, "c".as_bytes()),
+            Some((1, vec![Some(12)]))
+        );
+    }
+
     #[test]
     fn test_seq() {
         let ctx = &Context::new();
@@ -2351,6 +2809,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_kudamono_dimension() {
+        assert_eq!(parse_kudamono_dimension("14x13"), Some((14, 13)));
+        assert_eq!(parse_kudamono_dimension("1x1"), Some((1, 1)));
+        assert_eq!(parse_kudamono_dimension("0x0"), None);
+        assert_eq!(parse_kudamono_dimension("5x0"), None);
+        assert_eq!(parse_kudamono_dimension("0x5"), None);
+        assert_eq!(parse_kudamono_dimension("5"), None);
+        assert_eq!(parse_kudamono_dimension("5x"), None);
+    }
+
     #[test]
     fn test_kudamono_url_info() {
         {
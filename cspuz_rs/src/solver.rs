@@ -1,4 +1,5 @@
 use std::ops::{Add, BitAnd, BitOr, BitXor, Bound, Not, RangeBounds, Sub};
+use std::time::Instant;
 
 use crate::items::Arrow;
 pub use cspuz_core::config::{Config, GraphDivisionMode};
@@ -11,7 +12,7 @@ use cspuz_core::custom_constraints::PropagatorGenerator;
 use cspuz_core::domain::Domain;
 use cspuz_core::integration::IntegratedSolver;
 use cspuz_core::integration::Model as IntegratedModel;
-pub use cspuz_core::integration::PerfStats;
+pub use cspuz_core::integration::{InstanceStats, PerfStats};
 pub use cspuz_core::propagators::graph_division::GraphDivisionOptions;
 
 #[derive(Clone)]
@@ -928,6 +929,51 @@ where
     ret
 }
 
+fn abs_diff(a: CSPIntExpr, b: CSPIntExpr) -> IntExpr {
+    if let (CSPIntExpr::Const(a), CSPIntExpr::Const(b)) = (&a, &b) {
+        return int_constant((a - b).abs());
+    }
+    let a = Value(Array0DImpl { data: a });
+    let b = Value(Array0DImpl { data: b });
+    a.ge(&b).ite(&a - &b, &b - &a)
+}
+
+/// Adds a constraint that the Manhattan distance between the cells `pos_a` and `pos_b` (given as `(y, x)`) is `n`.
+///
+/// Each coordinate may be either a constant or a variable. If both positions are constants, no expression is added
+/// to the solver unless the distance differs from `n`, in which case the solver becomes unsatisfiable.
+///
+/// # Example
+/// ```
+/// # use cspuz_rs::solver::{manhattan_distance_eq, Solver};
+/// let mut solver = Solver::new();
+/// let y = &solver.int_var(0, 4);
+/// let x = &solver.int_var(0, 4);
+/// manhattan_distance_eq(&mut solver, (y, x), (0, 0), 8);
+///
+/// let model = solver.solve().unwrap();
+/// assert_eq!(model.get(y), 4);
+/// assert_eq!(model.get(x), 4);
+/// ```
+pub fn manhattan_distance_eq<A, B, C, D>(solver: &mut Solver, pos_a: (A, B), pos_b: (C, D), n: i32)
+where
+    A: Operand<Output = Array0DImpl<CSPIntExpr>>,
+    B: Operand<Output = Array0DImpl<CSPIntExpr>>,
+    C: Operand<Output = Array0DImpl<CSPIntExpr>>,
+    D: Operand<Output = Array0DImpl<CSPIntExpr>>,
+{
+    let dy = abs_diff(pos_a.0.as_expr_array().data, pos_b.0.as_expr_array().data);
+    let dx = abs_diff(pos_a.1.as_expr_array().data, pos_b.1.as_expr_array().data);
+
+    if let (CSPIntExpr::Const(dy), CSPIntExpr::Const(dx)) = (&dy.0.data, &dx.0.data) {
+        if dy + dx != n {
+            solver.add_expr(FALSE);
+        }
+        return;
+    }
+    solver.add_expr((dy + dx).eq(n));
+}
+
 impl<T> Value<Array2DImpl<T>>
 where
     T: Clone,
@@ -1075,10 +1121,164 @@ macro_rules! impl_deref_var {
 impl_deref_var!(CSPBoolVar);
 impl_deref_var!(CSPIntVar);
 
+/// A reference to an answer key variable, represented by its index among the answer keys of the same type.
+///
+/// Answer keys are indexed in the order of registration. Arrays are registered in row-major order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnswerKeyRef {
+    Bool(usize),
+    Int(usize),
+}
+
+/// A value of an answer key variable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnswerKeyValue {
+    Bool(bool),
+    Int(i32),
+}
+
+/// An identifier of a group of constraints, returned by `Solver::begin_group`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ConstraintId(pub usize);
+
+enum RecordedItem {
+    BoolVar,
+    IntVar(Domain),
+    IntVarFromList(Vec<i32>),
+    PrenormalizeVar(CSPBoolVar),
+    Constraint(Option<ConstraintId>, Stmt),
+}
+
+fn clone_stmt(stmt: &Stmt) -> Option<Stmt> {
+    let ret = match stmt {
+        Stmt::Expr(e) => Stmt::Expr(e.clone()),
+        Stmt::AllDifferent(exprs) => Stmt::AllDifferent(exprs.clone()),
+        Stmt::ActiveVerticesConnected(vertices, edges) => {
+            Stmt::ActiveVerticesConnected(vertices.clone(), edges.clone())
+        }
+        Stmt::Circuit(exprs) => Stmt::Circuit(exprs.clone()),
+        Stmt::ExtensionSupports(exprs, supports) => {
+            Stmt::ExtensionSupports(exprs.clone(), supports.clone())
+        }
+        Stmt::GraphDivision(sizes, edges, edge_values, opts) => {
+            Stmt::GraphDivision(sizes.clone(), edges.clone(), edge_values.clone(), *opts)
+        }
+        Stmt::CustomConstraint(_, _) => return None,
+    };
+    Some(ret)
+}
+
+/// A log of all variables and constraints added to a `Solver`, from which the instance can be rebuilt
+/// with only a part of the constraint groups.
+struct ExplanationLog {
+    config: Config,
+    items: Vec<RecordedItem>,
+    replayable: bool,
+}
+
+impl ExplanationLog {
+    /// Rebuilds the instance with the ungrouped constraints, the constraints in `groups` (which must be sorted)
+    /// and `extra`, and returns whether it is unsatisfiable.
+    fn is_unsat(&self, groups: &[ConstraintId], extra: &CSPBoolExpr) -> bool {
+        let mut solver = IntegratedSolver::with_config(self.config);
+        for item in &self.items {
+            match item {
+                RecordedItem::BoolVar => {
+                    solver.new_bool_var();
+                }
+                RecordedItem::IntVar(domain) => {
+                    solver.new_int_var(domain.clone());
+                }
+                RecordedItem::IntVarFromList(domain) => {
+                    solver.new_int_var_from_list(domain.clone());
+                }
+                RecordedItem::PrenormalizeVar(var) => solver.add_prenormalize_var(*var),
+                RecordedItem::Constraint(group, stmt) => {
+                    let included = match group {
+                        Some(g) => groups.binary_search(g).is_ok(),
+                        None => true,
+                    };
+                    if included {
+                        solver.add_constraint(clone_stmt(stmt).unwrap());
+                    }
+                }
+            }
+        }
+        solver.add_expr(extra.clone());
+        solver.solve().is_none()
+    }
+
+    /// Rebuilds the instance with `extra` and the constraints in each of the `num_groups` groups guarded by
+    /// an activation variable, which is returned for each group. A group is in effect only when its activation
+    /// variable is assumed to be true.
+    ///
+    /// Returns `None` if a group contains a constraint which cannot be guarded by a variable
+    /// (that is, a constraint other than an expression or an all-different constraint).
+    fn build_guarded<'b>(
+        &self,
+        num_groups: usize,
+        extra: &CSPBoolExpr,
+    ) -> Option<(IntegratedSolver<'b>, Vec<CSPBoolVar>)> {
+        let mut solver = IntegratedSolver::with_config(self.config);
+        for item in &self.items {
+            match item {
+                RecordedItem::BoolVar => {
+                    solver.new_bool_var();
+                }
+                RecordedItem::IntVar(domain) => {
+                    solver.new_int_var(domain.clone());
+                }
+                RecordedItem::IntVarFromList(domain) => {
+                    solver.new_int_var_from_list(domain.clone());
+                }
+                RecordedItem::PrenormalizeVar(var) => solver.add_prenormalize_var(*var),
+                RecordedItem::Constraint(..) => (),
+            }
+        }
+
+        // The activation variables are created after all recorded variables so that the ids of the latter are kept.
+        let activation = (0..num_groups)
+            .map(|_| solver.new_bool_var())
+            .collect::<Vec<_>>();
+        for &act in &activation {
+            solver.add_prenormalize_var(act);
+        }
+
+        for item in &self.items {
+            let RecordedItem::Constraint(group, stmt) = item else {
+                continue;
+            };
+            let Some(group) = group else {
+                solver.add_constraint(clone_stmt(stmt).unwrap());
+                continue;
+            };
+            let act = CSPBoolExpr::Var(activation[group.0]);
+            match stmt {
+                Stmt::Expr(e) => solver.add_expr(act.imp(e.clone())),
+                Stmt::AllDifferent(exprs) => {
+                    for i in 0..exprs.len() {
+                        for j in (i + 1)..exprs.len() {
+                            solver.add_expr(act.clone().imp(exprs[i].clone().ne(exprs[j].clone())));
+                        }
+                    }
+                }
+                _ => return None,
+            }
+        }
+        solver.add_expr(extra.clone());
+
+        Some((solver, activation))
+    }
+}
+
 pub struct Solver<'a> {
     solver: IntegratedSolver<'a>,
     answer_key_bool: Vec<CSPBoolVar>,
     answer_key_int: Vec<CSPIntVar>,
+    explanation_log: Option<ExplanationLog>,
+    num_groups: usize,
+    current_group: Option<ConstraintId>,
+    graph_division_mode: Option<GraphDivisionMode>,
 }
 
 impl<'a> Solver<'a> {
@@ -1088,6 +1288,10 @@ impl<'a> Solver<'a> {
             solver: IntegratedSolver::new(),
             answer_key_bool: vec![],
             answer_key_int: vec![],
+            explanation_log: None,
+            num_groups: 0,
+            current_group: None,
+            graph_division_mode: None,
         }
     }
 
@@ -1097,9 +1301,166 @@ impl<'a> Solver<'a> {
             solver: IntegratedSolver::with_config(config),
             answer_key_bool: vec![],
             answer_key_int: vec![],
+            explanation_log: None,
+            num_groups: 0,
+            current_group: None,
+            graph_division_mode: None,
         }
     }
 
+    /// Creates a new `Solver` instance which records the added variables and constraints so that `explain`
+    /// can be used.
+    pub fn with_explanation() -> Solver<'a> {
+        let config = Config::default();
+        let mut ret = Solver::with_config(config);
+        ret.explanation_log = Some(ExplanationLog {
+            config,
+            items: vec![],
+            replayable: true,
+        });
+        ret
+    }
+
+    fn new_csp_bool_var(&mut self) -> CSPBoolVar {
+        if let Some(log) = &mut self.explanation_log {
+            log.items.push(RecordedItem::BoolVar);
+        }
+        self.solver.new_bool_var()
+    }
+
+    fn new_csp_int_var(&mut self, domain: Domain) -> CSPIntVar {
+        if let Some(log) = &mut self.explanation_log {
+            log.items.push(RecordedItem::IntVar(domain.clone()));
+        }
+        self.solver.new_int_var(domain)
+    }
+
+    fn new_csp_int_var_from_list(&mut self, domain: Vec<i32>) -> CSPIntVar {
+        if let Some(log) = &mut self.explanation_log {
+            log.items.push(RecordedItem::IntVarFromList(domain.clone()));
+        }
+        self.solver.new_int_var_from_list(domain)
+    }
+
+    fn add_stmt(&mut self, stmt: Stmt) {
+        if let Some(log) = &mut self.explanation_log {
+            match clone_stmt(&stmt) {
+                Some(cloned) => log
+                    .items
+                    .push(RecordedItem::Constraint(self.current_group, cloned)),
+                None => log.replayable = false,
+            }
+        }
+        self.solver.add_constraint(stmt);
+    }
+
+    /// Starts a new group of constraints and returns its id.
+    ///
+    /// All constraints added until the next `end_group` call belong to this group. Groups are the units
+    /// reported by `explain`; constraints added outside of any group are always assumed.
+    /// Groups cannot be nested: beginning a group ends the current one.
+    pub fn begin_group(&mut self) -> ConstraintId {
+        let id = ConstraintId(self.num_groups);
+        self.num_groups += 1;
+        self.current_group = Some(id);
+        id
+    }
+
+    /// Ends the current group of constraints.
+    pub fn end_group(&mut self) {
+        self.current_group = None;
+    }
+
+    /// Explains why the answer key `key` is forced to be `value`.
+    ///
+    /// Returns a minimal set of constraint groups (see `begin_group`) which, together with the ungrouped
+    /// constraints, contradict `key != value`: dropping any one of the returned groups makes `key != value`
+    /// satisfiable again. The returned ids are sorted.
+    ///
+    /// The set is found by deletion: starting from all groups, each group is dropped in the order of the ids
+    /// and put back if the contradiction disappears. The result is therefore minimal, but not necessarily
+    /// minimum (there may be a smaller set of groups contradicting `key != value`), and when several minimal
+    /// sets exist, which one is returned depends on the order of the groups.
+    ///
+    /// The instance is rebuilt once with each group guarded by an activation variable, and the groups are switched
+    /// on and off by solving under assumptions on these variables (see `solve_under_assumptions`). If a group contains
+    /// a constraint which cannot be guarded (such as a graph constraint), the instance is instead rebuilt and solved
+    /// once for each group. In either case this is intended for hints and debugging rather than for the solving itself.
+    ///
+    /// Returns `None` if the solver was not created by `with_explanation`, a custom constraint has been added,
+    /// `key` does not refer to an answer key of the type of `value`, or `key` is not forced to be `value`.
+    ///
+    /// # Example
+    /// ```
+    /// # use cspuz_rs::solver::{AnswerKeyRef, AnswerKeyValue, ConstraintId, Solver};
+    /// let mut solver = Solver::with_explanation();
+    /// let x = &solver.bool_var();
+    /// let y = &solver.bool_var();
+    /// solver.add_answer_key_bool([x, y]);
+    ///
+    /// let g0 = solver.begin_group();
+    /// solver.add_expr(x | y);
+    /// solver.end_group();
+    /// let _g1 = solver.begin_group();
+    /// solver.add_expr(y | !x);
+    /// solver.end_group();
+    /// let g2 = solver.begin_group();
+    /// solver.add_expr(!x);
+    /// solver.end_group();
+    ///
+    /// assert_eq!(
+    ///     solver.explain(AnswerKeyRef::Bool(1), AnswerKeyValue::Bool(true)),
+    ///     Some(vec![g0, g2])
+    /// );
+    /// assert_eq!(solver.explain(AnswerKeyRef::Bool(1), AnswerKeyValue::Bool(false)), None);
+    /// ```
+    pub fn explain(&self, key: AnswerKeyRef, value: AnswerKeyValue) -> Option<Vec<ConstraintId>> {
+        let log = self.explanation_log.as_ref()?;
+        if !log.replayable {
+            return None;
+        }
+        let negated = match (key, value) {
+            (AnswerKeyRef::Bool(i), AnswerKeyValue::Bool(b)) => {
+                let v = self.answer_key_bool.get(i)?.expr();
+                if b {
+                    !v
+                } else {
+                    v
+                }
+            }
+            (AnswerKeyRef::Int(i), AnswerKeyValue::Int(n)) => {
+                self.answer_key_int.get(i)?.expr().ne(CSPIntExpr::Const(n))
+            }
+            _ => return None,
+        };
+
+        let mut guarded = log.build_guarded(self.num_groups, &negated);
+        let mut is_unsat = |groups: &[ConstraintId]| match &mut guarded {
+            Some((solver, activation)) => {
+                let assumptions = groups
+                    .iter()
+                    .map(|g| (activation[g.0], true))
+                    .collect::<Vec<_>>();
+                solver.solve_under_assumptions(&assumptions).is_none()
+            }
+            None => log.is_unsat(groups, &negated),
+        };
+
+        let mut groups = (0..self.num_groups).map(ConstraintId).collect::<Vec<_>>();
+        if !is_unsat(&groups) {
+            return None;
+        }
+        let mut i = 0;
+        while i < groups.len() {
+            let removed = groups.remove(i);
+            if !is_unsat(&groups) {
+                groups.insert(i, removed);
+                i += 1;
+            }
+        }
+        Some(groups)
+    }
+
     /// Creates and returns a new boolean variable.
     ///
     /// # Examples
@@ -1110,11 +1471,14 @@ impl<'a> Solver<'a> {
     /// ```
     pub fn bool_var(&mut self) -> BoolVar {
         Value(Array0DImpl {
-            data: self.solver.new_bool_var(),
+            data: self.new_csp_bool_var(),
         })
     }
 
     pub fn add_prenormalize_var(&mut self, var: BoolVar) {
+        if let Some(log) = &mut self.explanation_log {
+            log.items.push(RecordedItem::PrenormalizeVar(var.0.data));
+        }
         self.solver.add_prenormalize_var(var.0.data);
     }
 
@@ -1128,7 +1492,7 @@ impl<'a> Solver<'a> {
     /// ```
     pub fn bool_var_1d(&mut self, len: usize) -> BoolVarArray1D {
         Value(Array1DImpl {
-            data: (0..len).map(|_| self.solver.new_bool_var()).collect(),
+            data: (0..len).map(|_| self.new_csp_bool_var()).collect(),
         })
     }
 
@@ -1144,7 +1508,7 @@ impl<'a> Solver<'a> {
         let (h, w) = shape;
         Value(Array2DImpl {
             shape,
-            data: (0..(h * w)).map(|_| self.solver.new_bool_var()).collect(),
+            data: (0..(h * w)).map(|_| self.new_csp_bool_var()).collect(),
         })
     }
 
@@ -1160,7 +1524,7 @@ impl<'a> Solver<'a> {
     /// ```
     pub fn int_var(&mut self, low: i32, high: i32) -> IntVar {
         Value(Array0DImpl {
-            data: self.solver.new_int_var(Domain::range(low, high)),
+            data: self.new_csp_int_var(Domain::range(low, high)),
         })
     }
 
@@ -1176,7 +1540,7 @@ impl<'a> Solver<'a> {
     /// ```
     pub fn int_var_from_domain(&mut self, domain: Vec<i32>) -> IntVar {
         Value(Array0DImpl {
-            data: self.solver.new_int_var_from_list(domain),
+            data: self.new_csp_int_var_from_list(domain),
         })
     }
 
@@ -1191,7 +1555,7 @@ impl<'a> Solver<'a> {
     pub fn int_var_1d(&mut self, len: usize, low: i32, high: i32) -> IntVarArray1D {
         Value(Array1DImpl {
             data: (0..len)
-                .map(|_| self.solver.new_int_var(Domain::range(low, high)))
+                .map(|_| self.new_csp_int_var(Domain::range(low, high)))
                 .collect(),
         })
     }
@@ -1209,7 +1573,7 @@ impl<'a> Solver<'a> {
         Value(Array2DImpl {
             shape,
             data: (0..(h * w))
-                .map(|_| self.solver.new_int_var(Domain::range(low, high)))
+                .map(|_| self.new_csp_int_var(Domain::range(low, high)))
                 .collect(),
         })
     }
@@ -1236,7 +1600,7 @@ impl<'a> Solver<'a> {
             data: (0..(h * w))
                 .map(|i| {
                     let (low, high) = range[i / w][i % w];
-                    self.solver.new_int_var(Domain::range(low, high))
+                    self.new_csp_int_var(Domain::range(low, high))
                 })
                 .collect(),
         })
@@ -1265,7 +1629,7 @@ impl<'a> Solver<'a> {
     {
         exprs
             .into_iter()
-            .for_each(|e| self.solver.add_expr(e.as_expr_array().data));
+            .for_each(|e| self.add_stmt(Stmt::Expr(e.as_expr_array().data)));
     }
 
     /// Adds a constraint that the specified integer expressions have different values.
@@ -1288,7 +1652,66 @@ impl<'a> Solver<'a> {
             .into_iter()
             .map(|e| e.as_expr_array().data)
             .collect::<Vec<_>>();
-        self.solver.add_constraint(Stmt::AllDifferent(exprs));
+        self.add_stmt(Stmt::AllDifferent(exprs));
+    }
+
+    /// Adds a constraint that all cells of the 2D grid `grid` have different values.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cspuz_rs::solver::Solver;
+    /// let mut solver = Solver::new();
+    /// let a = &solver.int_var_2d((2, 3), 1, 6);
+    /// solver.all_different_2d(a);
+    ///
+    /// assert!(solver.solve().is_some());
+    /// ```
+    pub fn all_different_2d(&mut self, grid: &IntVarArray2D) {
+        let cells = grid.into_iter().collect::<Vec<_>>();
+        self.all_different(cells);
+    }
+
+    /// Adds a constraint that the sequence `a` is lexicographically less than or equal to `b`.
+    ///
+    /// This is useful for breaking symmetries: if exchanging two groups of variables maps an answer to
+    /// another answer, requiring the groups to be ordered keeps only one of them.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cspuz_rs::solver::{IntVar, Solver};
+    /// let mut solver = Solver::new();
+    /// let a = solver.int_var_1d(2, 0, 2).into_iter().collect::<Vec<IntVar>>();
+    /// let b = solver.int_var_1d(2, 0, 2).into_iter().collect::<Vec<IntVar>>();
+    /// solver.add_lex_le(&a, &b);
+    /// solver.add_expr(a[0].eq(1));
+    /// solver.add_expr(a[1].eq(1));
+    /// solver.add_expr(b[1].eq(0));
+    ///
+    /// let answer = solver.solve().unwrap();
+    /// assert_eq!(answer.get(&b[0]), 2);
+    /// ```
+    pub fn add_lex_le(&mut self, a: &[IntVar], b: &[IntVar]) {
+        assert_eq!(a.len(), b.len());
+
+        // `prefix_eq` is true iff `a[..i]` and `b[..i]` are equal (`None` stands for the empty prefix).
+        let mut prefix_eq: Option<BoolVar> = None;
+        for (i, (x, y)) in a.iter().zip(b).enumerate() {
+            match &prefix_eq {
+                Some(p) => self.add_expr(p.imp(x.le(y))),
+                None => self.add_expr(x.le(y)),
+            }
+            if i + 1 < a.len() {
+                let next = self.bool_var();
+                match &prefix_eq {
+                    Some(p) => self.add_expr(next.iff(p & x.eq(y))),
+                    None => self.add_expr(next.iff(x.eq(y))),
+                }
+                prefix_eq = Some(next);
+            }
+        }
     }
 
     pub fn add_active_vertices_connected<T>(&mut self, exprs: T, graph: &[(usize, usize)])
@@ -1303,8 +1726,25 @@ impl<'a> Solver<'a> {
             assert!(u < n_vertices);
             assert!(v < n_vertices);
         }
-        self.solver
-            .add_constraint(Stmt::ActiveVerticesConnected(vertices, graph.to_owned()));
+        self.add_stmt(Stmt::ActiveVerticesConnected(vertices, graph.to_owned()));
+    }
+
+    /// Sets the encoding used for graph division constraints added by this solver (e.g. by
+    /// `graph::graph_division_2d`), overriding `Config::graph_division_mode`.
+    ///
+    /// `GraphDivisionMode::Cpp` (the default) uses the propagator implemented in C++, and
+    /// `GraphDivisionMode::Rust` uses the one implemented in Rust. Both modes accept the same set of
+    /// solutions; which one is faster depends on the puzzle. A mode given explicitly in
+    /// `GraphDivisionOptions::mode` takes precedence over this setting.
+    ///
+    /// # Example
+    /// ```
+    /// # use cspuz_rs::solver::{GraphDivisionMode, Solver};
+    /// let mut solver = Solver::new();
+    /// solver.set_graph_division_mode(GraphDivisionMode::Rust);
+    /// ```
+    pub fn set_graph_division_mode(&mut self, mode: GraphDivisionMode) {
+        self.graph_division_mode = Some(mode);
     }
 
     pub fn add_graph_division<T>(
@@ -1342,7 +1782,11 @@ impl<'a> Solver<'a> {
             .into_iter()
             .map(|x| x.as_expr_array().data)
             .collect();
-        self.solver.add_constraint(Stmt::GraphDivision(
+        let opts = GraphDivisionOptions {
+            mode: opts.mode.or(self.graph_division_mode),
+            ..opts
+        };
+        self.add_stmt(Stmt::GraphDivision(
             sizes,
             edges.to_owned(),
             edge_values,
@@ -1356,8 +1800,7 @@ impl<'a> Solver<'a> {
         <T as IntoIterator>::Item: Operand<Output = Array0DImpl<CSPBoolExpr>>,
     {
         let vars = vars.into_iter().map(|x| x.as_expr_array().data).collect();
-        self.solver
-            .add_constraint(Stmt::CustomConstraint(vars, constraint));
+        self.add_stmt(Stmt::CustomConstraint(vars, constraint));
     }
 
     pub fn set_perf_stats<'b: 'a>(&mut self, perf_stats: &'b PerfStats) {
@@ -1408,10 +1851,59 @@ impl<'a> Solver<'a> {
             .extend(keys.into_iter().map(|x| x.deref_var().0.data))
     }
 
+    /// Returns references to all registered answer keys: boolean keys first, then integer keys.
+    /// Keys of each type are listed in the order of registration.
+    ///
+    /// # Example
+    /// ```
+    /// # use cspuz_rs::solver::{AnswerKeyRef, Solver};
+    /// let mut solver = Solver::new();
+    /// let x = &solver.int_var(0, 2);
+    /// let y = &solver.bool_var_1d(2);
+    /// solver.add_answer_key_int(x);
+    /// solver.add_answer_key_bool(y);
+    ///
+    /// assert_eq!(
+    ///     solver.answer_key_refs(),
+    ///     vec![AnswerKeyRef::Bool(0), AnswerKeyRef::Bool(1), AnswerKeyRef::Int(0)]
+    /// );
+    /// ```
+    pub fn answer_key_refs(&self) -> Vec<AnswerKeyRef> {
+        (0..self.answer_key_bool.len())
+            .map(AnswerKeyRef::Bool)
+            .chain((0..self.answer_key_int.len()).map(AnswerKeyRef::Int))
+            .collect()
+    }
+
+    /// Returns the number of registered answer keys of both types.
+    pub fn num_answer_keys(&self) -> usize {
+        self.answer_key_bool.len() + self.answer_key_int.len()
+    }
+
     pub fn encode(&mut self) -> bool {
         self.solver.encode()
     }
 
+    /// Returns the size of the SAT instance for the constraints added so far.
+    ///
+    /// This method encodes the pending constraints, so it is meant to be called after all `add_expr` calls
+    /// and before solving, to see why an instance is slow.
+    ///
+    /// # Example
+    /// ```
+    /// # use cspuz_rs::solver::Solver;
+    /// let mut solver = Solver::new();
+    /// let x = &solver.int_var_1d(3, 0, 5);
+    /// solver.add_expr((x.at(0) + x.at(1)).eq(x.at(2)));
+    ///
+    /// let stats = solver.instance_stats();
+    /// assert_eq!(stats.num_int_vars, 3);
+    /// assert!(stats.num_clauses > 0);
+    /// ```
+    pub fn instance_stats(&mut self) -> InstanceStats {
+        self.solver.instance_stats()
+    }
+
     /// Solves the CSP instance and returns a model (a mapping from variables to values) if it exists.
     ///
     /// If the CSP instance is unsatisfiable, this method returns `None`.
@@ -1447,6 +1939,42 @@ impl<'a> Solver<'a> {
         self.solver.solve().map(|model| Model { model })
     }
 
+    /// Same as `solve`, but finds a model in which each variable in `assumptions` takes the given value.
+    ///
+    /// Unlike constraints added by `add_expr`, the assumptions are only in effect during this call,
+    /// so the solver can be reused for other queries afterwards (e.g. for checking a hint interactively).
+    ///
+    /// # Example
+    /// ```
+    /// # use cspuz_rs::solver::Solver;
+    /// let mut solver = Solver::new();
+    /// let x = &solver.bool_var();
+    /// let y = &solver.bool_var();
+    /// solver.add_expr(x ^ y);
+    ///
+    /// let model = solver.solve_under_assumptions(&[(x.clone(), true)]);
+    /// assert_eq!(model.map(|model| model.get(y)), Some(false));
+    ///
+    /// let model = solver.solve_under_assumptions(&[(x.clone(), true), (y.clone(), true)]);
+    /// assert!(model.is_none());
+    ///
+    /// // The assumptions above are not kept.
+    /// let model = solver.solve_under_assumptions(&[(y.clone(), true)]);
+    /// assert_eq!(model.map(|model| model.get(x)), Some(false));
+    /// ```
+    pub fn solve_under_assumptions<'b>(
+        &'b mut self,
+        assumptions: &[(BoolVar, bool)],
+    ) -> Option<Model<'b>> {
+        let assumptions = assumptions
+            .iter()
+            .map(|(var, value)| (var.0.data, *value))
+            .collect::<Vec<_>>();
+        self.solver
+            .solve_under_assumptions(&assumptions)
+            .map(|model| Model { model })
+    }
+
     /// Returns a partial model containing each answer key variable whose value is the same across all possible models
     /// of the CSP instance. Each such variable is assigned its decided value in the returned model.
     ///
@@ -1483,7 +2011,75 @@ impl<'a> Solver<'a> {
     pub fn irrefutable_facts(self) -> Option<OwnedPartialModel> {
         self.solver
             .decide_irrefutable_facts(&self.answer_key_bool, &self.answer_key_int)
-            .map(|assignment| OwnedPartialModel { assignment })
+            .map(|assignment| {
+                OwnedPartialModel::new(assignment, &self.answer_key_bool, &self.answer_key_int)
+            })
+    }
+
+    /// Same as `irrefutable_facts`, but stops refining the facts once `deadline` has passed.
+    ///
+    /// Facts are confirmed one by one. If `deadline` is reached before all of them are decided, only the facts
+    /// confirmed so far are reported and every other answer key variable is undetermined in the returned model,
+    /// so the result is always a subset of the one `irrefutable_facts` would return.
+    /// Note that the deadline is checked between calls to the SAT solver and a running call is not interrupted.
+    ///
+    /// If the CSP instance is found to be unsatisfiable, this method returns `None`.
+    pub fn irrefutable_facts_partial(self, deadline: Instant) -> Option<OwnedPartialModel> {
+        self.solver
+            .decide_irrefutable_facts_with_deadline(
+                &self.answer_key_bool,
+                &self.answer_key_int,
+                deadline,
+            )
+            .map(|assignment| {
+                OwnedPartialModel::new(assignment, &self.answer_key_bool, &self.answer_key_int)
+            })
+    }
+
+    /// Returns the first answer key variable whose value is forced by the constraints, together with the forced value.
+    ///
+    /// Boolean answer keys are examined before integer ones, and each of them in the order of registration;
+    /// the first forced one is returned. This makes the result deterministic, but it is not necessarily
+    /// the "easiest" deduction for a human: how much propagation each deduction needs is not measured.
+    ///
+    /// If the CSP instance is unsatisfiable or no answer key variable is forced, this method returns `None`.
+    ///
+    /// This method may introduce additional constraints and therefore consumes the `Solver` instance.
+    ///
+    /// # Example
+    /// ```
+    /// # use cspuz_rs::solver::{AnswerKeyRef, AnswerKeyValue, Solver};
+    /// let mut solver = Solver::new();
+    /// let x = &solver.bool_var();
+    /// let y = &solver.bool_var();
+    /// solver.add_answer_key_bool([x, y]);
+    ///
+    /// solver.add_expr(x | y);
+    /// solver.add_expr(!x);
+    ///
+    /// assert_eq!(
+    ///     solver.first_forced_answer_key(),
+    ///     Some((AnswerKeyRef::Bool(0), AnswerKeyValue::Bool(false)))
+    /// );
+    /// ```
+    pub fn first_forced_answer_key(self) -> Option<(AnswerKeyRef, AnswerKeyValue)> {
+        let answer_key_bool = self.answer_key_bool.clone();
+        let answer_key_int = self.answer_key_int.clone();
+        let assignment = self
+            .solver
+            .decide_irrefutable_facts(&answer_key_bool, &answer_key_int)?;
+
+        for (i, &v) in answer_key_bool.iter().enumerate() {
+            if let Some(b) = assignment.get_bool(v) {
+                return Some((AnswerKeyRef::Bool(i), AnswerKeyValue::Bool(b)));
+            }
+        }
+        for (i, &v) in answer_key_int.iter().enumerate() {
+            if let Some(n) = assignment.get_int(v) {
+                return Some((AnswerKeyRef::Int(i), AnswerKeyValue::Int(n)));
+            }
+        }
+        None
     }
 
     /// Returns an iterator that yields all possible assignments to the answer key variables.
@@ -1517,7 +2113,10 @@ impl<'a> Solver<'a> {
     pub fn answer_iter(self) -> impl Iterator<Item = OwnedPartialModel> + 'a {
         self.solver
             .answer_iter(&self.answer_key_bool, &self.answer_key_int)
-            .map(|assignment| OwnedPartialModel { assignment })
+            .map(|assignment| OwnedPartialModel {
+                assignment,
+                undecided_count: 0,
+            })
     }
 }
 
@@ -1779,9 +2378,39 @@ impl FromOwnedPartialModel for Value<Array2DImpl<CSPIntVar>> {
 
 pub struct OwnedPartialModel {
     assignment: Assignment,
+    undecided_count: usize,
 }
 
 impl OwnedPartialModel {
+    fn new(
+        assignment: Assignment,
+        answer_key_bool: &[CSPBoolVar],
+        answer_key_int: &[CSPIntVar],
+    ) -> OwnedPartialModel {
+        let undecided_count = answer_key_bool
+            .iter()
+            .filter(|&&v| assignment.get_bool(v).is_none())
+            .count()
+            + answer_key_int
+                .iter()
+                .filter(|&&v| assignment.get_int(v).is_none())
+                .count();
+        OwnedPartialModel {
+            assignment,
+            undecided_count,
+        }
+    }
+
+    /// Returns the number of answer key variables whose value is not decided in this partial model.
+    ///
+    /// For the result of `Solver::irrefutable_facts`, the count is 0 if and only if the answer of the CSP instance
+    /// is unique (with respect to the answer keys). For the result of `Solver::irrefutable_facts_partial`, a count of 0
+    /// still implies a unique answer, but facts not confirmed before the deadline are counted as undecided.
+    /// The models yielded by `Solver::answer_iter` are complete answers and always report 0.
+    pub fn undecided_count(&self) -> usize {
+        self.undecided_count
+    }
+
     pub fn get<T>(&self, var: &T) -> <T as FromOwnedPartialModel>::Output
     where
         T: FromOwnedPartialModel,
@@ -1824,6 +2453,59 @@ mod tests {
         let _ = !(b2d ^ b2d);
     }
 
+    #[test]
+    fn test_all_different_2d() {
+        {
+            let mut solver = Solver::new();
+            let grid = &solver.int_var_2d((2, 2), 1, 4);
+            solver.add_answer_key_int(grid);
+            solver.all_different_2d(grid);
+            assert_eq!(solver.answer_iter().count(), 24);
+        }
+        {
+            let mut solver = Solver::new();
+            let grid = &solver.int_var_2d((2, 2), 1, 4);
+            solver.all_different_2d(grid);
+            solver.add_expr(grid.at((0, 0)).eq(3));
+            solver.add_expr(grid.at((1, 1)).eq(3));
+            assert!(solver.solve().is_none());
+        }
+    }
+
+    #[test]
+    fn test_add_lex_le() {
+        {
+            let mut solver = Solver::new();
+            let a = &solver.int_var_1d(3, 0, 1);
+            let b = &solver.int_var_1d(3, 0, 1);
+            solver.add_answer_key_int(a);
+            solver.add_answer_key_int(b);
+            let av = a.into_iter().collect::<Vec<_>>();
+            let bv = b.into_iter().collect::<Vec<_>>();
+            solver.add_lex_le(&av, &bv);
+
+            // 8 pairs with `a == b` and half of the other 56 pairs
+            let answers = solver.answer_iter().collect::<Vec<_>>();
+            assert_eq!(answers.len(), 36);
+            for answer in &answers {
+                assert!(answer.get_unwrap(a) <= answer.get_unwrap(b));
+            }
+        }
+        for (b_value, expected) in [([1, 0, 0], false), ([1, 0, 1], true), ([1, 1, 0], true)] {
+            let mut solver = Solver::new();
+            let a = &solver.int_var_1d(3, 0, 1);
+            let b = &solver.int_var_1d(3, 0, 1);
+            let av = a.into_iter().collect::<Vec<_>>();
+            let bv = b.into_iter().collect::<Vec<_>>();
+            solver.add_lex_le(&av, &bv);
+            for ((x, y), (xv, yv)) in av.iter().zip(&bv).zip([1, 0, 1].into_iter().zip(b_value)) {
+                solver.add_expr(x.eq(xv));
+                solver.add_expr(y.eq(yv));
+            }
+            assert_eq!(solver.solve().is_some(), expected);
+        }
+    }
+
     #[test]
     fn test_ite() {
         let mut solver = Solver::new();
@@ -1907,4 +2589,266 @@ mod tests {
         }
         assert_eq!(n_ans, 24);
     }
+
+    #[test]
+    fn test_instance_stats() {
+        let mut solver = Solver::new();
+        let x = &solver.int_var_1d(3, 0, 5);
+        solver.add_expr((x.at(0) + x.at(1)).eq(x.at(2)));
+        let stats1 = solver.instance_stats();
+        assert_eq!(stats1.num_int_vars, 3);
+
+        let y = &solver.int_var_1d(3, 0, 5);
+        solver.add_expr((y.at(0) + y.at(1)).eq(y.at(2)));
+        solver.add_expr(x.at(0).ne(y.at(0)));
+        let stats2 = solver.instance_stats();
+        assert_eq!(stats2.num_int_vars, 6);
+        assert!(stats2.num_vars > stats1.num_vars);
+        assert!(stats2.num_clauses > stats1.num_clauses);
+
+        assert!(solver.solve().is_some());
+    }
+
+    #[test]
+    fn test_irrefutable_facts_partial() {
+        let build = || {
+            let mut solver = Solver::new();
+            let x = &solver.bool_var_1d(10);
+            solver.add_answer_key_bool(x);
+            solver.add_expr(x.at(0));
+            for i in 1..10 {
+                solver.add_expr(x.at(i - 1).imp(x.at(i)) | x.at(i - 1).iff(x.at(i / 2)));
+            }
+            (solver, x.clone())
+        };
+
+        let (solver, x) = build();
+        let full = solver.irrefutable_facts().unwrap().get(&x);
+
+        let (solver, x) = build();
+        let partial = solver.irrefutable_facts_partial(Instant::now());
+        assert!(partial.is_some());
+        let partial = partial.unwrap().get(&x);
+        for i in 0..10 {
+            assert!(partial[i].is_none() || partial[i] == full[i]);
+        }
+
+        let (solver, x) = build();
+        let deadline = Instant::now() + std::time::Duration::from_secs(3600);
+        assert_eq!(
+            solver.irrefutable_facts_partial(deadline).unwrap().get(&x),
+            full
+        );
+
+        let mut solver = Solver::new();
+        let y = &solver.bool_var();
+        solver.add_answer_key_bool(y);
+        solver.add_expr(y);
+        solver.add_expr(!y);
+        assert!(solver.irrefutable_facts_partial(Instant::now()).is_none());
+    }
+
+    /// Does not constrain its inputs, but blocks every propagation until `deadline` has passed.
+    struct BlockUntil {
+        deadline: Instant,
+    }
+
+    impl cspuz_core::custom_constraints::SimpleCustomConstraint for BlockUntil {
+        fn initialize_sat(&mut self, _num_inputs: usize) {}
+
+        fn notify(&mut self, _index: usize, _value: bool) {}
+
+        fn find_inconsistency(&mut self) -> Option<Vec<(usize, bool)>> {
+            let now = Instant::now();
+            if now < self.deadline {
+                std::thread::sleep(self.deadline - now);
+            }
+            None
+        }
+
+        fn undo(&mut self) {}
+    }
+
+    #[test]
+    fn test_irrefutable_facts_partial_expired_during_refinement() {
+        let mut solver = Solver::new();
+        let x = &solver.bool_var_1d(10);
+        solver.add_answer_key_bool(x);
+        solver.add_expr(x.at(0));
+
+        let deadline = Instant::now() + std::time::Duration::from_millis(10);
+        solver.add_custom_constraint(Box::new(BlockUntil { deadline }), x);
+        let partial = solver.irrefutable_facts_partial(deadline).unwrap();
+        assert!(Instant::now() >= deadline);
+
+        // Only `x[0]` is forced. Values of the other variables taken from the answers found
+        // before the deadline must not be reported as facts.
+        let partial = partial.get(x);
+        assert!(partial[0].is_none() || partial[0] == Some(true));
+        for i in 1..10 {
+            assert_eq!(partial[i], None);
+        }
+    }
+
+    #[test]
+    fn test_undecided_count() {
+        let mut solver = Solver::new();
+        let x = &solver.bool_var_1d(3);
+        let n = &solver.int_var(0, 2);
+        solver.add_answer_key_bool(x);
+        solver.add_answer_key_int(n);
+        solver.add_expr(x.at(0));
+        solver.add_expr(x.at(1) ^ x.at(2));
+        solver.add_expr(n.eq(x.at(1).ite(1, 2)));
+        let facts = solver.irrefutable_facts().unwrap();
+        assert_eq!(facts.undecided_count(), 3);
+
+        let mut solver = Solver::new();
+        let x = &solver.bool_var_1d(3);
+        solver.add_answer_key_bool(x);
+        solver.add_expr(x.all());
+        assert_eq!(solver.irrefutable_facts().unwrap().undecided_count(), 0);
+    }
+
+    #[test]
+    fn test_solve_under_assumptions() {
+        let mut solver = Solver::new();
+        let x = &solver.bool_var_1d(3);
+        let unused = &solver.bool_var();
+        solver.add_expr(x.count_true().eq(1));
+
+        for i in 0..3 {
+            let model = solver.solve_under_assumptions(&[(x.at(i), true), (unused.clone(), true)]);
+            assert!(model.is_some());
+            let model = model.unwrap();
+            for j in 0..3 {
+                assert_eq!(model.get(&x.at(j)), i == j);
+            }
+            assert!(model.get(unused));
+        }
+
+        assert!(solver
+            .solve_under_assumptions(&[(x.at(0), true), (x.at(2), true)])
+            .is_none());
+        assert!(solver
+            .solve_under_assumptions(&[(x.at(0), false), (x.at(1), false), (x.at(2), false)])
+            .is_none());
+
+        // Retracting the assumptions restores the solvability.
+        assert!(solver.solve_under_assumptions(&[]).is_some());
+        assert!(solver.solve().is_some());
+
+        solver.add_expr(!x.at(1));
+        assert!(solver.solve_under_assumptions(&[(x.at(1), true)]).is_none());
+        let model = solver.solve_under_assumptions(&[(x.at(0), false)]);
+        assert_eq!(model.map(|model| model.get(&x.at(2))), Some(true));
+    }
+
+    #[test]
+    fn test_manhattan_distance_eq() {
+        let mut solver = Solver::new();
+        manhattan_distance_eq(&mut solver, (0, 0), (2, 3), 5);
+        assert!(solver.solve().is_some());
+
+        let mut solver = Solver::new();
+        manhattan_distance_eq(&mut solver, (0, 0), (2, 3), 4);
+        assert!(solver.solve().is_none());
+
+        let mut solver = Solver::new();
+        let y = &solver.int_var(0, 3);
+        let x = &solver.int_var(0, 3);
+        solver.add_answer_key_int(y);
+        solver.add_answer_key_int(x);
+        manhattan_distance_eq(&mut solver, (y, x), (1, 2), 4);
+        let facts = solver.irrefutable_facts().unwrap();
+        assert_eq!(facts.get(y), Some(3));
+        assert_eq!(facts.get(x), Some(0));
+    }
+
+    #[test]
+    fn test_answer_key_refs() {
+        let mut solver = Solver::new();
+        assert_eq!(solver.num_answer_keys(), 0);
+        assert!(solver.answer_key_refs().is_empty());
+
+        // Same layout as the Shugaku solver: two integer grids registered as answer keys
+        let kind = &solver.int_var_2d((3, 4), 0, 3);
+        let direction = &solver.int_var_2d((3, 4), 0, 3);
+        solver.add_answer_key_int(kind);
+        solver.add_answer_key_int(direction);
+        let is_aisle = &solver.bool_var_2d((3, 4));
+        solver.add_expr(is_aisle.iff(kind.eq(0)));
+
+        assert_eq!(solver.num_answer_keys(), 24);
+        let refs = solver.answer_key_refs();
+        assert_eq!(refs.len(), solver.num_answer_keys());
+        assert_eq!(refs[0], AnswerKeyRef::Int(0));
+        assert_eq!(refs[23], AnswerKeyRef::Int(23));
+
+        solver.add_answer_key_bool(is_aisle.at((0, 0)));
+        assert_eq!(solver.num_answer_keys(), 25);
+        assert_eq!(solver.answer_key_refs()[0], AnswerKeyRef::Bool(0));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut solver = Solver::with_explanation();
+        let a = &solver.int_var_1d(3, 0, 2);
+        solver.add_answer_key_int(a);
+
+        let g0 = solver.begin_group();
+        solver.all_different(a);
+        solver.end_group();
+        let _g1 = solver.begin_group();
+        solver.add_expr(a.at(1).ne(2));
+        solver.end_group();
+        let g2 = solver.begin_group();
+        solver.add_expr(a.at(0).eq(0));
+        solver.end_group();
+        solver.add_expr(a.at(2).ne(1));
+
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Int(2), AnswerKeyValue::Int(2)),
+            Some(vec![g0, g2])
+        );
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Int(0), AnswerKeyValue::Int(0)),
+            Some(vec![g2])
+        );
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Int(1), AnswerKeyValue::Int(2)),
+            None
+        );
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Bool(0), AnswerKeyValue::Bool(true)),
+            None
+        );
+
+        // A group with a graph constraint cannot be guarded by a variable.
+        let mut solver = Solver::with_explanation();
+        let x = &solver.bool_var_1d(3);
+        solver.add_answer_key_bool(x);
+        let g0 = solver.begin_group();
+        solver.add_active_vertices_connected(x, &[(0, 1), (1, 2)]);
+        solver.end_group();
+        let g1 = solver.begin_group();
+        solver.add_expr(x.at(0) & x.at(2));
+        solver.end_group();
+        let _g2 = solver.begin_group();
+        solver.add_expr(x.at(0));
+        solver.end_group();
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Bool(1), AnswerKeyValue::Bool(true)),
+            Some(vec![g0, g1])
+        );
+
+        let mut solver = Solver::new();
+        let x = &solver.bool_var();
+        solver.add_answer_key_bool(x);
+        solver.add_expr(x);
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Bool(0), AnswerKeyValue::Bool(true)),
+            None
+        );
+    }
 }
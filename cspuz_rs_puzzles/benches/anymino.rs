@@ -0,0 +1,45 @@
+//! Measures the solving time of Anymino, whose custom constraint (`AnyminoConstraint`) caches the
+//! block state of each room between propagations.
+//!
+//! Run with `cargo bench -p cspuz_rs_puzzles --bench anymino`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use cspuz_rs::graph;
+use cspuz_rs_puzzles::puzzles::anymino;
+
+/// A `height` x `width` board divided into rooms of `room_height` x `room_width` cells.
+fn uniform_rooms(
+    height: usize,
+    width: usize,
+    room_height: usize,
+    room_width: usize,
+) -> graph::InnerGridEdges<Vec<Vec<bool>>> {
+    graph::InnerGridEdges {
+        horizontal: (0..(height - 1))
+            .map(|y| vec![(y + 1) % room_height == 0; width])
+            .collect(),
+        vertical: (0..height)
+            .map(|_| {
+                (0..(width - 1))
+                    .map(|x| (x + 1) % room_width == 0)
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+fn bench_anymino_10x10(c: &mut Criterion) {
+    let borders = uniform_rooms(10, 10, 2, 5);
+    let mut group = c.benchmark_group("anymino");
+    // a single solve takes a few hundred milliseconds
+    group.sample_size(10);
+    group.bench_function("10x10", |b| {
+        b.iter(|| black_box(anymino::solve_anymino(&borders)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_anymino_10x10);
+criterion_main!(benches);
@@ -0,0 +1,127 @@
+//! Compares the solving time of representative puzzles under different solver configurations.
+//!
+//! Run with `cargo bench -p cspuz_rs_puzzles`. Each configuration is installed as the thread-local default
+//! (`Config::set_default`), so the puzzle solvers pick it up without any change.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use cspuz_rs::solver::{Config, GraphDivisionMode};
+use cspuz_rs_puzzles::puzzles::{masyu, polyominous, shugaku, slitherlink};
+
+fn configs() -> Vec<(&'static str, Config)> {
+    let base = Config::initial_default();
+    let mut ret = vec![("default", base)];
+
+    let mut config = base;
+    config.graph_division_mode = GraphDivisionMode::Cpp;
+    ret.push(("graph_division_cpp", config));
+
+    let mut config = base;
+    config.graph_division_mode = GraphDivisionMode::Rust;
+    ret.push(("graph_division_rust", config));
+
+    let mut config = base;
+    config.use_log_encoding = false;
+    ret.push(("no_log_encoding", config));
+
+    let mut config = base;
+    config.direct_encoding_for_binary_vars = true;
+    ret.push(("direct_encoding_for_binary_vars", config));
+
+    let mut config = base;
+    config.merge_equivalent_variables = true;
+    ret.push(("merge_equivalent_variables", config));
+
+    ret
+}
+
+fn bench_puzzle<F: Fn()>(c: &mut Criterion, name: &str, solve: F) {
+    let mut group = c.benchmark_group(name);
+    for (config_name, config) in configs() {
+        group.bench_function(BenchmarkId::from_parameter(config_name), |b| {
+            Config::set_default(config);
+            b.iter(&solve);
+        });
+    }
+    group.finish();
+    Config::set_default(Config::initial_default());
+}
+
+fn bench_pentominous(c: &mut Criterion) {
+    let (clues, borders) =
+        polyominous::deserialize_pentominous_problem("https://puzz.link/p?pentominous/5/5/72zi")
+            .unwrap();
+    bench_puzzle(c, "pentominous", || {
+        black_box(polyominous::solve_pentominous(&clues, &borders));
+    });
+}
+
+fn bench_pentominous_heavily_clued(c: &mut Criterion) {
+    let (clues, borders) = polyominous::deserialize_pentominous_problem(
+        "https://puzz.link/p?pentominous/5/5/7222272555777574445744777",
+    )
+    .unwrap();
+    bench_puzzle(c, "pentominous_heavily_clued", || {
+        black_box(polyominous::solve_pentominous(&clues, &borders));
+    });
+}
+
+/// Compares the two encodings of the graph division constraint (see `graph::graph_division_2d_with_mode`)
+/// on a 10x10 Pentominous with one clue per piece.
+fn bench_pentominous_10x10(c: &mut Criterion) {
+    let (clues, borders) = polyominous::deserialize_pentominous_problem(
+        "https://puzz.link/p?pentominous/10/10/g4iag3q4h7i2l63j8haqa7j1h2r4hagbj2j7j1i",
+    )
+    .unwrap();
+    let mut group = c.benchmark_group("pentominous_10x10");
+    for (mode_name, mode) in [
+        ("cpp", GraphDivisionMode::Cpp),
+        ("rust", GraphDivisionMode::Rust),
+    ] {
+        let mut config = Config::initial_default();
+        config.graph_division_mode = mode;
+        group.bench_function(BenchmarkId::from_parameter(mode_name), |b| {
+            Config::set_default(config);
+            b.iter(|| black_box(polyominous::solve_pentominous(&clues, &borders)));
+        });
+    }
+    group.finish();
+    Config::set_default(Config::initial_default());
+}
+
+fn bench_shugaku(c: &mut Criterion) {
+    let problem = shugaku::deserialize_problem("https://puzz.link/p?shugaku/4/4/a03e").unwrap();
+    bench_puzzle(c, "shugaku", || {
+        black_box(shugaku::solve_shugaku(&problem));
+    });
+}
+
+fn bench_slitherlink(c: &mut Criterion) {
+    let problem =
+        slitherlink::deserialize_problem("https://puzz.link/p?slither/4/4/dgdh2c71").unwrap();
+    bench_puzzle(c, "slitherlink", || {
+        black_box(slitherlink::solve_slitherlink(&problem));
+    });
+}
+
+fn bench_masyu(c: &mut Criterion) {
+    let problem = masyu::deserialize_problem(
+        "https://puzz.link/p?masyu/10/10/0600003i06b1300600000a30600i090330",
+    )
+    .unwrap();
+    bench_puzzle(c, "masyu", || {
+        black_box(masyu::solve_masyu(&problem));
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_pentominous,
+    bench_pentominous_heavily_clued,
+    bench_pentominous_10x10,
+    bench_shugaku,
+    bench_slitherlink,
+    bench_masyu
+);
+criterion_main!(benches);
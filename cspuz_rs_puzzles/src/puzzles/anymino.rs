@@ -1,90 +1,48 @@
+use crate::util::polyomino;
 use cspuz_rs::graph;
 
 use cspuz_rs::serializer::{get_kudamono_url_info_detailed, parse_kudamono_dimension, problem_to_url_with_context, url_to_problem, Combinator, Context, KudamonoBorder, Rooms, Size};
 
-use cspuz_rs::solver::{any, count_true, Solver};
+use super::regionomino::{solve_regionomino, RegionominoAdjacency, RegionominoSize, RegionominoSpec};
 
 use cspuz_core::custom_constraints::SimpleCustomConstraint;
 use std::collections::HashSet;
 
+/// Anymino: shade a connected polyomino of at least 3 cells in each room so that all shaded cells
+/// are connected and no 2x2 area is fully shaded. Blocks in different rooms touching each other
+/// must not be congruent.
+///
+/// Block sizes are constrained the other way around from the common "adjacent regions differ in
+/// size" rule: the block in each room must touch at least one block of the *same* size in another
+/// room. This is intended (see `RegionominoSpec::touch_same_size`).
 pub fn solve_anymino(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
 ) -> Option<Vec<Vec<Option<bool>>>> {
-    let h = borders.vertical.len();
-    assert!(h > 0);
-    let w = borders.vertical[0].len() + 1;
-
-    let mut solver = Solver::new();
-    let is_black = &solver.bool_var_2d((h, w));
-    solver.add_answer_key_bool(is_black);
-
-    graph::active_vertices_connected_2d(&mut solver, is_black);
-    solver.add_expr(!is_black.conv2d_and((2, 2)));
+    solve_anymino_with_options(borders, false)
+}
 
-    let rooms = graph::borders_to_rooms(borders);
-    if rooms.len() < 2 {
+/// `solve_anymino` with an extra rule switch.
+///
+/// If `forbid_congruent_anywhere` is `true`, no two blocks may be congruent even when they do not
+/// touch each other. Otherwise, only touching blocks are checked (the standard rule).
+pub fn solve_anymino_with_options(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    forbid_congruent_anywhere: bool,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    if graph::borders_to_rooms(borders).len() < 2 {
         return None;
     }
-    let mut room_id = vec![vec![0; w]; h];
-    for (i, room) in rooms.iter().enumerate() {
-        for &(y, x) in room {
-            room_id[y][x] = i;
-        }
-    }
-
-    let room_sizes = &solver.int_var_1d(rooms.len(), 3, (h * w) as i32);
-    for i in 0..rooms.len() {
-        let room_cells = &rooms[i];
-
-        // --- 制約1: 各部屋の黒マスは連結している ---
-        graph::active_vertices_connected_2d_region(&mut solver, &is_black, room_cells);
-
-        // --- 制約2: 各部屋の黒マスの数は指定されたサイズと等しい ---
-        let mut black_cell_exprs = Vec::with_capacity(room_cells.len());
-        for &(y, x) in room_cells {
-            black_cell_exprs.push(is_black.at((y, x)).expr());
-        }
-        solver.add_expr(count_true(black_cell_exprs).eq(room_sizes.at(i)));
-
-        // --- 制約3: 隣接する部屋との境界に関する制約 ---
-        let mut adjacent_constraints = vec![];
-        let current_room_id = i;
-        for &(y, x) in room_cells {
-            // (y, x) の上下左右の隣接セルをループでチェック
-            for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-                let (ny, nx) = (y as i32 + dy, x as i32 + dx);
-
-                // 盤面の範囲外はスキップ
-                if ny < 0 || ny >= h as i32 || nx < 0 || nx >= w as i32 {
-                    continue;
-                }
-
-                let (ny, nx) = (ny as usize, nx as usize);
-                let neighbor_room_id = room_id[ny][nx];
-                // 隣接セルが別の部屋の場合、制約候補を追加
-                if current_room_id != neighbor_room_id {
-                    let constraint = is_black.at((y, x))
-                        & is_black.at((ny, nx))
-                        & room_sizes
-                        .at(current_room_id)
-                        .eq(room_sizes.at(neighbor_room_id));
-
-                    adjacent_constraints.push(constraint);
-                }
-            }
-        }
-
-        // この部屋が他の部屋と隣接している場合、
-        // 「黒マス同士で接しており、かつ部屋サイズが同じ」という境界が"少なくとも1つ"存在する、という制約を追加。
-        if !adjacent_constraints.is_empty() {
-            solver.add_expr(any(&adjacent_constraints));
-        }
-    }
 
-    let constraint = AnyminoConstraint::new(h, w, rooms, room_id);
-    solver.add_custom_constraint(Box::new(constraint), is_black);
-
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    let spec = RegionominoSpec {
+        size: RegionominoSize::AtLeast(3),
+        adjacency: RegionominoAdjacency::CongruenceBan {
+            anywhere: forbid_congruent_anywhere,
+        },
+        connected: true,
+        no_2x2: true,
+        touch_same_size: true,
+    };
+    solve_regionomino(borders, &spec)
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -94,180 +52,135 @@ enum CellState {
     Undecided,
 }
 
-struct AnyminoConstraint {
+/// Per-room information used by `AnyminoConstraint::find_inconsistency`.
+#[derive(Clone, Default)]
+struct RoomState {
+    black_cells: HashSet<(i32, i32)>,
+    white_adjacent_cells: HashSet<(i32, i32)>,
+    /// Normalized shape of the block in this room, or empty if the block is not closed yet.
+    closed_block: Vec<(i32, i32)>,
+}
+
+/// Custom constraint forbidding congruent closed blocks in different rooms.
+pub(super) struct AnyminoConstraint {
     height: usize,
     width: usize,
+    topology: graph::GridTopology,
     rooms: Vec<Vec<(usize, usize)>>,
     room_id_map: Vec<Vec<usize>>,
+    /// Edge-adjacent rooms of each room (as computed by `graph::room_adjacency`).
+    room_adjacency: Vec<Vec<usize>>,
+    /// If `true`, congruent blocks are reported even when their rooms are not adjacent.
+    forbid_congruent_anywhere: bool,
     board: Vec<Vec<CellState>>,
     decision_stack: Vec<(usize, usize)>,
+    /// Cached `RoomState` of each room. Only valid for rooms whose `dirty` flag is `false`.
+    room_states: Vec<RoomState>,
+    dirty: Vec<bool>,
 }
 
 impl AnyminoConstraint {
-    fn new(
+    pub(super) fn new(
         height: usize,
         width: usize,
         rooms: Vec<Vec<(usize, usize)>>,
         room_id_map: Vec<Vec<usize>>,
+        room_adjacency: Vec<Vec<usize>>,
+        forbid_congruent_anywhere: bool,
     ) -> AnyminoConstraint {
+        let num_rooms = rooms.len();
         AnyminoConstraint {
             height,
             width,
+            topology: graph::GridTopology::new(height, width),
             rooms,
             room_id_map,
+            room_adjacency,
+            forbid_congruent_anywhere,
             board: vec![vec![CellState::Undecided; width]; height],
             decision_stack: vec![],
+            room_states: vec![RoomState::default(); num_rooms],
+            dirty: vec![true; num_rooms],
         }
     }
-}
-
-fn adjust_bbox(block: &mut [(i32, i32)]) {
-    let mut min_y = i32::MAX;
-    let mut min_x = i32::MAX;
-    for &(y, x) in block.iter() {
-        min_y = min_y.min(y);
-        min_x = min_x.min(x);
-    }
-    for p in block.iter_mut() {
-        p.0 -= min_y;
-        p.1 -= min_x;
-    }
-}
-
-fn flip_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    let mut ymax = 0;
-    for &(y, _) in block.iter() {
-        ymax = ymax.max(y);
-    }
-    let mut ret = block
-        .iter()
-        .map(|&(y, x)| (ymax - y, x))
-        .collect::<Vec<_>>();
-    ret.sort();
-    ret
-}
-
-fn rotate_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    let mut ymax = 0;
-    for &(y, _) in block.iter() {
-        ymax = ymax.max(y);
-    }
-
-    let mut ret = block
-        .iter()
-        .map(|&(y, x)| (x, ymax - y))
-        .collect::<Vec<_>>();
-    ret.sort();
-    ret
-}
-
-fn normalize_block(mut block: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
-    if block.is_empty() {
-        return vec![];
-    }
-    adjust_bbox(&mut block);
-    block.sort();
-
-    let mut ret = block.clone();
-    for i in 0..4 {
-        ret = ret.min(block.clone());
-        ret = ret.min(flip_block(&block));
-        if i < 3 {
-            block = rotate_block(&block);
-        }
-    }
-    ret
-}
 
-impl SimpleCustomConstraint for AnyminoConstraint {
-    fn initialize_sat(&mut self, num_inputs: usize) {
-        assert_eq!(num_inputs, self.height * self.width);
-    }
-
-    fn notify(&mut self, index: usize, value: bool) {
-        let y = index / self.width;
-        let x = index % self.width;
-        self.board[y][x] = if value {
-            CellState::Black
-        } else {
-            CellState::White
-        };
-        self.decision_stack.push((y, x));
+    /// Marks the room whose `RoomState` may be affected by a change of cell (y, x).
+    /// A `RoomState` only depends on the cells of its own room.
+    fn mark_dirty(&mut self, y: usize, x: usize) {
+        self.dirty[self.room_id_map[y][x]] = true;
     }
 
-    fn find_inconsistency(&mut self) -> Option<Vec<(usize, bool)>> {
-        let mut closed_blocks = vec![vec![]; self.rooms.len()];
-        let mut black_cells = vec![HashSet::new(); self.rooms.len()];
-        let mut white_adjacent_cells = vec![HashSet::new(); self.rooms.len()];
-        let mut adjacent_rooms = vec![HashSet::new(); self.rooms.len()];
+    fn compute_room_state(&self, room_id: usize) -> RoomState {
+        let mut state = RoomState::default();
+        let mut is_closed = true;
 
-        for room_id in 0..self.rooms.len() {
-            let room_cells = &self.rooms[room_id];
-            let mut is_closed = true;
-
-            for &(y, x) in room_cells {
-                if self.board[y][x] == CellState::Black {
-                    black_cells[room_id].insert((y as i32, x as i32));
-                }
+        for &(y, x) in &self.rooms[room_id] {
+            if self.board[y][x] == CellState::Black {
+                state.black_cells.insert((y as i32, x as i32));
             }
-            
-            for &(y, x) in &black_cells[room_id] {
-                for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-                    let (ny, nx) = (y + dy, x + dx);
-                    if ny < 0 || ny >= self.height as i32 || nx < 0 || nx >= self.width as i32 {
-                        continue;
-                    }
+        }
 
-                    if self.room_id_map[ny as usize][nx as usize] != room_id {
-                        if self.board[ny as usize][nx as usize] == CellState::Black {
-                            adjacent_rooms[room_id]
-                                .insert(self.room_id_map[ny as usize][nx as usize]);
-                        }
-                    } else if self.board[ny as usize][nx as usize] == CellState::White {
-                        white_adjacent_cells[room_id].insert((ny, nx));
-                    } else if self.board[ny as usize][nx as usize] == CellState::Undecided {
-                        is_closed = false;
-                        break;
-                    }
+        for &(y, x) in &state.black_cells {
+            for &(ny, nx) in self.topology.neighbors((y as usize, x as usize)) {
+                if self.room_id_map[ny][nx] != room_id {
+                    continue;
                 }
-                if !is_closed {
+                if self.board[ny][nx] == CellState::White {
+                    state.white_adjacent_cells.insert((ny as i32, nx as i32));
+                } else if self.board[ny][nx] == CellState::Undecided {
+                    is_closed = false;
                     break;
                 }
             }
-
-            if !is_closed || black_cells[room_id].is_empty() {
-                continue;
+            if !is_closed {
+                break;
             }
+        }
 
-            closed_blocks[room_id] =
-                normalize_block(black_cells[room_id].iter().cloned().collect());
+        if is_closed && !state.black_cells.is_empty() {
+            state.closed_block =
+                polyomino::canonical_form(&state.black_cells.iter().cloned().collect::<Vec<_>>());
         }
+        state
+    }
 
+    fn find_congruent_blocks(&self, room_states: &[RoomState]) -> Option<Vec<(usize, bool)>> {
         for room_id in 0..self.rooms.len() {
-            if closed_blocks[room_id].is_empty() {
+            if room_states[room_id].closed_block.is_empty() {
                 continue;
             }
-            if adjacent_rooms.is_empty() {
-                continue; 
-            }
 
-            for &adjacent_room_id in &adjacent_rooms[room_id] {
-                if closed_blocks[adjacent_room_id].is_empty() {
+            // Only rooms adjacent to this room can contain a block touching the block in this room.
+            let other_rooms: Vec<usize> = if self.forbid_congruent_anywhere {
+                ((room_id + 1)..self.rooms.len()).collect()
+            } else {
+                self.room_adjacency[room_id]
+                    .iter()
+                    .copied()
+                    .filter(|&r| r > room_id)
+                    .collect()
+            };
+
+            for other_room_id in other_rooms {
+                if room_states[other_room_id].closed_block.is_empty() {
                     continue;
                 }
 
-                if closed_blocks[room_id] == closed_blocks[adjacent_room_id] {
+                if room_states[room_id].closed_block == room_states[other_room_id].closed_block
+                    && (self.forbid_congruent_anywhere
+                        || self.blocks_touch(&room_states[room_id], &room_states[other_room_id]))
+                {
                     let mut ret = vec![];
-                    for &(y, x) in &black_cells[room_id] {
+                    for &(y, x) in &room_states[room_id].black_cells {
                         ret.push(((y * self.width as i32 + x) as usize, true));
                     }
-                    for &(y, x) in &black_cells[adjacent_room_id] {
+                    for &(y, x) in &room_states[other_room_id].black_cells {
                         ret.push(((y * self.width as i32 + x) as usize, true));
                     }
-                    for &(y, x) in &white_adjacent_cells[room_id] {
+                    for &(y, x) in &room_states[room_id].white_adjacent_cells {
                         ret.push(((y * self.width as i32 + x) as usize, false));
                     }
-                    for &(y, x) in &white_adjacent_cells[adjacent_room_id] {
+                    for &(y, x) in &room_states[other_room_id].white_adjacent_cells {
                         ret.push(((y * self.width as i32 + x) as usize, false));
                     }
 
@@ -279,9 +192,58 @@ impl SimpleCustomConstraint for AnyminoConstraint {
         None
     }
 
+    /// Returns whether a black cell of `a` is edge-adjacent to a black cell of `b`.
+    fn blocks_touch(&self, a: &RoomState, b: &RoomState) -> bool {
+        a.black_cells.iter().any(|&(y, x)| {
+            self.topology
+                .neighbors((y as usize, x as usize))
+                .iter()
+                .any(|&(ny, nx)| b.black_cells.contains(&(ny as i32, nx as i32)))
+        })
+    }
+
+    /// Same as `find_inconsistency`, but recomputes the states of all rooms from scratch.
+    #[cfg(test)]
+    fn find_inconsistency_naive(&self) -> Option<Vec<(usize, bool)>> {
+        let room_states = (0..self.rooms.len())
+            .map(|room_id| self.compute_room_state(room_id))
+            .collect::<Vec<_>>();
+        self.find_congruent_blocks(&room_states)
+    }
+}
+
+impl SimpleCustomConstraint for AnyminoConstraint {
+    fn initialize_sat(&mut self, num_inputs: usize) {
+        assert_eq!(num_inputs, self.height * self.width);
+    }
+
+    fn notify(&mut self, index: usize, value: bool) {
+        let y = index / self.width;
+        let x = index % self.width;
+        self.board[y][x] = if value {
+            CellState::Black
+        } else {
+            CellState::White
+        };
+        self.decision_stack.push((y, x));
+        self.mark_dirty(y, x);
+    }
+
+    fn find_inconsistency(&mut self) -> Option<Vec<(usize, bool)>> {
+        for room_id in 0..self.rooms.len() {
+            if self.dirty[room_id] {
+                self.room_states[room_id] = self.compute_room_state(room_id);
+                self.dirty[room_id] = false;
+            }
+        }
+
+        self.find_congruent_blocks(&self.room_states)
+    }
+
     fn undo(&mut self) {
         let (y, x) = self.decision_stack.pop().unwrap();
         self.board[y][x] = CellState::Undecided;
+        self.mark_dirty(y, x);
     }
 }
 
@@ -326,3 +288,219 @@ pub fn deserialize_problem(url: &str) -> Option<Problem> {
     Some(border)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+    use cspuz_rs::solver::{count_true, Solver};
+
+    // Two L-trominoes in rooms separated by a blank column
+    // (room 0: x = 0..=1, room 1: x = 2, room 2: x = 3..=4):
+    //   ##.##
+    //   #...#
+    fn constraint_with_separated_trominoes(forbid_congruent_anywhere: bool) -> AnyminoConstraint {
+        let rooms = vec![
+            vec![(0, 0), (0, 1), (1, 0), (1, 1)],
+            vec![(0, 2), (1, 2)],
+            vec![(0, 3), (0, 4), (1, 3), (1, 4)],
+        ];
+        let room_id_map = vec![vec![0, 0, 1, 2, 2], vec![0, 0, 1, 2, 2]];
+        let room_adjacency = vec![vec![1], vec![0, 2], vec![1]];
+        let mut constraint = AnyminoConstraint::new(
+            2,
+            5,
+            rooms,
+            room_id_map,
+            room_adjacency,
+            forbid_congruent_anywhere,
+        );
+        constraint.initialize_sat(10);
+
+        let cells = [
+            [true, true, false, true, true],
+            [true, false, false, false, true],
+        ];
+        for (y, row) in cells.iter().enumerate() {
+            for (x, &b) in row.iter().enumerate() {
+                constraint.notify(y * 5 + x, b);
+            }
+        }
+        constraint
+    }
+
+    #[test]
+    fn test_anymino_kudamono_degenerate_dimension() {
+        for dim in ["0x0", "0x5", "5x0"] {
+            let url = format!(
+                "https://pedros.works/paper-puzzle-player?W={}&G=anymino",
+                dim
+            );
+            assert_eq!(deserialize_problem(&url), None);
+        }
+    }
+
+    #[test]
+    fn test_anymino_constraint_adjacent_only() {
+        let mut constraint = constraint_with_separated_trominoes(false);
+        assert!(constraint.find_inconsistency().is_none());
+    }
+
+    #[test]
+    fn test_anymino_constraint_congruent_anywhere() {
+        let mut constraint = constraint_with_separated_trominoes(true);
+        let reason = constraint.find_inconsistency();
+        assert!(reason.is_some());
+
+        let mut reason = reason.unwrap();
+        reason.sort();
+        assert_eq!(
+            reason,
+            vec![
+                (0, true),
+                (1, true),
+                (3, true),
+                (4, true),
+                (5, true),
+                (6, false),
+                (8, false),
+                (9, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_anymino_constraint_cache_matches_naive() {
+        // 6x6 board divided into 2x3 rooms
+        let (height, width) = (6, 6);
+        let room_id_map = (0..height)
+            .map(|y| (0..width).map(|x| y / 2 * 2 + x / 3).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut rooms = vec![vec![]; 6];
+        for (y, row) in room_id_map.iter().enumerate() {
+            for (x, &id) in row.iter().enumerate() {
+                rooms[id].push((y, x));
+            }
+        }
+        let borders = graph::InnerGridEdges {
+            horizontal: (0..(height - 1))
+                .map(|y| (0..width).map(|_| y % 2 == 1).collect())
+                .collect(),
+            vertical: (0..height)
+                .map(|_| (0..(width - 1)).map(|x| x == 2).collect())
+                .collect(),
+        };
+        let room_adjacency = graph::room_adjacency(&borders);
+
+        let mut random_state = 0x123456789abcdefu64;
+        let mut next_random = |max: usize| {
+            random_state = random_state.wrapping_mul(0x123456789);
+            ((random_state >> 16) % (max as u64)) as usize
+        };
+
+        for forbid_congruent_anywhere in [false, true] {
+            let mut constraint = AnyminoConstraint::new(
+                height,
+                width,
+                rooms.clone(),
+                room_id_map.clone(),
+                room_adjacency.clone(),
+                forbid_congruent_anywhere,
+            );
+            constraint.initialize_sat(height * width);
+
+            for _ in 0..2000 {
+                let num_decided = constraint.decision_stack.len();
+                if num_decided > 0 && (num_decided == height * width || next_random(3) == 0) {
+                    constraint.undo();
+                } else {
+                    let undecided = (0..height * width)
+                        .filter(|&i| constraint.board[i / width][i % width] == CellState::Undecided)
+                        .collect::<Vec<_>>();
+                    let index = undecided[next_random(undecided.len())];
+                    constraint.notify(index, next_random(2) == 0);
+                }
+
+                let expected = constraint.find_inconsistency_naive().map(|mut r| {
+                    r.sort();
+                    r
+                });
+                let actual = constraint.find_inconsistency().map(|mut r| {
+                    r.sort();
+                    r
+                });
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    fn adjacent_rooms_answers(use_helper: bool) -> Vec<(Vec<Vec<bool>>, Vec<i32>)> {
+        // 0 1 1
+        // 0 2 2
+        let rooms = vec![
+            vec![(0, 0), (1, 0)],
+            vec![(0, 1), (0, 2)],
+            vec![(1, 1), (1, 2)],
+        ];
+        let room_id = vec![vec![0, 1, 1], vec![0, 2, 2]];
+        let (h, w) = (2, 3);
+
+        let mut solver = Solver::new();
+        let is_black = &solver.bool_var_2d((h, w));
+        solver.add_answer_key_bool(is_black);
+        let room_sizes = &solver.int_var_1d(rooms.len(), 0, 2);
+        solver.add_answer_key_int(room_sizes);
+        for (i, room) in rooms.iter().enumerate() {
+            solver.add_expr(count_true(is_black.select(room)).eq(room_sizes.at(i)));
+        }
+
+        if use_helper {
+            util::add_adjacent_rooms_value_constraints(
+                &mut solver,
+                &rooms,
+                &room_id,
+                is_black,
+                room_sizes,
+                util::AdjacentRoomsRule::Share,
+            );
+        } else {
+            for (i, room) in rooms.iter().enumerate() {
+                let mut adjacent_constraints = vec![];
+                for &(y, x) in room {
+                    for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                        let (ny, nx) = (y as i32 + dy, x as i32 + dx);
+                        if ny < 0 || ny >= h as i32 || nx < 0 || nx >= w as i32 {
+                            continue;
+                        }
+                        let (ny, nx) = (ny as usize, nx as usize);
+                        let j = room_id[ny][nx];
+                        if i != j {
+                            adjacent_constraints.push(
+                                is_black.at((y, x))
+                                    & is_black.at((ny, nx))
+                                    & room_sizes.at(i).eq(room_sizes.at(j)),
+                            );
+                        }
+                    }
+                }
+                if !adjacent_constraints.is_empty() {
+                    solver.add_expr(cspuz_rs::solver::any(&adjacent_constraints));
+                }
+            }
+        }
+
+        let mut answers = solver
+            .answer_iter()
+            .map(|f| (f.get_unwrap(is_black), f.get_unwrap(room_sizes)))
+            .collect::<Vec<_>>();
+        answers.sort();
+        answers
+    }
+
+    #[test]
+    fn test_anymino_adjacent_rooms_constraints() {
+        let inline = adjacent_rooms_answers(false);
+        let helper = adjacent_rooms_answers(true);
+        assert!(!inline.is_empty());
+        assert_eq!(inline, helper);
+    }
+}
@@ -13,16 +13,24 @@ pub fn solve_aquapelago(problem: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<b
     solver.add_answer_key_bool(is_black);
     solver.add_expr(!is_black.conv2d_and((1, 2)));
     solver.add_expr(!is_black.conv2d_and((2, 1)));
-    solver.add_expr(is_black.conv2d_or((2, 2)));
+    graph::no_2x2_empty(&mut solver, is_black);
     graph::active_vertices_connected_2d(&mut solver, !is_black);
 
+    // Islands are the groups of diagonally connected shaded cells. Unlike `diagonally_connected_2d`,
+    // shaded cells may form several islands, so the island sizes are given by a graph division instead.
+    let graph = graph::infer_diagonal_graph_from_2d_array((h, w));
     let mut aux_graph = vec![];
-    let mut aux_sizes = vec![];
     let mut aux_edges = vec![];
+    for i in 0..graph.n_edges() {
+        let (u, v) = graph[i];
+        aux_graph.push((u, v));
+        aux_edges.push(!(is_black.at((u / w, u % w)) & is_black.at((v / w, v % w))));
+    }
 
-    for y in 0..h {
-        for x in 0..w {
-            if let Some(n) = problem[y][x] {
+    let mut aux_sizes = vec![];
+    for (y, row) in problem.iter().enumerate() {
+        for (x, &clue) in row.iter().enumerate() {
+            if let Some(n) = clue {
                 solver.add_expr(is_black.at((y, x)));
                 if n > 0 {
                     aux_sizes.push(Some(int_constant(n)));
@@ -32,17 +40,6 @@ pub fn solve_aquapelago(problem: &[Vec<Option<i32>>]) -> Option<Vec<Vec<Option<b
             } else {
                 aux_sizes.push(None);
             }
-
-            if y < h - 1 {
-                if x < w - 1 {
-                    aux_graph.push((y * w + x, (y + 1) * w + x + 1));
-                    aux_edges.push(!(is_black.at((y, x)) & is_black.at((y + 1, x + 1))));
-                }
-                if x > 0 {
-                    aux_graph.push((y * w + x, (y + 1) * w + x - 1));
-                    aux_edges.push(!(is_black.at((y, x)) & is_black.at((y + 1, x - 1))));
-                }
-            }
         }
     }
     solver.add_graph_division(&aux_sizes, &aux_graph, &aux_edges);
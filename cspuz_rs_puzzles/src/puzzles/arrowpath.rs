@@ -0,0 +1,181 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::items::{Arrow, NumberedArrow};
+use cspuz_rs::serializer::{
+    choice, problem_to_url, url_to_problem, Combinator, Grid, NumberedArrowCombinator, Optionalize,
+    Spaces,
+};
+use cspuz_rs::solver::{any, Solver};
+
+/// Arrow Path: draw a path through the cell centers from the top-left cell to the bottom-right cell.
+/// Every arrow cell is on the path, and the path leaves it in the direction of the arrow
+/// (an unspecified direction imposes nothing). Arrow cells with numbers are visited in increasing order
+/// of the numbers; `-1` stands for an arrow without a number.
+pub fn solve_arrowpath(
+    clues: &[Vec<Option<NumberedArrow>>],
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+    if h * w < 2 {
+        return None;
+    }
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_path_grid_edges(&mut solver, is_line, &[(0, 0), (h - 1, w - 1)]);
+
+    // `dist` is the number of steps from the top-left cell along the path.
+    let dist = &solver.int_var_2d((h, w), 0, (h * w) as i32 - 1);
+    solver.add_expr(dist.at((0, 0)).eq(0));
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 {
+                solver.add_expr(is_line.vertical.at((y, x)).imp(
+                    dist.at((y, x)).eq(dist.at((y + 1, x)) + 1)
+                        | dist.at((y + 1, x)).eq(dist.at((y, x)) + 1),
+                ));
+            }
+            if x < w - 1 {
+                solver.add_expr(is_line.horizontal.at((y, x)).imp(
+                    dist.at((y, x)).eq(dist.at((y, x + 1)) + 1)
+                        | dist.at((y, x + 1)).eq(dist.at((y, x)) + 1),
+                ));
+            }
+            if (y, x) != (0, 0) {
+                let mut from_prev = vec![];
+                if y > 0 {
+                    from_prev.push(
+                        is_line.vertical.at((y - 1, x))
+                            & dist.at((y - 1, x)).eq(dist.at((y, x)) - 1),
+                    );
+                }
+                if y < h - 1 {
+                    from_prev.push(
+                        is_line.vertical.at((y, x)) & dist.at((y + 1, x)).eq(dist.at((y, x)) - 1),
+                    );
+                }
+                if x > 0 {
+                    from_prev.push(
+                        is_line.horizontal.at((y, x - 1))
+                            & dist.at((y, x - 1)).eq(dist.at((y, x)) - 1),
+                    );
+                }
+                if x < w - 1 {
+                    from_prev.push(
+                        is_line.horizontal.at((y, x)) & dist.at((y, x + 1)).eq(dist.at((y, x)) - 1),
+                    );
+                }
+                solver.add_expr(is_passed.at((y, x)).imp(any(from_prev)));
+            }
+        }
+    }
+
+    let mut numbered = vec![];
+    for y in 0..h {
+        for x in 0..w {
+            if let Some((dir, n)) = clues[y][x] {
+                solver.add_expr(is_passed.at((y, x)));
+
+                let next = match dir {
+                    Arrow::Unspecified => None,
+                    Arrow::Up => Some((y as i32 - 1, x as i32)),
+                    Arrow::Down => Some((y as i32 + 1, x as i32)),
+                    Arrow::Left => Some((y as i32, x as i32 - 1)),
+                    Arrow::Right => Some((y as i32, x as i32 + 1)),
+                };
+                if let Some((ny, nx)) = next {
+                    if !(0 <= ny && ny < h as i32 && 0 <= nx && nx < w as i32) {
+                        return None;
+                    }
+                    let (ny, nx) = (ny as usize, nx as usize);
+                    let edge = if ny != y {
+                        is_line.vertical.at((y.min(ny), x))
+                    } else {
+                        is_line.horizontal.at((y, x.min(nx)))
+                    };
+                    solver.add_expr(edge);
+                    solver.add_expr(dist.at((ny, nx)).eq(dist.at((y, x)) + 1));
+                }
+
+                if n >= 0 {
+                    numbered.push((n, (y, x)));
+                }
+            }
+        }
+    }
+    numbered.sort();
+    for i in 1..numbered.len() {
+        let (n1, c1) = numbered[i - 1];
+        let (n2, c2) = numbered[i];
+        if n1 < n2 {
+            solver.add_expr(dist.at(c1).lt(dist.at(c2)));
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<Option<NumberedArrow>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(choice![
+        Optionalize::new(NumberedArrowCombinator),
+        Spaces::new(None, 'a'),
+    ])
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "arrowpath", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["arrowpath"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 5];
+        ret[0][3] = Some((Arrow::Right, 1));
+        ret[1][2] = Some((Arrow::Left, 4));
+        ret[2][4] = Some((Arrow::Left, 2));
+        ret[3][1] = Some((Arrow::Up, 3));
+        ret
+    }
+
+    #[test]
+    fn test_arrowpath_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_arrowpath(&problem);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [1, 1, 1, 1],
+                [1, 1, 0, 0],
+                [0, 1, 0, 1],
+                [0, 1, 1, 0],
+                [1, 1, 1, 1],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [0, 0, 0, 0, 1],
+                [1, 0, 1, 0, 1],
+                [1, 1, 0, 1, 0],
+                [1, 0, 0, 0, 0],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_arrowpath_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?arrowpath/5/5/c41c34f32a13h";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
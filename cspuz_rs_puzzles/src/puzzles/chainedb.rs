@@ -147,62 +147,6 @@ impl ChainedbConstraint {
     }
 }
 
-fn adjust_bbox(block: &mut [(i32, i32)]) {
-    let mut min_y = std::i32::MAX;
-    let mut min_x = std::i32::MAX;
-    for &(y, x) in block.iter() {
-        min_y = min_y.min(y);
-        min_x = min_x.min(x);
-    }
-    for p in block.iter_mut() {
-        p.0 -= min_y;
-        p.1 -= min_x;
-    }
-}
-
-fn flip_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    let mut ymax = 0;
-    for &(y, _) in block.iter() {
-        ymax = ymax.max(y);
-    }
-    let mut ret = block
-        .iter()
-        .map(|&(y, x)| (ymax - y, x))
-        .collect::<Vec<_>>();
-    ret.sort();
-    ret
-}
-
-fn rotate_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    let mut ymax = 0;
-    for &(y, _) in block.iter() {
-        ymax = ymax.max(y);
-    }
-
-    let mut ret = block
-        .iter()
-        .map(|&(y, x)| (x, ymax - y))
-        .collect::<Vec<_>>();
-    ret.sort();
-    ret
-}
-
-fn normalize_block(mut block: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
-    adjust_bbox(&mut block);
-    block.sort();
-
-    let mut ret = block.clone();
-    for i in 0..4 {
-        ret = ret.min(block.clone());
-        ret = ret.min(flip_block(&block));
-        if i < 3 {
-            block = rotate_block(&block);
-        }
-    }
-
-    ret
-}
-
 fn offset(
     (y, x): (usize, usize),
     (dy, dx): (i32, i32),
@@ -311,7 +255,7 @@ impl SimpleCustomConstraint for ChainedbConstraint {
                 }
                 if is_closed {
                     blocks_by_chain[chain_id[y][x] as usize]
-                        .push((normalize_block(block), last_block_id));
+                        .push((util::polyomino::canonical_form(&block), last_block_id));
                 }
                 last_block_id += 1;
             }
@@ -429,7 +373,7 @@ mod tests {
     fn test_chainedb_normalize_block() {
         let block = vec![(1, 2), (2, 2), (2, 1), (2, 0)];
 
-        let normalized = normalize_block(block.clone());
+        let normalized = util::polyomino::canonical_form(&block);
         assert_eq!(normalized, vec![(0, 0), (0, 1), (0, 2), (1, 0),]);
     }
 
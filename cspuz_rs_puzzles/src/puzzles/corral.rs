@@ -0,0 +1,144 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
+};
+use cspuz_rs::solver::{Solver, FALSE};
+
+pub fn solve_corral(clues: &[Vec<Option<i32>>]) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h, w));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+    graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    // a cell is inside the loop iff the number of loop edges between it and the outside is odd
+    let is_inside = &solver.bool_var_2d((h, w));
+    for y in 0..=h {
+        for x in 0..w {
+            let a = if y == 0 {
+                FALSE
+            } else {
+                is_inside.at((y - 1, x)).expr()
+            };
+            let b = if y == h {
+                FALSE
+            } else {
+                is_inside.at((y, x)).expr()
+            };
+            solver.add_expr(is_line.horizontal.at((y, x)) ^ a.iff(b));
+        }
+    }
+    for y in 0..h {
+        for x in 0..=w {
+            let a = if x == 0 {
+                FALSE
+            } else {
+                is_inside.at((y, x - 1)).expr()
+            };
+            let b = if x == w {
+                FALSE
+            } else {
+                is_inside.at((y, x)).expr()
+            };
+            solver.add_expr(is_line.vertical.at((y, x)) ^ a.iff(b));
+        }
+    }
+
+    for (y, row) in clues.iter().enumerate() {
+        for (x, &clue) in row.iter().enumerate() {
+            if let Some(n) = clue {
+                solver.add_expr(is_inside.at((y, x)));
+                if n < 0 {
+                    continue;
+                }
+                let up = is_inside.slice_fixed_x((..y, x)).reverse();
+                let down = is_inside.slice_fixed_x(((y + 1).., x));
+                let left = is_inside.slice_fixed_y((y, ..x)).reverse();
+                let right = is_inside.slice_fixed_y((y, (x + 1)..));
+                solver.add_expr(
+                    (up.consecutive_prefix_true()
+                        + down.consecutive_prefix_true()
+                        + left.consecutive_prefix_true()
+                        + right.consecutive_prefix_true()
+                        + 1)
+                    .eq(n),
+                );
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+        Box::new(Dict::new(Some(-1), ".")),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "corral", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["corral", "bag"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    fn problem_for_tests() -> Problem {
+        vec![
+            vec![None, None, None, None, None, Some(3)],
+            vec![None, None, Some(6), None, None, None],
+            vec![None, Some(2), None, None, None, None],
+            vec![None, None, Some(7), None, Some(3), None],
+            vec![Some(2), None, None, Some(3), None, None],
+            vec![None, None, None, None, None, None],
+        ]
+    }
+
+    #[test]
+    fn test_corral_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_corral(&problem);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [0, 0, 0, 1, 1, 1],
+                [0, 0, 1, 0, 1, 1],
+                [0, 1, 0, 1, 0, 0],
+                [0, 1, 0, 1, 1, 0],
+                [1, 0, 0, 0, 1, 0],
+                [0, 1, 0, 1, 0, 0],
+                [1, 1, 1, 0, 0, 0],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [0, 0, 0, 1, 0, 0, 1],
+                [0, 0, 1, 0, 1, 0, 0],
+                [0, 1, 0, 1, 0, 0, 0],
+                [0, 0, 1, 0, 0, 1, 0],
+                [1, 1, 1, 0, 1, 0, 0],
+                [1, 0, 0, 1, 0, 0, 0],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_corral_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?corral/6/6/k3h6j2l7g3g2h3n";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,182 @@
+use cspuz_rs::complex_constraints::{line_runs_spec, RunSpec};
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, Dict, HexInt, Map,
+    PrefixAndSuffix, Seq, Size, UnlimitedSeq,
+};
+use cspuz_rs::solver::{BoolVarArray1D, Solver};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CrossTheStreamsClue {
+    /// A block of exactly `n` shaded cells. A line whose only clue is `Num(0)` has no shaded cells.
+    Num(i32),
+    /// `?`: a single block of unknown length.
+    Unknown,
+    /// `*`: one or more blocks of unknown lengths.
+    Any,
+}
+
+pub fn solve_crossthestreams(
+    clues_up: &[Option<Vec<CrossTheStreamsClue>>],
+    clues_left: &[Option<Vec<CrossTheStreamsClue>>],
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let h = clues_left.len();
+    let w = clues_up.len();
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    graph::active_vertices_connected_2d(&mut solver, is_black);
+    graph::no_2x2_block(&mut solver, is_black);
+
+    for x in 0..w {
+        if let Some(clue) = &clues_up[x] {
+            add_line_clue(&mut solver, &is_black.slice_fixed_x((.., x)), clue);
+        }
+    }
+    for y in 0..h {
+        if let Some(clue) = &clues_left[y] {
+            add_line_clue(&mut solver, &is_black.slice_fixed_y((y, ..)), clue);
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_black))
+}
+
+fn add_line_clue(solver: &mut Solver, cells: &BoolVarArray1D, clue: &[CrossTheStreamsClue]) {
+    let specs = clue
+        .iter()
+        .map(|&c| match c {
+            CrossTheStreamsClue::Num(n) => RunSpec::Fixed(n),
+            CrossTheStreamsClue::Unknown => RunSpec::AtLeast(1),
+            CrossTheStreamsClue::Any => RunSpec::Unknown,
+        })
+        .collect::<Vec<_>>();
+    line_runs_spec(solver, cells, &specs);
+}
+
+type Problem = (
+    Vec<Option<Vec<CrossTheStreamsClue>>>,
+    Vec<Option<Vec<CrossTheStreamsClue>>>,
+);
+
+fn line_combinator() -> impl Combinator<Option<Vec<CrossTheStreamsClue>>> {
+    let item_combinator = Choice::new(vec![
+        Box::new(Map::new(
+            HexInt,
+            |c: CrossTheStreamsClue| match c {
+                CrossTheStreamsClue::Num(n) => Some(n),
+                _ => None,
+            },
+            |n| Some(CrossTheStreamsClue::Num(n)),
+        )),
+        Box::new(Dict::new(CrossTheStreamsClue::Unknown, "g")),
+        Box::new(Dict::new(CrossTheStreamsClue::Any, "h")),
+    ]);
+
+    // Each line is terminated by '.'; a line without clues is just ".".
+    Choice::new(vec![
+        Box::new(Dict::new(None, ".")),
+        Box::new(Map::new(
+            PrefixAndSuffix::new("", UnlimitedSeq::new(item_combinator), "."),
+            |clue: Option<Vec<CrossTheStreamsClue>>| clue.filter(|c| !c.is_empty()),
+            |clue| Some(Some(clue)),
+        )),
+    ])
+}
+
+struct CrossTheStreamsCombinator;
+
+impl Combinator<Problem> for CrossTheStreamsCombinator {
+    fn serialize(&self, ctx: &Context, input: &[Problem]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let (clues_up, clues_left) = &input[0];
+
+        let mut lines = clues_up.clone();
+        lines.extend(clues_left.iter().cloned());
+        let (_, ret) = Seq::new(line_combinator(), lines.len()).serialize(ctx, &[lines])?;
+
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Problem>)> {
+        let height = ctx.height?;
+        let width = ctx.width?;
+
+        let (n_read, lines) =
+            Seq::new(line_combinator(), width + height).deserialize(ctx, input)?;
+        let mut lines = lines.into_iter().next()?;
+        let clues_left = lines.split_off(width);
+
+        Some((n_read, vec![(lines, clues_left)]))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(CrossTheStreamsCombinator)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.1.len();
+    let width = problem.0.len();
+    problem_to_url_with_context(
+        combinator(),
+        "crossthestreams",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["crossthestreams"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        use CrossTheStreamsClue::{Any, Num, Unknown};
+
+        let clues_up = vec![
+            Some(vec![Num(4)]),
+            Some(vec![Unknown]),
+            Some(vec![Num(4)]),
+            Some(vec![Num(1)]),
+            Some(vec![Num(4)]),
+        ];
+        let clues_left = vec![
+            Some(vec![Num(1), Any]),
+            Some(vec![Num(1), Unknown]),
+            Some(vec![Unknown, Num(1)]),
+            None,
+        ];
+        (clues_up, clues_left)
+    }
+
+    #[test]
+    fn test_crossthestreams_problem() {
+        let (clues_up, clues_left) = problem_for_tests();
+        let ans = solve_crossthestreams(&clues_up, &clues_left);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_bool_2d([
+            [1, 0, 1, 0, 1],
+            [1, 0, 1, 1, 1],
+            [1, 1, 1, 0, 1],
+            [1, 0, 1, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_crossthestreams_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?crossthestreams/5/4/4.g.4.1.4.1h.1g.g1..";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
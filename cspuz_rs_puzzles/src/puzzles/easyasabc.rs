@@ -1,11 +1,14 @@
 use crate::util;
 use cspuz_rs::serializer::{
-    Choice, Combinator, Context, DecInt, Dict, HexInt,
-    Optionalize, Seq, Size, Spaces, UnlimitedSeq,
+    choice, Combinator, Context, DecInt, Dict, HeaderThen, HexInt, Map, Optionalize, Seq, Size,
+    Spaces, UnlimitedSeq,
 };
-use cspuz_rs::solver::Solver;
+use cspuz_rs::complex_constraints::first_non_empty_eq;
+use cspuz_rs::solver::{IntVarArray2D, Solver};
 use cspuz_rs::serializer;
 
+const EMPTY: i32 = 0; // 0は空白を表す
+
 pub fn solve_easyasabc(
     key_size: i32,
     key_up: &[Option<i32>],
@@ -13,16 +16,62 @@ pub fn solve_easyasabc(
     key_down: &[Option<i32>],
     key_left: &[Option<i32>],
     center: &[Vec<Option<i32>>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    solve_easyasabc_with_options(
+        key_size, key_up, key_right, key_down, key_left, center, true,
+    )
+}
+
+/// `solve_easyasabc` with an extra rule switch.
+///
+/// If `allow_empty` is `false`, every cell is filled with a letter (the "no gaps" variant), so the answer is
+/// a Latin square and `key_size` must be equal to the size of the board.
+/// Since each letter appears exactly once in each row, a board with as many letters as rows has no room
+/// for empty cells anyway, so the flag only rejects problems whose `key_size` differs from the board size.
+pub fn solve_easyasabc_with_options(
+    key_size: i32,
+    key_up: &[Option<i32>],
+    key_right: &[Option<i32>],
+    key_down: &[Option<i32>],
+    key_left: &[Option<i32>],
+    center: &[Vec<Option<i32>>],
+    allow_empty: bool,
 ) -> Option<Vec<Vec<Option<i32>>>> {
     let (h, w) = util::infer_shape(center);
     if h != w {
         return None;
     }
+    if !allow_empty && key_size != h as i32 {
+        return None;
+    }
 
-    const EMPTY: i32 = 0;
     let mut solver = Solver::new();
-    let letter = &solver.int_var_2d((h, w), EMPTY, key_size); // 0は空白を表す
+    let letter = &add_constraints(
+        &mut solver,
+        key_size,
+        key_up,
+        key_right,
+        key_down,
+        key_left,
+        center,
+    );
     solver.add_answer_key_int(letter);
+
+    solver.irrefutable_facts().map(|f| f.get(letter))
+}
+
+fn add_constraints(
+    solver: &mut Solver,
+    key_size: i32,
+    key_up: &[Option<i32>],
+    key_right: &[Option<i32>],
+    key_down: &[Option<i32>],
+    key_left: &[Option<i32>],
+    center: &[Vec<Option<i32>>],
+) -> IntVarArray2D {
+    let (h, w) = util::infer_shape(center);
+
+    let letter = &solver.int_var_2d((h, w), EMPTY, key_size);
     
     for x in 0..w {
         for y in 0..h {
@@ -32,75 +81,40 @@ pub fn solve_easyasabc(
         }
     }
     
-    for x in 0..w {
-        let key_u = key_up.get(x).cloned().unwrap_or(None);
-        let key_d = key_down.get(x).cloned().unwrap_or(None);
-
-        for i in 1..=key_size {
-            solver.add_expr(letter.slice_fixed_y((x, ..)).eq(i).count_true().eq(1));
-        }
-        
-        let rank = &solver.int_var_1d(h, 0, key_size);
-        for y in 0..h {
-            if y == 0 {
-                solver.add_expr(rank.at(y).eq((letter.at((y, x)).eq(EMPTY)).ite(0, 1)));
-            } else {
-                solver.add_expr(
-                    rank.at(y)
-                        .eq((letter.at((y, x)).eq(EMPTY)).ite(rank.at(y - 1), rank.at(y - 1) + 1)),
-                );
-            }
-
-            if let Some(key_u) = key_u {
-                solver.add_expr(
-                    (rank.at(y).eq(1) & letter.at((y, x)).ne(EMPTY))
-                        .imp(letter.at((y, x)).eq(key_u)),
-                );
-            }
-            if let Some(key_d) = key_d {
-                solver.add_expr(
-                    (rank.at(y).eq(key_size) & letter.at((y, x)).ne(EMPTY))
-                        .imp(letter.at((y, x)).eq(key_d)),
-                );
+    // 各列・各行の制約をそれぞれ1つのグループにまとめる (`Solver::explain` 用)
+    let letters = (1..=key_size).collect::<Vec<_>>();
+    util::add_exactly_once_per_line_grouped(
+        solver,
+        letter,
+        &letters,
+        util::LineAxis::Column,
+        |solver, x| {
+            let column = letter.slice_fixed_x((.., x));
+            if let Some(key_u) = key_up.get(x).cloned().unwrap_or(None) {
+                solver.add_expr(first_non_empty_eq(&column, EMPTY, key_u));
             }
-        }
-    }
-    
-    for y in 0..h {
-        let key_l = key_left.get(y).cloned().unwrap_or(None);
-        let key_r = key_right.get(y).cloned().unwrap_or(None);
-
-        for i in 1..=key_size {
-            solver.add_expr(letter.slice_fixed_x((.., y)).eq(i).count_true().eq(1));
-        }
-
-        let rank = &solver.int_var_1d(w, 0, key_size);
-        for x in 0..w {
-            if x == 0 {
-                solver.add_expr(rank.at(x).eq((letter.at((y, x)).eq(EMPTY)).ite(0, 1)));
-            } else {
-                solver.add_expr(
-                    rank.at(x)
-                        .eq((letter.at((y, x)).eq(EMPTY)).ite(rank.at(x - 1), rank.at(x - 1) + 1)),
-                );
+            if let Some(key_d) = key_down.get(x).cloned().unwrap_or(None) {
+                solver.add_expr(first_non_empty_eq(column.reverse(), EMPTY, key_d));
             }
-
-            if let Some(key_l) = key_l {
-                solver.add_expr(
-                    (rank.at(x).eq(1) & letter.at((y, x)).ne(EMPTY))
-                        .imp(letter.at((y, x)).eq(key_l)),
-                );
+        },
+    );
+    util::add_exactly_once_per_line_grouped(
+        solver,
+        letter,
+        &letters,
+        util::LineAxis::Row,
+        |solver, y| {
+            let row = letter.slice_fixed_y((y, ..));
+            if let Some(key_l) = key_left.get(y).cloned().unwrap_or(None) {
+                solver.add_expr(first_non_empty_eq(&row, EMPTY, key_l));
             }
-            if let Some(key_r) = key_r {
-                solver.add_expr(
-                    (rank.at(x).eq(key_size) & letter.at((y, x)).ne(EMPTY))
-                        .imp(letter.at((y, x)).eq(key_r)),
-                );
+            if let Some(key_r) = key_right.get(y).cloned().unwrap_or(None) {
+                solver.add_expr(first_non_empty_eq(row.reverse(), EMPTY, key_r));
             }
-        }
-    }
+        },
+    );
 
-    solver.irrefutable_facts().map(|f| f.get(letter))
+    letter.clone()
 }
 
 pub type Problem = (
@@ -114,31 +128,38 @@ pub type Problem = (
 
 /// 外周ヒント(`ExCell`)用のデータコンビネータ
 fn excell_data_combinator() -> impl Combinator<Vec<Option<i32>>> {
-    let item_combinator = Choice::new(vec![
-        Box::new(Optionalize::new(HexInt)),
-        Box::new(Spaces::new(None, 'g')),
-    ]);
+    let item_combinator = choice![Optionalize::new(HexInt), Spaces::new(None, 'g')];
     UnlimitedSeq::new(item_combinator)
 }
 
 /// 中央盤面(`Cell`)用のデータコンビネータ
 fn center_data_combinator() -> impl Combinator<Vec<Option<i32>>> {
-    let item_combinator = Choice::new(vec![
-        Box::new(Optionalize::new(HexInt)),
-        Box::new(Spaces::new(None, 'g')),
-        Box::new(Dict::new(Some(-1), ".")),
-    ]);
+    let item_combinator = choice![
+        Optionalize::new(HexInt),
+        Spaces::new(None, 'g'),
+        Dict::new(Some(-1), "."),
+    ];
     UnlimitedSeq::new(item_combinator)
 }
 
-struct EasyAsAbcCombinator;
+/// `Problem` without the key size, which is serialized separately as the header.
+type ProblemBody = (
+    Vec<Option<i32>>,
+    Vec<Option<i32>>,
+    Vec<Option<i32>>,
+    Vec<Option<i32>>,
+    Vec<Vec<Option<i32>>>,
+);
+
+/// 鍵サイズより後ろ(外周と中央のデータ)のコンビネータ
+struct EasyAsAbcBodyCombinator;
 
-impl Combinator<Problem> for EasyAsAbcCombinator {
-    fn serialize(&self, ctx: &Context, input: &[Problem]) -> Option<(usize, Vec<u8>)> {
+impl Combinator<ProblemBody> for EasyAsAbcBodyCombinator {
+    fn serialize(&self, ctx: &Context, input: &[ProblemBody]) -> Option<(usize, Vec<u8>)> {
         if input.is_empty() {
             return None;
         }
-        let (key_size, key_up, key_right, key_down, key_left, center) = &input[0];
+        let (key_up, key_right, key_down, key_left, center) = &input[0];
 
         let mut excell_data: Vec<Option<i32>> = vec![];
         excell_data.extend(key_up.iter().cloned());
@@ -150,10 +171,6 @@ impl Combinator<Problem> for EasyAsAbcCombinator {
         let has_center_data = center_data.iter().any(|x| x.is_some());
 
         let mut result_bytes: Vec<u8> = vec![];
-        let (_, indicator_bytes) = DecInt.serialize(ctx, &[*key_size])?;
-        result_bytes.extend(indicator_bytes);
-        result_bytes.push(b'/');
-
         let (_, excell_bytes) = excell_data_combinator().serialize(ctx, &[excell_data])?;
         result_bytes.extend(excell_bytes);
 
@@ -165,23 +182,13 @@ impl Combinator<Problem> for EasyAsAbcCombinator {
         Some((1, result_bytes))
     }
 
-    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Problem>)> {
-        let slash_pos = input.iter().position(|&c| c == b'/')?;
-        let indicator_bytes = &input[..slash_pos];
-        let data_bytes = &input[slash_pos + 1..];
-
-        let (_, key_size_vec) = DecInt.deserialize(ctx, indicator_bytes)?;
-        let key_size = key_size_vec.get(0).copied().unwrap_or(3);
-
+    fn deserialize(&self, ctx: &Context, data_bytes: &[u8]) -> Option<(usize, Vec<ProblemBody>)> {
         let height = ctx.height?;
         let width = ctx.width?;
 
         // 外周と中央のデータが連結されているため、まず外周の分だけをデコードする
         let excell_len = width * 2 + height * 2;
-        let excell_item_combinator = Choice::new(vec![
-            Box::new(Optionalize::new(HexInt)),
-            Box::new(Spaces::new(None, 'g')),
-        ]);
+        let excell_item_combinator = choice![Optionalize::new(HexInt), Spaces::new(None, 'g')];
         let (excell_bytes_read, mut excell_data) =
             Seq::new(excell_item_combinator, excell_len).deserialize(ctx, data_bytes)?;
         let mut excell_flat = excell_data.swap_remove(0);
@@ -205,13 +212,26 @@ impl Combinator<Problem> for EasyAsAbcCombinator {
             vec![vec![None; width]; height]
         };
 
-        let problem = (key_size, key_up, key_right, key_down, key_left, center);
-        Some((input.len(), vec![problem]))
+        let body = (key_up, key_right, key_down, key_left, center);
+        Some((data_bytes.len(), vec![body]))
     }
 }
 
 fn easyasabc_combinator() -> impl Combinator<Problem> {
-    Size::new(EasyAsAbcCombinator)
+    // 鍵サイズが省略されている場合は3とみなす
+    Size::new(Map::new(
+        HeaderThen::new(
+            choice![DecInt, Dict::new(3, "")],
+            "/",
+            EasyAsAbcBodyCombinator,
+        ),
+        |(key_size, key_up, key_right, key_down, key_left, center): Problem| {
+            Some((key_size, (key_up, key_right, key_down, key_left, center)))
+        },
+        |(key_size, (key_up, key_right, key_down, key_left, center)): (i32, ProblemBody)| {
+            Some((key_size, key_up, key_right, key_down, key_left, center))
+        },
+    ))
 }
 
 pub fn deserialize_problem(url: &str) -> Option<Problem> {
@@ -233,3 +253,191 @@ pub fn serialize_problem(problem: &Problem) -> Option<String> {
         &ctx,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cspuz_rs::solver::{AnswerKeyRef, AnswerKeyValue, ConstraintId};
+
+    #[test]
+    fn test_easyasabc_first_forced_answer_key() {
+        // 3x3 grid with 3 letters (no empty cells); the clue C above the middle column
+        // forces the middle cell of the top row.
+        let center = vec![vec![None; 3]; 3];
+        let key_up = vec![None, Some(3), None];
+        let key_none = vec![None; 3];
+
+        let mut solver = Solver::new();
+        let letter = &add_constraints(
+            &mut solver,
+            3,
+            &key_up,
+            &key_none,
+            &key_none,
+            &key_none,
+            &center,
+        );
+        solver.add_answer_key_int(letter);
+
+        assert_eq!(
+            solver.first_forced_answer_key(),
+            Some((AnswerKeyRef::Int(1), AnswerKeyValue::Int(3)))
+        );
+    }
+
+    #[test]
+    fn test_easyasabc_exactly_once_per_line() {
+        // The helper must give the same solutions as the inline "exactly once" constraints.
+        let collect_answers = |use_helper: bool| {
+            let mut solver = Solver::new();
+            let letter = &solver.int_var_2d((3, 3), 0, 2);
+            solver.add_answer_key_int(letter);
+            for i in 1..=2 {
+                if use_helper {
+                    util::add_exactly_once_per_line(&mut solver, letter, i, util::LineAxis::Row);
+                    util::add_exactly_once_per_line(&mut solver, letter, i, util::LineAxis::Column);
+                } else {
+                    for y in 0..3 {
+                        solver.add_expr(letter.slice_fixed_y((y, ..)).eq(i).count_true().eq(1));
+                    }
+                    for x in 0..3 {
+                        solver.add_expr(letter.slice_fixed_x((.., x)).eq(i).count_true().eq(1));
+                    }
+                }
+            }
+            let mut answers = solver
+                .answer_iter()
+                .map(|ans| ans.get_unwrap(letter))
+                .collect::<Vec<_>>();
+            answers.sort();
+            answers
+        };
+
+        let expected = collect_answers(false);
+        // Each solution is a 3x3 Latin square with the symbols 0, 1 and 2.
+        assert_eq!(expected.len(), 12);
+        assert_eq!(collect_answers(true), expected);
+    }
+
+    #[test]
+    fn test_easyasabc_explain() {
+        // Groups are numbered column 0..3, then row 0..3.
+        let key_none = vec![None; 3];
+
+        // The clue C above the middle column forces the middle cell of the top row by itself.
+        let center = vec![vec![None; 3]; 3];
+        let key_up = vec![None, Some(3), None];
+        let mut solver = Solver::with_explanation();
+        let letter = &add_constraints(
+            &mut solver,
+            3,
+            &key_up,
+            &key_none,
+            &key_none,
+            &key_none,
+            &center,
+        );
+        solver.add_answer_key_int(letter);
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Int(1), AnswerKeyValue::Int(3)),
+            Some(vec![ConstraintId(1)])
+        );
+
+        // With A and an empty cell given in the top row, the row forces B into the remaining cell.
+        let mut center = vec![vec![None; 3]; 3];
+        center[0][0] = Some(1);
+        center[0][1] = Some(0);
+        let mut solver = Solver::with_explanation();
+        let letter = &add_constraints(
+            &mut solver,
+            2,
+            &key_none,
+            &key_none,
+            &key_none,
+            &key_none,
+            &center,
+        );
+        solver.add_answer_key_int(letter);
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Int(2), AnswerKeyValue::Int(2)),
+            Some(vec![ConstraintId(3)])
+        );
+        assert_eq!(
+            solver.explain(AnswerKeyRef::Int(3), AnswerKeyValue::Int(2)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_easyasabc_no_empty() {
+        let key_up = vec![None, None, Some(4), Some(3)];
+        let key_right = vec![None, None, Some(4), None];
+        let key_down = vec![None, Some(1), None, None];
+        let key_left = vec![None; 4];
+        let center = vec![vec![None; 4]; 4];
+
+        let ans = solve_easyasabc_with_options(
+            4, &key_up, &key_right, &key_down, &key_left, &center, false,
+        );
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = vec![
+            vec![1, 2, 4, 3],
+            vec![3, 4, 2, 1],
+            vec![2, 3, 1, 4],
+            vec![4, 1, 3, 2],
+        ];
+        assert_eq!(ans, crate::util::tests::to_option_2d(expected.clone()));
+
+        // every letter appears exactly once in each row and each column
+        for (i, row) in expected.iter().enumerate() {
+            let mut row = row.clone();
+            let mut column = expected.iter().map(|r| r[i]).collect::<Vec<_>>();
+            row.sort();
+            column.sort();
+            assert_eq!(row, vec![1, 2, 3, 4]);
+            assert_eq!(column, vec![1, 2, 3, 4]);
+        }
+
+        // with as many letters as columns there is no room for empty cells, so both rules agree
+        assert_eq!(
+            solve_easyasabc(4, &key_up, &key_right, &key_down, &key_left, &center),
+            Some(crate::util::tests::to_option_2d(expected))
+        );
+
+        // with fewer letters than columns, one cell in each row must be empty
+        let key_up = vec![Some(1), None, None, None];
+        let key_left = vec![Some(1), None, None, None];
+        let key_right = vec![None; 4];
+        let key_down = vec![None; 4];
+        assert!(solve_easyasabc_with_options(
+            3, &key_up, &key_right, &key_down, &key_left, &center, true,
+        )
+        .is_some());
+        assert_eq!(
+            solve_easyasabc_with_options(
+                3, &key_up, &key_right, &key_down, &key_left, &center, false,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_easyasabc_serializer() {
+        let problem = (
+            2,
+            vec![Some(1), None, None],
+            vec![None, None, None],
+            vec![None, None, None],
+            vec![None, None, Some(2)],
+            vec![vec![None; 3]; 3],
+        );
+        // the payload after the key size contains no `/`
+        let url = "https://puzz.link/p?easyasabc/3/3/2/1m2i";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+
+        // an omitted key size is 3
+        let problem = deserialize_problem("https://puzz.link/p?easyasabc/3/3//1m2i");
+        assert_eq!(problem.map(|p| p.0), Some(3));
+    }
+}
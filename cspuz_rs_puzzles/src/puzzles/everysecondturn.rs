@@ -0,0 +1,163 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{problem_to_url, url_to_problem, Combinator, Grid, Map, MultiDigit};
+use cspuz_rs::solver::{count_true, BoolExpr, BoolVar, Solver};
+
+pub fn solve_everysecondturn(
+    is_circle: &[Vec<bool>],
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(is_circle);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    // The loop is oriented: `to_right` / `to_down` give the direction of each edge.
+    // `parity` of an edge is the parity of the number of circles visited before the edge;
+    // a circle is a turn iff the parity of its incoming edge is 0 (up to flipping all parities),
+    // so that turns and straights alternate along the loop.
+    let to_right = &solver.bool_var_2d((h, w - 1));
+    let to_down = &solver.bool_var_2d((h - 1, w));
+    let parity_horizontal = &solver.bool_var_2d((h, w - 1));
+    let parity_vertical = &solver.bool_var_2d((h - 1, w));
+
+    for y in 0..h {
+        for x in 0..w {
+            if is_circle[y][x] {
+                solver.add_expr(is_passed.at((y, x)));
+            }
+
+            // (is_line, is incoming, parity, is vertical)
+            let mut adj: Vec<(BoolVar, BoolExpr, BoolVar, bool)> = vec![];
+            if y > 0 {
+                adj.push((
+                    is_line.vertical.at((y - 1, x)),
+                    to_down.at((y - 1, x)).expr(),
+                    parity_vertical.at((y - 1, x)),
+                    true,
+                ));
+            }
+            if y < h - 1 {
+                adj.push((
+                    is_line.vertical.at((y, x)),
+                    !to_down.at((y, x)),
+                    parity_vertical.at((y, x)),
+                    true,
+                ));
+            }
+            if x > 0 {
+                adj.push((
+                    is_line.horizontal.at((y, x - 1)),
+                    to_right.at((y, x - 1)).expr(),
+                    parity_horizontal.at((y, x - 1)),
+                    false,
+                ));
+            }
+            if x < w - 1 {
+                adj.push((
+                    is_line.horizontal.at((y, x)),
+                    !to_right.at((y, x)),
+                    parity_horizontal.at((y, x)),
+                    false,
+                ));
+            }
+
+            let n_in = count_true(
+                adj.iter()
+                    .map(|(line, incoming, _, _)| line & incoming)
+                    .collect::<Vec<_>>(),
+            );
+            let n_out = count_true(
+                adj.iter()
+                    .map(|(line, incoming, _, _)| line & !incoming)
+                    .collect::<Vec<_>>(),
+            );
+            solver.add_expr(n_in.eq(is_passed.at((y, x)).ite(1, 0)));
+            solver.add_expr(n_out.eq(is_passed.at((y, x)).ite(1, 0)));
+
+            for (i, (line_in, incoming, parity_in, vertical_in)) in adj.iter().enumerate() {
+                for (j, (line_out, outgoing, parity_out, vertical_out)) in adj.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    let cond = line_in & incoming & line_out & !outgoing;
+                    solver.add_expr(cond.imp(parity_out.iff(parity_in ^ is_circle[y][x])));
+                    if is_circle[y][x] {
+                        let is_turn = vertical_in != vertical_out;
+                        solver.add_expr(cond.imp(parity_in.iff(!is_turn)));
+                    }
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<bool>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Map::new(
+        MultiDigit::new(2, 5),
+        |x: bool| Some(if x { 1 } else { 0 }),
+        |n: i32| Some(n == 1),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "everysecondturn", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["everysecondturn"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        crate::util::tests::to_bool_2d([
+            [1, 0, 0, 0, 1],
+            [1, 1, 0, 1, 0],
+            [0, 0, 1, 0, 0],
+            [1, 0, 0, 0, 0],
+            [0, 0, 0, 1, 0],
+        ])
+    }
+
+    #[test]
+    fn test_everysecondturn_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_everysecondturn(&problem);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::GridEdges {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [1, 0, 0, 1],
+                [0, 0, 0, 0],
+                [0, 1, 0, 0],
+                [1, 0, 1, 0],
+                [0, 1, 1, 1],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [1, 1, 0, 1, 1],
+                [1, 1, 0, 1, 1],
+                [1, 0, 1, 1, 1],
+                [0, 1, 0, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_everysecondturn_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?everysecondturn/5/5/hq4g2";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,227 @@
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    map_2d, problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid,
+    MultiDigit, Sequencer, Size,
+};
+use cspuz_rs::solver::{Array2DImpl, Solver, Value, TRUE};
+
+/// Grand Tour: draw a single loop through the cell centers which visits every cell.
+/// `hints.horizontal[y][x]` (resp. `hints.vertical[y][x]`) is the hint on the edge between (y, x) and (y, x + 1)
+/// (resp. (y + 1, x)): `Some(true)` means the loop must pass the edge, and `Some(false)` means it must not.
+pub fn solve_grandtour(
+    hints: &graph::BoolGridEdgesIrrefutableFacts,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let h = hints.horizontal.len();
+    if h == 0 {
+        return None;
+    }
+    let w = hints.horizontal[0].len() + 1;
+    if !(h * w).is_multiple_of(2) {
+        return None;
+    }
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    graph::single_cycle_covering(
+        &mut solver,
+        is_line,
+        Value::<Array2DImpl<_>>::new((h, w), vec![TRUE; h * w]),
+    );
+
+    for y in 0..h {
+        for x in 0..(w - 1) {
+            if let Some(b) = hints.horizontal[y][x] {
+                solver.add_expr(is_line.horizontal.at((y, x)).iff(b));
+            }
+        }
+    }
+    for y in 0..(h - 1) {
+        for x in 0..w {
+            if let Some(b) = hints.vertical[y][x] {
+                solver.add_expr(is_line.vertical.at((y, x)).iff(b));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = graph::BoolGridEdgesIrrefutableFacts;
+
+/// Serializes the edge hints: edges between horizontally adjacent cells first, followed by edges between
+/// vertically adjacent cells, each packed 3 edges per character (0: no hint, 1: line, 2: cross).
+struct EdgeHints;
+
+fn hint_to_i32(hint: &Option<bool>) -> i32 {
+    match hint {
+        None => 0,
+        Some(true) => 1,
+        Some(false) => 2,
+    }
+}
+
+fn i32_to_hint(n: &i32) -> Option<bool> {
+    match n {
+        1 => Some(true),
+        2 => Some(false),
+        _ => None,
+    }
+}
+
+impl Combinator<Problem> for EdgeHints {
+    fn serialize(&self, ctx: &Context, input: &[Problem]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let height = ctx.height.unwrap();
+        let width = ctx.width.unwrap();
+
+        let horizontal = map_2d(&input[0].horizontal, hint_to_i32);
+        let vertical = map_2d(&input[0].vertical, hint_to_i32);
+
+        let ctx_grid = ContextBasedGrid::new(MultiDigit::new(3, 3));
+        let mut ret = vec![];
+        let (_, app) = ctx_grid.serialize(
+            &Context {
+                height: Some(height),
+                width: Some(width - 1),
+                ..*ctx
+            },
+            &[horizontal],
+        )?;
+        ret.extend(app);
+        let (_, app) = ctx_grid.serialize(
+            &Context {
+                height: Some(height - 1),
+                width: Some(width),
+                ..*ctx
+            },
+            &[vertical],
+        )?;
+        ret.extend(app);
+
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Problem>)> {
+        let height = ctx.height.unwrap();
+        let width = ctx.width.unwrap();
+        if height == 0 || width == 0 {
+            return None;
+        }
+        let mut sequencer = Sequencer::new(input);
+        let ctx_grid = ContextBasedGrid::new(MultiDigit::new(3, 3));
+
+        let horizontal = sequencer.deserialize_one_elem(
+            &Context {
+                height: Some(height),
+                width: Some(width - 1),
+                ..*ctx
+            },
+            &ctx_grid,
+        )?;
+        let vertical = sequencer.deserialize_one_elem(
+            &Context {
+                height: Some(height - 1),
+                width: Some(width),
+                ..*ctx
+            },
+            &ctx_grid,
+        )?;
+
+        Some((
+            sequencer.n_read(),
+            vec![graph::GridEdges {
+                horizontal: map_2d(&horizontal, i32_to_hint),
+                vertical: map_2d(&vertical, i32_to_hint),
+            }],
+        ))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(EdgeHints)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.horizontal.len();
+    let width = problem.horizontal[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "grandtour",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["grandtour"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = graph::GridEdges {
+            horizontal: vec![vec![None; 3]; 4],
+            vertical: vec![vec![None; 4]; 3],
+        };
+        ret.horizontal[1][1] = Some(false);
+        ret.vertical[0][1] = Some(true);
+        ret
+    }
+
+    #[test]
+    fn test_grandtour_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_grandtour(&problem);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [1, 0, 1],
+                [0, 0, 0],
+                [0, 1, 0],
+                [1, 1, 1],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [1, 1, 1, 1],
+                [1, 1, 1, 1],
+                [1, 0, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_grandtour_odd_cells() {
+        let problem = graph::GridEdges {
+            horizontal: vec![vec![None; 2]; 3],
+            vertical: vec![vec![None; 3]; 2],
+        };
+        assert!(solve_grandtour(&problem).is_none());
+    }
+
+    #[test]
+    fn test_grandtour_empty_board() {
+        let problem = graph::GridEdges {
+            horizontal: vec![],
+            vertical: vec![],
+        };
+        assert!(solve_grandtour(&problem).is_none());
+        assert!(deserialize_problem("https://puzz.link/p?grandtour/4/0/").is_none());
+        assert!(deserialize_problem("https://puzz.link/p?grandtour/0/4/").is_none());
+    }
+
+    #[test]
+    fn test_grandtour_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?grandtour/4/4/06003000";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,156 @@
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    choice, problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid,
+    HexInt, Optionalize, Rooms, Size, Spaces, Tuple2,
+};
+use cspuz_rs::solver::{all, BoolVarArray1D, Solver};
+
+/// Adds a constraint that two numbers on a line with no other number between them are apart by exactly
+/// as many cells as their difference. `size[i]` is the number placed in the `i`-th cell if it has one.
+fn add_distance_constraints(solver: &mut Solver, has_number: &BoolVarArray1D, size: &[i32]) {
+    let n = size.len();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if (j - i - 1) as i32 == (size[i] - size[j]).abs() {
+                continue;
+            }
+            let mut cond = vec![has_number.at(i).expr(), has_number.at(j).expr()];
+            for k in (i + 1)..j {
+                cond.push(!has_number.at(k));
+            }
+            solver.add_expr(!all(cond));
+        }
+    }
+}
+
+/// Hanare-gumi: place exactly one number in each room, which equals the number of cells in the room.
+/// When two numbers are in the same row or column with no other number between them,
+/// the number of cells between them equals the difference of the numbers.
+///
+/// Returns the number placed in each cell, where `Some(0)` means that the cell has no number.
+pub fn solve_hanare(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Vec<Option<i32>>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = borders.base_shape();
+
+    let rooms = graph::borders_to_rooms(borders);
+    let mut size = vec![vec![0; w]; h];
+    for room in &rooms {
+        for &(y, x) in room {
+            size[y][x] = room.len() as i32;
+        }
+    }
+
+    let mut solver = Solver::new();
+    let has_number = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(has_number);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                if n != size[y][x] {
+                    return None;
+                }
+                solver.add_expr(has_number.at((y, x)));
+            }
+        }
+    }
+
+    for room in &rooms {
+        solver.add_expr(has_number.select(room).count_true().eq(1));
+    }
+
+    for y in 0..h {
+        add_distance_constraints(&mut solver, &has_number.slice_fixed_y((y, ..)), &size[y]);
+    }
+    for x in 0..w {
+        let size_column = (0..h).map(|y| size[y][x]).collect::<Vec<_>>();
+        add_distance_constraints(
+            &mut solver,
+            &has_number.slice_fixed_x((.., x)),
+            &size_column,
+        );
+    }
+
+    solver.irrefutable_facts().map(|f| {
+        let has_number = f.get(has_number);
+        (0..h)
+            .map(|y| {
+                (0..w)
+                    .map(|x| has_number[y][x].map(|b| if b { size[y][x] } else { 0 }))
+                    .collect()
+            })
+            .collect()
+    })
+}
+
+pub type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<Option<i32>>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(choice![Optionalize::new(HexInt), Spaces::new(None, 'g')]),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "hanare",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["hanare"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[0, 1, 1, 1], [1, 1, 0, 0], [0, 1, 1, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[1, 0, 1], [0, 0, 1], [1, 1, 1], [0, 1, 0]]),
+        };
+        let mut clues = vec![vec![None; 4]; 4];
+        clues[2][1] = Some(1);
+        (borders, clues)
+    }
+
+    #[test]
+    fn test_hanare_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_hanare(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = vec![
+            vec![0, 2, 0, 1],
+            vec![0, 0, 5, 0],
+            vec![0, 1, 0, 0],
+            vec![3, 0, 4, 0],
+        ];
+        assert_eq!(ans, crate::util::tests::to_option_2d(expected));
+    }
+
+    #[test]
+    fn test_hanare_clue_mismatch() {
+        let (borders, mut clues) = problem_for_tests();
+        clues[2][1] = None;
+        clues[1][2] = Some(4);
+        assert_eq!(solve_hanare(&borders, &clues), None);
+    }
+
+    #[test]
+    fn test_hanare_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?hanare/4/4/kugfhgo1l";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
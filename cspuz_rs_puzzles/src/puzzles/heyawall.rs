@@ -0,0 +1,95 @@
+use crate::puzzles::heyawake;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{problem_to_url_with_context, url_to_problem, Context};
+use cspuz_rs::solver::{sum, Solver};
+
+/// Returns the number of sides of the cell (y, x) which are walls, that is, room borders or the outer edge of the grid.
+fn cell_border_count(borders: &graph::InnerGridEdges<Vec<Vec<bool>>>, y: usize, x: usize) -> i32 {
+    let (h, w) = borders.base_shape();
+    let mut ret = 0;
+    if y == 0 || borders.horizontal[y - 1][x] {
+        ret += 1;
+    }
+    if y == h - 1 || borders.horizontal[y][x] {
+        ret += 1;
+    }
+    if x == 0 || borders.vertical[y][x - 1] {
+        ret += 1;
+    }
+    if x == w - 1 || borders.vertical[y][x] {
+        ret += 1;
+    }
+    ret
+}
+
+/// Heyawall: a variant of Heyawake where the number in a room is the total number of walls
+/// (see `cell_border_count`) touching the shaded cells in the room, instead of the number of shaded cells.
+pub fn solve_heyawall(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Option<i32>],
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    let rooms = graph::borders_to_rooms(borders);
+    assert_eq!(rooms.len(), clues.len());
+    heyawake::add_constraints(&mut solver, is_black, borders, &vec![None; rooms.len()]);
+
+    for i in 0..rooms.len() {
+        if let Some(n) = clues[i] {
+            let walls = rooms[i]
+                .iter()
+                .map(|&(y, x)| is_black.at((y, x)).ite(cell_border_count(borders, y, x), 0));
+            solver.add_expr(sum(walls).eq(n));
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_black))
+}
+
+type Problem = heyawake::Problem;
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        heyawake::combinator(),
+        "heyawall",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(heyawake::combinator(), &["heyawall"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heyawall_problem() {
+        let url = "https://puzz.link/p?heyawall/4/4/brgcao00g4g0";
+        let problem = deserialize_problem(url);
+        assert!(problem.is_some());
+        let problem = problem.unwrap();
+        assert_eq!(serialize_problem(&problem), Some(String::from(url)));
+        let (borders, clues) = problem;
+
+        let ans = solve_heyawall(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_bool_2d([
+            [0, 0, 0, 0],
+            [0, 0, 1, 0],
+            [0, 1, 0, 0],
+            [1, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+}
@@ -2,19 +2,12 @@ use crate::puzzles::masyu::MasyuClue;
 use crate::util;
 use cspuz_rs::graph;
 use cspuz_rs::serializer::{
-    problem_to_url, url_to_problem, Choice, Combinator, ContextBasedGrid, DecInt, Dict, Grid,
-    HexInt, Map, MaybeSkip, MultiDigit, Optionalize, PrefixAndSuffix, Rooms, Size, Spaces, Tuple2,
-    Tuple3,
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    DecInt, Dict, Grid, HexInt, Map, MaybeSkip, MultiDigit, Optionalize, PrefixAndSuffix, Rooms,
+    Size, Spaces, Tuple2, Tuple3,
 };
 use cspuz_rs::solver::{Config, GraphDivisionMode, Solver};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum IsowatariClue {
-    None,
-    White,
-    Black,
-}
-
 pub fn solve_isowatari(size: i32, clues: &[Vec<i32>]) -> Option<Vec<Vec<Option<bool>>>> {
     let (h, w) = util::infer_shape(clues);
 
@@ -24,24 +17,13 @@ pub fn solve_isowatari(size: i32, clues: &[Vec<i32>]) -> Option<Vec<Vec<Option<b
     None
 }
 
-type Problem = (i32, Vec<Vec<IsowatariClue>>, Option<Vec<Vec<bool>>>);
+type Problem = (i32, Vec<Vec<MasyuClue>>, Option<Vec<Vec<bool>>>);
 
 fn combinator() -> impl Combinator<Problem> {
     let circle_combinator = ContextBasedGrid::new(Map::new(
         MultiDigit::new(3, 3),
-        |x: IsowatariClue| {
-            Some(match x {
-                IsowatariClue::None => 0,
-                IsowatariClue::White => 1,
-                IsowatariClue::Black => 2,
-            })
-        },
-        |n: i32| match n {
-            0 => Some(IsowatariClue::None),
-            1 => Some(IsowatariClue::White),
-            2 => Some(IsowatariClue::Black),
-            _ => None,
-        },
+        |x: MasyuClue| Some(x.to_digit()),
+        MasyuClue::from_digit,
     ));
 
     let empty_combinator = ContextBasedGrid::new(Map::new(
@@ -61,9 +43,42 @@ fn combinator() -> impl Combinator<Problem> {
 }
 
 pub fn serialize_problem(problem: &Problem) -> Option<String> {
-    problem_to_url(combinator(), "isowatari", problem.clone())
+    let (h, w) = util::infer_shape(&problem.1);
+    problem_to_url_with_context(
+        combinator(),
+        "isowatari",
+        problem.clone(),
+        &Context::sized(h, w),
+    )
 }
 
 pub fn deserialize_problem(url: &str) -> Option<Problem> {
     url_to_problem(combinator(), &["isowatari"], url)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circles_for_tests() -> Vec<Vec<MasyuClue>> {
+        let mut ret = vec![vec![MasyuClue::None; 3]; 2];
+        ret[0][0] = MasyuClue::White;
+        ret[1][2] = MasyuClue::Black;
+        ret
+    }
+
+    #[test]
+    fn test_isowatari_serializer_with_empty_cells() {
+        let empty = crate::util::tests::to_bool_2d([[0, 0, 1], [0, 0, 0]]);
+        let problem = (2, circles_for_tests(), Some(empty));
+        let url = "https://puzz.link/p?isowatari/3/2/2/9240";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+
+    #[test]
+    fn test_isowatari_serializer_without_empty_cells() {
+        let problem = (2, circles_for_tests(), None);
+        let url = "https://puzz.link/p?isowatari/3/2/2/92";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
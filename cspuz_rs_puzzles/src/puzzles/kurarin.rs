@@ -2,6 +2,7 @@ use crate::util;
 use cspuz_rs::{graph, serializer};
 use cspuz_rs::serializer::{Choice, Combinator, Context, FixedLengthHexInt, Optionalize, Size, Spaces, UnlimitedSeq};
 use cspuz_rs::solver::Solver;
+use std::ops::RangeInclusive;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum KurarinClue {
@@ -11,9 +12,22 @@ pub enum KurarinClue {
     Black,
 }
 
-
+/// If `loop_length` is given, the loop consists of exactly that many edges.
 pub fn solve_kurarin(
     clues: &[Vec<KurarinClue>],
+    loop_length: Option<i32>,
+) -> Option<(graph::BoolGridEdgesIrrefutableFacts, Vec<Vec<Option<bool>>>)> {
+    solve_kurarin_with_options(clues, loop_length, true)
+}
+
+/// `solve_kurarin` with an extra rule switch.
+///
+/// - If `full_coverage` is `true` (the standard rule), the loop passes through every non-black cell.
+/// - If `full_coverage` is `false`, the loop only has to avoid black cells and may leave some white cells unvisited.
+pub fn solve_kurarin_with_options(
+    clues: &[Vec<KurarinClue>],
+    loop_length: Option<i32>,
+    full_coverage: bool,
 ) -> Option<(graph::BoolGridEdgesIrrefutableFacts, Vec<Vec<Option<bool>>>)> {
     let (h_clue, w_clue) = util::infer_shape(clues);
     let h = (h_clue + 1) / 2;
@@ -24,15 +38,23 @@ pub fn solve_kurarin(
     solver.add_answer_key_bool(&is_line.horizontal);
     solver.add_answer_key_bool(&is_line.vertical);
 
-    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
     let is_black = &solver.bool_var_2d((h, w));
     solver.add_answer_key_bool(is_black);
-    solver.add_expr(is_passed ^ is_black);
+    let is_passed = &if full_coverage {
+        graph::single_cycle_covering(&mut solver, is_line, !is_black)
+    } else {
+        graph::single_cycle_grid_edges(&mut solver, is_line)
+    };
+    solver.add_expr(is_passed.imp(!is_black));
+    if let Some(n) = loop_length {
+        solver.add_expr((is_line.horizontal.count_true() + is_line.vertical.count_true()).eq(n));
+    }
 
     for y in 0..h_clue {
         for x in 0..w_clue {
-            let b = is_black.slice(((y / 2)..=((y + 1) / 2), (x / 2)..=((x + 1) / 2))).count_true();
-            let w = (!is_black).slice(((y / 2)..=((y + 1) / 2), (x / 2)..=((x + 1) / 2))).count_true();
+            let window = clue_window(y, x);
+            let b = is_black.slice(window.clone()).count_true();
+            let w = (!is_black).slice(window).count_true();
 
             match clues[y][x] {
                 KurarinClue::None => {}
@@ -54,6 +76,15 @@ pub fn solve_kurarin(
         .map(|f| (f.get(is_line), f.get(is_black)))
 }
 
+/// Returns the range of cells covered by the clue at (y, x) of the doubled grid.
+///
+/// A clue at even `y` (resp. `x`) lies on the center of a cell row (resp. column) and covers only that row,
+/// while a clue at odd `y` lies on the border of two rows and covers both of them. Thus the window is
+/// 1x1 on cell centers, 1x2 or 2x1 on cell borders and 2x2 on cell corners; it is never empty,
+/// even for clues on the outermost dots.
+fn clue_window(y: usize, x: usize) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+    ((y / 2)..=((y + 1) / 2), (x / 2)..=((x + 1) / 2))
+}
 
 impl KurarinClue {
     fn to_digit(self) -> i32 {
@@ -184,18 +215,162 @@ pub fn deserialize_problem(url: &str) -> Option<Problem> {
 }
 
 /// `Problem`をURL文字列にシリアライズします。
+/// 盤面が空の場合は`None`を返します。
 pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    if problem.is_empty() || problem[0].is_empty() {
+        return None;
+    }
     // ドット盤面のサイズからセルのサイズを逆算
     let height = (problem.len() + 1) / 2;
-    let width = if height > 0 { (problem[0].len() + 1) / 2 } else { 0 };
-
-    let ctx = Context::sized(height, width);
-    let (_, body) = kurarin_combinator().serialize(&ctx, &[problem.clone()])?;
-
-    let puzzle_kind = "kurarin";
-    let prefix = "https://puzz.link/p?";
-    String::from_utf8(body).ok().map(|body_str| {
-        format!("{}{}?{}/{}/{}", prefix, puzzle_kind, width, height, body_str)
-            .replace("/?","/") // puzz.linkがwidth/heightの前に?を要求しない場合への対応
-    })
-}
\ No newline at end of file
+    let width = (problem[0].len() + 1) / 2;
+
+    serializer::problem_to_url_with_context(
+        kurarin_combinator(),
+        "kurarin",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        // 5 * 5 = 25 clues, so that the last clue pair is padded with `None`
+        let n = KurarinClue::None;
+        let b = KurarinClue::Black;
+        let g = KurarinClue::Gray;
+        let w = KurarinClue::White;
+        vec![
+            vec![w, n, n, n, b],
+            vec![n, n, g, n, n],
+            vec![n, n, n, n, n],
+            vec![n, b, n, w, n],
+            vec![n, n, n, n, g],
+        ]
+    }
+
+    #[test]
+    fn test_kurarin_clue_window() {
+        assert_eq!(clue_window(0, 0), (0..=0, 0..=0));
+        assert_eq!(clue_window(0, 1), (0..=0, 0..=1));
+        assert_eq!(clue_window(3, 4), (1..=2, 2..=2));
+        assert_eq!(clue_window(3, 3), (1..=2, 1..=2));
+    }
+
+    #[test]
+    fn test_kurarin_outermost_clues() {
+        let n = KurarinClue::None;
+        let b = KurarinClue::Black;
+        let g = KurarinClue::Gray;
+
+        // black clue on a corner cell (1x1 window) and gray clue on the border of two cells (1x2 window)
+        let mut problem = vec![vec![n; 5]; 5];
+        problem[0][0] = b;
+        problem[0][1] = g;
+        let ans = solve_kurarin(&problem, None);
+        assert!(ans.is_some());
+        let (_, is_black) = ans.unwrap();
+        assert_eq!(is_black[0][0], Some(true));
+        assert_eq!(is_black[0][1], Some(false));
+
+        // gray clue on a corner cell can never be satisfied
+        let mut problem = vec![vec![n; 5]; 5];
+        problem[4][4] = g;
+        assert!(solve_kurarin(&problem, None).is_none());
+    }
+
+    #[test]
+    fn test_kurarin_full_coverage() {
+        // a white clue in the middle of a 2x2 board rules out the empty loop
+        let n = KurarinClue::None;
+        let w = KurarinClue::White;
+        let problem = vec![vec![n, n, n], vec![n, w, n], vec![n, n, n]];
+
+        let ans = solve_kurarin_with_options(&problem, None, true);
+        assert!(ans.is_some());
+        let (is_line, is_black) = ans.unwrap();
+        assert_eq!(is_black, vec![vec![Some(false); 2]; 2]);
+        assert_eq!(is_line.horizontal, vec![vec![Some(true)]; 2]);
+        assert_eq!(is_line.vertical, vec![vec![Some(true); 2]]);
+
+        // without full coverage, white cells need not be visited by the loop
+        let ans = solve_kurarin_with_options(&problem, None, false);
+        assert!(ans.is_some());
+        let (_, is_black) = ans.unwrap();
+        assert_eq!(is_black[0][0], None);
+    }
+
+    #[test]
+    fn test_kurarin_loop_length() {
+        // without clues, a 2x3 board admits both the 6-edge loop around the whole board
+        // and 4-edge loops around either 2x2 half
+        let problem = vec![vec![KurarinClue::None; 5]; 3];
+
+        let ans = solve_kurarin(&problem, None);
+        assert!(ans.is_some());
+        let (_, is_black) = ans.unwrap();
+        assert_eq!(is_black[0][0], None);
+
+        let ans = solve_kurarin(&problem, Some(6));
+        assert!(ans.is_some());
+        let (is_line, is_black) = ans.unwrap();
+        assert_eq!(is_black, vec![vec![Some(false); 3]; 2]);
+        assert_eq!(is_line.horizontal, vec![vec![Some(true); 2]; 2]);
+        assert_eq!(
+            is_line.vertical,
+            vec![vec![Some(true), Some(false), Some(true)]]
+        );
+        assert_eq!(
+            graph::trace_single_cycle(&is_line),
+            Some(vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 1), (1, 0)])
+        );
+
+        assert!(solve_kurarin(&problem, Some(5)).is_none());
+    }
+
+    #[test]
+    fn test_kurarin_serializer() {
+        let problem = problem_for_tests();
+        // Encoded by hand (not by `serialize_problem`) following the puzz.link format: the clues are read
+        // in row-major order and packed in pairs as `(first << 2) | second` with None = 0, Black = 1,
+        // Gray = 2 and White = 3, so each pair is one hex digit; runs of 1-20 empty pairs are `g`-`z`.
+        //   (W, -) = c, 1 empty pair = g, (B, -) = 4, (-, G) = 2, 4 empty pairs = j,
+        //   (B, -) = 4, (W, -) = c, 2 empty pairs = h, (G, padding) = 8
+        let url = "https://puzz.link/p?kurarin/3/3/cg42j4ch8";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+
+    #[test]
+    fn test_kurarin_serializer_empty() {
+        assert_eq!(serialize_problem(&vec![]), None);
+        assert_eq!(serialize_problem(&vec![vec![]]), None);
+    }
+
+    #[test]
+    fn test_kurarin_serializer_roundtrip() {
+        // the number of clues (2h - 1) * (2w - 1) is always odd, so the last pair is always padded
+        util::tests::roundtrip_property(
+            kurarin_combinator(),
+            |rng| {
+                let height = rng.next(5) + 1;
+                let width = rng.next(5) + 1;
+                let problem = (0..(height * 2 - 1))
+                    .map(|_| {
+                        (0..(width * 2 - 1))
+                            .map(|_| match rng.next(6) {
+                                0 => KurarinClue::White,
+                                1 => KurarinClue::Gray,
+                                2 => KurarinClue::Black,
+                                _ => KurarinClue::None,
+                            })
+                            .collect()
+                    })
+                    .collect::<Problem>();
+                (Context::sized(height, width), problem)
+            },
+            200,
+        );
+    }
+}
@@ -2,7 +2,7 @@ use cspuz_rs::graph;
 use cspuz_rs::serializer::{
     problem_to_url_with_context, url_to_problem, Combinator, Context, Rooms, Size,
 };
-use cspuz_rs::solver::{any, count_true, Solver, FALSE};
+use cspuz_rs::solver::{any, count_true, BoolVarArray2D, IntVarArray1D, Solver, FALSE};
 
 pub fn solve_lits(
     borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
@@ -15,7 +15,21 @@ pub fn solve_lits(
     let is_black = &solver.bool_var_2d((h, w));
     solver.add_answer_key_bool(is_black);
 
-    graph::active_vertices_connected_2d(&mut solver, is_black);
+    add_constraints(&mut solver, is_black, borders);
+
+    solver.irrefutable_facts().map(|f| f.get(is_black))
+}
+
+/// Adds the LITS rules on `is_black` and returns the tetromino type of each room
+/// (in the order of `graph::borders_to_rooms`): 0 for L, 1 for I, 2 for T and 3 for S.
+pub(super) fn add_constraints(
+    solver: &mut Solver,
+    is_black: &BoolVarArray2D,
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+) -> IntVarArray1D {
+    let (h, w) = is_black.shape();
+
+    graph::active_vertices_connected_2d(solver, is_black);
     solver.add_expr(
         !(is_black.slice((..(h - 1), ..(w - 1)))
             & is_black.slice((..(h - 1), 1..))
@@ -134,7 +148,7 @@ pub fn solve_lits(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(is_black))
+    room_kind.clone()
 }
 
 type Problem = graph::InnerGridEdges<Vec<Vec<bool>>>;
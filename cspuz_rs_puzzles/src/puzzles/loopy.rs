@@ -0,0 +1,233 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid, Map,
+    MultiDigit, Seq, Size, Tuple2,
+};
+use cspuz_rs::solver::Solver;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LoopyCell {
+    Free,
+    OnLoop,
+    OffLoop,
+}
+
+/// Finds a single loop passing through cells orthogonally, subject to the per-cell specification
+/// `cell_spec` and the per-edge specification `edge_spec` (`Some(b)` forces the edge to be `b`).
+pub fn solve_loopy(
+    cell_spec: &[Vec<LoopyCell>],
+    edge_spec: &graph::BoolGridEdgesIrrefutableFacts,
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(cell_spec);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    for y in 0..h {
+        for x in 0..w {
+            match cell_spec[y][x] {
+                LoopyCell::Free => (),
+                LoopyCell::OnLoop => solver.add_expr(is_passed.at((y, x))),
+                LoopyCell::OffLoop => solver.add_expr(!is_passed.at((y, x))),
+            }
+        }
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if x < w - 1 {
+                if let Some(b) = edge_spec.horizontal[y][x] {
+                    solver.add_expr(is_line.horizontal.at((y, x)).iff(b));
+                }
+            }
+            if y < h - 1 {
+                if let Some(b) = edge_spec.vertical[y][x] {
+                    solver.add_expr(is_line.vertical.at((y, x)).iff(b));
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = (Vec<Vec<LoopyCell>>, graph::BoolGridEdgesIrrefutableFacts);
+
+fn edge_item_combinator() -> impl Combinator<Option<bool>> {
+    Map::new(
+        MultiDigit::new(3, 3),
+        |x: Option<bool>| {
+            Some(match x {
+                None => 0,
+                Some(true) => 1,
+                Some(false) => 2,
+            })
+        },
+        |n: i32| match n {
+            0 => Some(None),
+            1 => Some(Some(true)),
+            2 => Some(Some(false)),
+            _ => None,
+        },
+    )
+}
+
+/// Serializes horizontal edges (H * (W - 1)) followed by vertical edges ((H - 1) * W), in row-major order.
+struct LoopyEdgesCombinator;
+
+impl Combinator<graph::BoolGridEdgesIrrefutableFacts> for LoopyEdgesCombinator {
+    fn serialize(
+        &self,
+        ctx: &Context,
+        input: &[graph::BoolGridEdgesIrrefutableFacts],
+    ) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+        let edges = &input[0];
+
+        let mut seq = vec![];
+        for row in edges.horizontal.iter().chain(edges.vertical.iter()) {
+            seq.extend(row.iter().cloned());
+        }
+        let (_, ret) = Seq::new(edge_item_combinator(), seq.len()).serialize(ctx, &[seq])?;
+
+        Some((1, ret))
+    }
+
+    fn deserialize(
+        &self,
+        ctx: &Context,
+        input: &[u8],
+    ) -> Option<(usize, Vec<graph::BoolGridEdgesIrrefutableFacts>)> {
+        let height = ctx.height?;
+        let width = ctx.width?;
+        if width < 2 || height < 2 {
+            return None;
+        }
+
+        let n_horizontal = height * (width - 1);
+        let n_vertical = (height - 1) * width;
+        let (n_read, seq) =
+            Seq::new(edge_item_combinator(), n_horizontal + n_vertical).deserialize(ctx, input)?;
+        let seq = seq.into_iter().next()?;
+
+        let horizontal = seq[..n_horizontal]
+            .chunks(width - 1)
+            .map(|row| row.to_vec())
+            .collect();
+        let vertical = seq[n_horizontal..]
+            .chunks(width)
+            .map(|row| row.to_vec())
+            .collect();
+
+        Some((
+            n_read,
+            vec![graph::GridEdges {
+                horizontal,
+                vertical,
+            }],
+        ))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        ContextBasedGrid::new(Map::new(
+            MultiDigit::new(3, 3),
+            |x: LoopyCell| {
+                Some(match x {
+                    LoopyCell::Free => 0,
+                    LoopyCell::OnLoop => 1,
+                    LoopyCell::OffLoop => 2,
+                })
+            },
+            |n: i32| match n {
+                0 => Some(LoopyCell::Free),
+                1 => Some(LoopyCell::OnLoop),
+                2 => Some(LoopyCell::OffLoop),
+                _ => None,
+            },
+        )),
+        LoopyEdgesCombinator,
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (h, w) = util::infer_shape(&problem.0);
+    problem_to_url_with_context(
+        combinator(),
+        "loopy",
+        problem.clone(),
+        &Context::sized(h, w),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["loopy"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut cell_spec = vec![vec![LoopyCell::Free; 5]; 4];
+        cell_spec[0][0] = LoopyCell::OffLoop;
+        cell_spec[0][3] = LoopyCell::OffLoop;
+        cell_spec[1][0] = LoopyCell::OnLoop;
+        cell_spec[2][4] = LoopyCell::OnLoop;
+
+        let mut edge_spec = graph::GridEdges {
+            horizontal: vec![vec![None; 4]; 4],
+            vertical: vec![vec![None; 5]; 3],
+        };
+        edge_spec.horizontal[3][3] = Some(false);
+        edge_spec.vertical[1][1] = Some(true);
+
+        (cell_spec, edge_spec)
+    }
+
+    #[test]
+    fn test_loopy_problem() {
+        let (cell_spec, edge_spec) = problem_for_tests();
+        let ans = solve_loopy(&cell_spec, &edge_spec);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::GridEdges {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [0, 0, 0, 0],
+                [1, 0, 1, 1],
+                [0, 1, 0, 1],
+                [1, 1, 1, 0],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [0, 0, 0, 0, 0],
+                [1, 1, 1, 0, 1],
+                [1, 0, 0, 1, 0],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_loopy_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?loopy/5/4/ij0010000000i03000";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+
+    #[test]
+    fn test_loopy_deserializer_degenerate_size() {
+        assert!(deserialize_problem("https://puzz.link/p?loopy/1/4/00").is_none());
+        assert!(deserialize_problem("https://puzz.link/p?loopy/4/1/00").is_none());
+        assert!(deserialize_problem("https://puzz.link/p?loopy/5/0/").is_none());
+        assert!(deserialize_problem("https://puzz.link/p?loopy/0/5/").is_none());
+    }
+}
@@ -0,0 +1,176 @@
+use cspuz_rs::complex_constraints::arrow_points_to_max;
+use cspuz_rs::graph;
+use cspuz_rs::items::Arrow;
+use cspuz_rs::serializer::{
+    choice, problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid,
+    Dict, Map, MultiDigit, Rooms, Size, Spaces, Tuple2,
+};
+use cspuz_rs::solver::Solver;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MakaroCell {
+    Empty,
+    Num(i32),
+    Black,
+    Arrow(Arrow),
+}
+
+impl MakaroCell {
+    fn is_black(self) -> bool {
+        matches!(self, MakaroCell::Black | MakaroCell::Arrow(_))
+    }
+}
+
+/// Returns the numbers filled in the cells. Black cells (including arrow cells) are `Some(0)`.
+pub fn solve_makaro(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    cells: &[Vec<MakaroCell>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = borders.base_shape();
+
+    let (rooms, room_id) = graph::borders_to_rooms_with_ids(borders);
+    let rooms = rooms
+        .into_iter()
+        .map(|room| {
+            room.into_iter()
+                .filter(|&(y, x)| !cells[y][x].is_black())
+                .collect::<Vec<_>>()
+        })
+        .filter(|room| !room.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut ranges = vec![vec![(0, 0); w]; h];
+    for room in &rooms {
+        for &(y, x) in room {
+            ranges[y][x] = (1, room.len() as i32);
+        }
+    }
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d_from_ranges((h, w), &ranges);
+    solver.add_answer_key_int(num);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let MakaroCell::Num(n) = cells[y][x] {
+                solver.add_expr(num.at((y, x)).eq(n));
+            }
+        }
+    }
+
+    for room in &rooms {
+        let room_nums = num.select(room);
+        for i in 1..=room.len() {
+            solver.add_expr(room_nums.eq(i as i32).count_true().eq(1));
+        }
+    }
+
+    // orthogonally adjacent cells across a room border have different numbers
+    for y in 0..h {
+        for x in 0..w {
+            if cells[y][x].is_black() {
+                continue;
+            }
+            if y < h - 1 && !cells[y + 1][x].is_black() && room_id[y][x] != room_id[y + 1][x] {
+                solver.add_expr(num.at((y, x)).ne(num.at((y + 1, x))));
+            }
+            if x < w - 1 && !cells[y][x + 1].is_black() && room_id[y][x] != room_id[y][x + 1] {
+                solver.add_expr(num.at((y, x)).ne(num.at((y, x + 1))));
+            }
+        }
+    }
+
+    // an arrow points to the strictly largest number among the orthogonal neighbors;
+    // black neighbors are fixed to 0, so they never exceed the target
+    for y in 0..h {
+        for x in 0..w {
+            if let MakaroCell::Arrow(dir) = cells[y][x] {
+                if !arrow_points_to_max(&mut solver, num, (y, x), dir) {
+                    return None;
+                }
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(num))
+}
+
+pub type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<MakaroCell>>);
+
+fn cell_combinator() -> impl Combinator<MakaroCell> {
+    choice![
+        Dict::new(MakaroCell::Black, "."),
+        Dict::new(MakaroCell::Arrow(Arrow::Up), "a"),
+        Dict::new(MakaroCell::Arrow(Arrow::Down), "b"),
+        Dict::new(MakaroCell::Arrow(Arrow::Left), "c"),
+        Dict::new(MakaroCell::Arrow(Arrow::Right), "d"),
+        // numbers 1..=10 are encoded as a single decimal digit of (n - 1)
+        Map::new(
+            MultiDigit::new(10, 1),
+            |c: MakaroCell| match c {
+                MakaroCell::Num(n) => Some(n - 1),
+                _ => None,
+            },
+            |n: i32| Some(MakaroCell::Num(n + 1)),
+        ),
+        Spaces::new(MakaroCell::Empty, 'g'),
+    ]
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(Rooms, ContextBasedGrid::new(cell_combinator())))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "makaro",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["makaro"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[1, 1, 1], [0, 1, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 0], [1, 1], [0, 1]]),
+        };
+
+        let e = MakaroCell::Empty;
+        let cells = vec![
+            vec![MakaroCell::Num(1), e, e],
+            vec![e, MakaroCell::Arrow(Arrow::Up), e],
+            vec![e, e, e],
+        ];
+
+        (borders, cells)
+    }
+
+    #[test]
+    fn test_makaro_problem() {
+        let (borders, cells) = problem_for_tests();
+        let ans = solve_makaro(&borders, &cells);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_2d([[1, 3, 2], [2, 0, 1], [3, 1, 2]]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_makaro_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?makaro/3/3/6gt00iaj";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
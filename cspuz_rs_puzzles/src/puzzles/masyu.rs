@@ -10,6 +10,27 @@ pub enum MasyuClue {
     Black,
 }
 
+impl MasyuClue {
+    /// Returns the digit representing this clue in the puzz.link encoding (0: none, 1: white, 2: black).
+    pub fn to_digit(self) -> i32 {
+        match self {
+            MasyuClue::None => 0,
+            MasyuClue::White => 1,
+            MasyuClue::Black => 2,
+        }
+    }
+
+    /// Inverse of `to_digit`. Returns `None` for a digit which does not represent any clue.
+    pub fn from_digit(digit: i32) -> Option<MasyuClue> {
+        match digit {
+            0 => Some(MasyuClue::None),
+            1 => Some(MasyuClue::White),
+            2 => Some(MasyuClue::Black),
+            _ => None,
+        }
+    }
+}
+
 pub fn solve_masyu(clues: &[Vec<MasyuClue>]) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
     let (h, w) = util::infer_shape(clues);
 
@@ -63,19 +84,8 @@ type Problem = Vec<Vec<MasyuClue>>;
 fn combinator() -> impl Combinator<Vec<Vec<MasyuClue>>> {
     Grid::new(Map::new(
         MultiDigit::new(3, 3),
-        |x: MasyuClue| {
-            Some(match x {
-                MasyuClue::None => 0,
-                MasyuClue::White => 1,
-                MasyuClue::Black => 2,
-            })
-        },
-        |n: i32| match n {
-            0 => Some(MasyuClue::None),
-            1 => Some(MasyuClue::White),
-            2 => Some(MasyuClue::Black),
-            _ => None,
-        },
+        |x: MasyuClue| Some(x.to_digit()),
+        MasyuClue::from_digit,
     ))
 }
 
@@ -141,4 +151,17 @@ mod tests {
         let url = "https://puzz.link/p?masyu/10/10/0600003i06b1300600000a30600i090330";
         util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
     }
+
+    #[test]
+    fn test_masyu_clue_digit() {
+        for clue in [MasyuClue::None, MasyuClue::White, MasyuClue::Black] {
+            assert_eq!(MasyuClue::from_digit(clue.to_digit()), Some(clue));
+        }
+        assert_eq!(MasyuClue::from_digit(3), None);
+        assert_eq!(MasyuClue::from_digit(-1), None);
+
+        let problem = vec![vec![MasyuClue::None, MasyuClue::White, MasyuClue::Black]];
+        let url = "https://puzz.link/p?masyu/3/1/5";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
 }
@@ -99,4 +99,24 @@ pub mod shugaku;
 pub mod kurarin;
 pub mod waterwalk;
 pub mod easyasabc;
-pub mod isowatari;
\ No newline at end of file
+pub mod isowatari;
+pub mod nanro;
+pub mod nanrosignpost;
+pub mod corral;
+pub mod tontti;
+pub mod sukororoom;
+pub mod vertexloop;
+pub mod crossthestreams;
+pub mod regionstar;
+pub mod loopy;
+pub mod everysecondturn;
+pub mod survo;
+pub mod makaro;
+pub mod sashiko;
+pub mod arrowpath;
+pub mod rlits;
+pub mod grandtour;
+pub mod hanare;
+pub mod roma;
+pub mod heyawall;
+pub mod regionomino;
@@ -0,0 +1,158 @@
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    HexInt, Optionalize, Rooms, Size, Spaces, Tuple2,
+};
+use cspuz_rs::solver::{count_true, IntVarArray2D, Solver};
+
+/// Solves a Nanro puzzle.
+///
+/// Each room contains at least one filled cell, and every filled cell holds the number of filled
+/// cells in its room. Filled cells are orthogonally connected, no 2x2 block is entirely filled,
+/// and orthogonally adjacent filled cells in different rooms do not hold the same number.
+/// A given number is a filled cell holding that number. Empty cells are reported as `Some(0)`.
+pub fn solve_nanro(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Vec<Option<i32>>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let num = &add_constraints(&mut solver, borders);
+    solver.add_answer_key_int(num);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(c) = clues[y][x] {
+                solver.add_expr(num.at((y, x)).eq(c));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(num))
+}
+
+/// Adds the Nanro rules on the rooms given by `borders` and returns the number of each cell
+/// (0 for empty cells).
+pub(super) fn add_constraints(
+    solver: &mut Solver,
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+) -> IntVarArray2D {
+    let (h, w) = borders.base_shape();
+
+    let rooms = graph::borders_to_rooms(borders);
+    let mut room_id = vec![vec![0; w]; h];
+    let mut ranges = vec![vec![(0, 0); w]; h];
+    for (i, room) in rooms.iter().enumerate() {
+        for &(y, x) in room {
+            room_id[y][x] = i;
+            ranges[y][x] = (0, room.len() as i32);
+        }
+    }
+
+    let num = solver.int_var_2d_from_ranges((h, w), &ranges);
+
+    let is_filled = &num.ne(0);
+    graph::active_vertices_connected_2d(solver, is_filled);
+    solver.add_expr(!is_filled.conv2d_and((2, 2)));
+
+    for room in &rooms {
+        let n = solver.int_var(1, room.len() as i32);
+        solver.add_expr(count_true(is_filled.select(room)).eq(&n));
+        for &pt in room {
+            solver.add_expr(is_filled.at(pt).imp(num.at(pt).eq(&n)));
+        }
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if y < h - 1 && room_id[y][x] != room_id[y + 1][x] {
+                solver.add_expr(
+                    is_filled
+                        .at((y, x))
+                        .imp(num.at((y, x)).ne(num.at((y + 1, x)))),
+                );
+            }
+            if x < w - 1 && room_id[y][x] != room_id[y][x + 1] {
+                solver.add_expr(
+                    is_filled
+                        .at((y, x))
+                        .imp(num.at((y, x)).ne(num.at((y, x + 1)))),
+                );
+            }
+        }
+    }
+
+    num
+}
+
+pub type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<Option<i32>>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Spaces::new(None, 'g')),
+        ])),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "nanro",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["nanro"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[0, 1, 1, 0], [1, 0, 0, 0], [0, 1, 1, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 1, 0], [1, 0, 1], [1, 0, 1], [0, 0, 1]]),
+        };
+
+        let clues = vec![
+            vec![None, None, Some(2), None],
+            vec![None, None, None, None],
+            vec![None, None, Some(2), None],
+            vec![None, None, None, None],
+        ];
+
+        (borders, clues)
+    }
+
+    #[test]
+    fn test_nanro_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_nanro(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_2d([
+            [3, 3, 2, 2],
+            [3, 0, 0, 0],
+            [1, 2, 2, 0],
+            [0, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_nanro_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?nanro/4/4/aq8d1gh2m2k";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,104 @@
+use super::nanro;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    HexInt, Optionalize, Rooms, Size, Spaces, Tuple2,
+};
+use cspuz_rs::solver::{count_true, Solver};
+
+/// Solves a Nanro Signpost puzzle.
+///
+/// The rules of Nanro (see [`nanro::solve_nanro`]) apply. In addition, a labeled cell (a
+/// "signpost") is filled and its label gives the size of the group of filled cells in its room.
+/// Empty cells are reported as `Some(0)`.
+pub fn solve_nanro_signpost(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Vec<Option<i32>>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let mut solver = Solver::new();
+    let num = &nanro::add_constraints(&mut solver, borders);
+    solver.add_answer_key_int(num);
+
+    let is_filled = &num.ne(0);
+    for room in graph::borders_to_rooms(borders) {
+        for &pt in &room {
+            if let Some(c) = clues[pt.0][pt.1] {
+                solver.add_expr(is_filled.at(pt));
+                solver.add_expr(count_true(is_filled.select(&room)).eq(c));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(num))
+}
+
+pub type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Vec<Option<i32>>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Optionalize::new(HexInt)),
+            Box::new(Spaces::new(None, 'g')),
+        ])),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "nanrosignpost",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["nanrosignpost"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[0, 1, 1, 0], [1, 0, 0, 0], [0, 1, 1, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 1, 0], [1, 0, 1], [1, 0, 1], [0, 0, 1]]),
+        };
+
+        let clues = vec![
+            vec![None, None, Some(2), None],
+            vec![None, None, None, None],
+            vec![None, None, Some(2), None],
+            vec![None, None, None, None],
+        ];
+
+        (borders, clues)
+    }
+
+    #[test]
+    fn test_nanro_signpost_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_nanro_signpost(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_2d([
+            [3, 3, 2, 2],
+            [3, 0, 0, 0],
+            [1, 2, 2, 0],
+            [0, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_nanro_signpost_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?nanrosignpost/4/4/aq8d1gh2m2k";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
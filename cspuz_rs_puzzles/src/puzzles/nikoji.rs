@@ -143,48 +143,6 @@ enum CellState {
     Undecided,
 }
 
-fn flip_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    let mut ymax = 0;
-    for &(y, _) in block.iter() {
-        ymax = ymax.max(y);
-    }
-    let mut ret = block
-        .iter()
-        .map(|&(y, x)| (ymax - y, x))
-        .collect::<Vec<_>>();
-    ret.sort();
-    ret
-}
-
-fn rotate_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
-    let mut ymax = 0;
-    for &(y, _) in block.iter() {
-        ymax = ymax.max(y);
-    }
-
-    let mut ret = block
-        .iter()
-        .map(|&(y, x)| (x, ymax - y))
-        .collect::<Vec<_>>();
-    ret.sort();
-    ret
-}
-
-fn normalize_block(mut block: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
-    block.sort();
-
-    let mut ret = block.clone();
-    for i in 0..4 {
-        ret = ret.min(block.clone());
-        ret = ret.min(flip_block(&block));
-        if i < 3 {
-            block = rotate_block(&block);
-        }
-    }
-
-    ret
-}
-
 struct DifferentShape {
     height: usize,
     width: usize,
@@ -292,7 +250,7 @@ impl SimpleCustomConstraint for DifferentShape {
                 }
             }
 
-            shapes[b] = normalize_block(shape);
+            shapes[b] = util::polyomino::canonical_form(&shape);
         }
 
         if shapes[0] != shapes[1] {
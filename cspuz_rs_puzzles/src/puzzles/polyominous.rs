@@ -1,11 +1,16 @@
 use crate::util;
+use crate::util::polyomino;
 use cspuz_rs::graph;
 use cspuz_rs::serializer::{
-    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
-    Dict, MultiDigit, Optionalize, Rooms, Size, Spaces, Tuple2,
+    choice, problem_to_url_with_context, url_to_problem, ClueOrHole, Combinator, Context,
+    ContextBasedGrid, Dict, MultiDigit, Optionalize, Rooms, Size, Spaces, Tuple2,
 };
-use cspuz_rs::solver::{all, any, Solver};
+use cspuz_rs::solver::{all, any, GraphDivisionMode, IntVarArray2D, Solver};
+use std::collections::HashMap;
 
+use cspuz_core::custom_constraints::SimpleCustomConstraint;
+
+#[derive(Clone, Copy)]
 enum PieceSet {
     Tetromino,
     Pentomino,
@@ -39,10 +44,46 @@ fn tetrominoes() -> Vec<(char, Vec<(usize, usize)>)> {
 }
 
 fn get_pieces(piece_set: PieceSet) -> Vec<(char, Vec<(usize, usize)>)> {
-    match piece_set {
+    let pieces = match piece_set {
         PieceSet::Tetromino => tetrominoes(),
         PieceSet::Pentomino => pentominoes(),
+    };
+    debug_assert_eq!(validate_piece_set(&pieces), Ok(()));
+    pieces
+}
+
+/// Checks that all pieces in `pieces` have the same number of cells and no two of them are the same
+/// up to translation, rotation and reflection.
+fn validate_piece_set(pieces: &[(char, Vec<(usize, usize)>)]) -> Result<(), String> {
+    let mut canonical_forms: Vec<(char, Vec<(usize, usize)>)> = vec![];
+    for (name, piece) in pieces {
+        if piece.len() != pieces[0].1.len() {
+            return Err(format!(
+                "piece {} has {} cells while piece {} has {}",
+                name,
+                piece.len(),
+                pieces[0].0,
+                pieces[0].1.len()
+            ));
+        }
+
+        let min_y = piece.iter().map(|&(y, _)| y).min().unwrap_or(0);
+        let min_x = piece.iter().map(|&(_, x)| x).min().unwrap_or(0);
+        let piece = piece
+            .iter()
+            .map(|&(y, x)| (y - min_y, x - min_x))
+            .collect::<Vec<_>>();
+        let canonical = enumerate_variants(&piece).swap_remove(0);
+
+        if let Some((other, _)) = canonical_forms.iter().find(|(_, c)| *c == canonical) {
+            return Err(format!(
+                "pieces {} and {} are the same up to rotation and reflection",
+                other, name
+            ));
+        }
+        canonical_forms.push((*name, canonical));
     }
+    Ok(())
 }
 
 fn bbox(piece: &[(usize, usize)]) -> (usize, usize) {
@@ -55,29 +96,26 @@ fn bbox(piece: &[(usize, usize)]) -> (usize, usize) {
     (h, w)
 }
 
-fn rotate(piece: &[(usize, usize)]) -> Vec<(usize, usize)> {
-    let (h, _w) = bbox(piece);
-    piece.iter().map(|&(y, x)| (x, h - y - 1)).collect()
-}
-
-fn flip(piece: &[(usize, usize)]) -> Vec<(usize, usize)> {
-    let (h, _w) = bbox(piece);
-    piece.iter().map(|&(y, x)| (h - y - 1, x)).collect()
-}
-
+/// Enumerates all distinct placements of `piece` under rotation and reflection.
+///
+/// This is `polyomino::all_variants` on `usize` coordinates: the cells of each placement are sorted
+/// in lexicographic (y, x) order, so that two placements covering the same cells compare equal, and
+/// the placements themselves are returned in lexicographic order, so the smallest one comes first;
+/// the order of the clauses built from them (and hence the solver behavior) is stable.
 fn enumerate_variants(piece: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
-    let mut cands = vec![];
-    cands.push(piece.to_owned());
-    for i in 0..3 {
-        cands.push(rotate(&cands[i]));
-    }
-    for i in 0..4 {
-        cands.push(flip(&cands[i]));
-    }
-    cands.sort();
-    cands.dedup();
-
-    cands
+    let piece = piece
+        .iter()
+        .map(|&(y, x)| (y as i32, x as i32))
+        .collect::<Vec<_>>();
+    polyomino::all_variants(&piece)
+        .into_iter()
+        .map(|variant| {
+            variant
+                .into_iter()
+                .map(|(y, x)| (y as usize, x as usize))
+                .collect()
+        })
+        .collect()
 }
 
 fn adjacent_edges(piece: &[(usize, usize)]) -> (Vec<(usize, usize)>, Vec<(usize, usize)>) {
@@ -96,18 +134,204 @@ fn adjacent_edges(piece: &[(usize, usize)]) -> (Vec<(usize, usize)>, Vec<(usize,
     (horizontal, vertical)
 }
 
+/// Custom constraint forbidding a piece to be used more than once in the whole board.
+///
+/// Input `i * num_cells + c` is true iff cell `c` is covered by piece `i`. As every piece covers
+/// exactly `size_of_piece` cells, a piece is used more than once iff more than `size_of_piece` cells
+/// are covered by it.
+struct UniquePieceConstraint {
+    num_cells: usize,
+    size_of_piece: usize,
+    /// Inputs decided to be true, grouped by the piece.
+    covered_cells: Vec<Vec<usize>>,
+    decision_stack: Vec<(usize, bool)>,
+}
+
+impl UniquePieceConstraint {
+    fn new(num_pieces: usize, num_cells: usize, size_of_piece: usize) -> UniquePieceConstraint {
+        UniquePieceConstraint {
+            num_cells,
+            size_of_piece,
+            covered_cells: vec![vec![]; num_pieces],
+            decision_stack: vec![],
+        }
+    }
+}
+
+impl SimpleCustomConstraint for UniquePieceConstraint {
+    fn initialize_sat(&mut self, num_inputs: usize) {
+        assert_eq!(num_inputs, self.covered_cells.len() * self.num_cells);
+    }
+
+    fn notify(&mut self, index: usize, value: bool) {
+        if value {
+            self.covered_cells[index / self.num_cells].push(index);
+        }
+        self.decision_stack.push((index, value));
+    }
+
+    fn find_inconsistency(&mut self) -> Option<Vec<(usize, bool)>> {
+        for cells in &self.covered_cells {
+            if cells.len() > self.size_of_piece {
+                return Some(
+                    cells[..=self.size_of_piece]
+                        .iter()
+                        .map(|&i| (i, true))
+                        .collect(),
+                );
+            }
+        }
+        None
+    }
+
+    fn undo(&mut self) {
+        let (index, value) = self.decision_stack.pop().unwrap();
+        if value {
+            self.covered_cells[index / self.num_cells].pop();
+        }
+    }
+}
+
+/// Options of `solve_pentominous_with_options` and `solve_tetrominous_with_options`.
+#[derive(Clone, Copy, Default)]
+pub struct PolyominousOptions {
+    /// If `true`, each piece may be used at most once in the whole board.
+    /// Otherwise, only adjacent pieces must differ (the standard rule).
+    pub unique_pieces: bool,
+    /// Encoding of the division into pieces. If `None`, the mode of the solver is used.
+    pub graph_division_mode: Option<GraphDivisionMode>,
+}
+
 fn solve_polyominous(
     clues: &[Vec<Option<i32>>],
     default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
     piece_set: PieceSet,
+    opts: PolyominousOptions,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+    let mut solver = Solver::new();
+    let (is_border, _) = add_constraints(&mut solver, clues, default_borders, piece_set, opts)?;
+    solver.irrefutable_facts().map(|f| f.get(&is_border))
+}
+
+/// Same as `solve_polyominous`, but also returns the letter of the piece covering each cell.
+/// Cells not covered by any piece and undetermined cells are `None`.
+fn solve_polyominous_with_kind(
+    clues: &[Vec<Option<i32>>],
+    default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
+    piece_set: PieceSet,
+) -> Option<(
+    graph::BoolInnerGridEdgesIrrefutableFacts,
+    Vec<Vec<Option<char>>>,
+)> {
+    let names = get_pieces(piece_set)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    let mut solver = Solver::new();
+    let (is_border, kind) = add_constraints(
+        &mut solver,
+        clues,
+        default_borders,
+        piece_set,
+        PolyominousOptions::default(),
+    )?;
+    solver.add_answer_key_int(&kind);
+    solver.irrefutable_facts().map(|f| {
+        let kind = f
+            .get(&kind)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|k| match k {
+                        Some(k) if k >= 0 => Some(names[k as usize]),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        (f.get(&is_border), kind)
+    })
+}
+
+/// Counts the pieces in a polyominous answer for each piece letter.
+///
+/// A piece is a maximal group of cells with the same letter connected through edges which are known
+/// not to be borders. This can be used to check whether a piece is used more than once.
+pub fn piece_usage(
+    kind: &[Vec<Option<char>>],
+    borders: &graph::BoolInnerGridEdgesIrrefutableFacts,
+) -> HashMap<char, usize> {
+    let (h, w) = util::infer_shape(kind);
+    let mut visited = vec![vec![false; w]; h];
+    let mut ret = HashMap::new();
+
+    for y in 0..h {
+        for x in 0..w {
+            let name = match kind[y][x] {
+                Some(name) if !visited[y][x] => name,
+                _ => continue,
+            };
+            *ret.entry(name).or_insert(0) += 1;
+
+            visited[y][x] = true;
+            let mut stack = vec![(y, x)];
+            while let Some((y, x)) = stack.pop() {
+                let mut neighbors = vec![];
+                if y > 0 && borders.horizontal[y - 1][x] == Some(false) {
+                    neighbors.push((y - 1, x));
+                }
+                if y < h - 1 && borders.horizontal[y][x] == Some(false) {
+                    neighbors.push((y + 1, x));
+                }
+                if x > 0 && borders.vertical[y][x - 1] == Some(false) {
+                    neighbors.push((y, x - 1));
+                }
+                if x < w - 1 && borders.vertical[y][x] == Some(false) {
+                    neighbors.push((y, x + 1));
+                }
+                for (ny, nx) in neighbors {
+                    if !visited[ny][nx] && kind[ny][nx] == Some(name) {
+                        visited[ny][nx] = true;
+                        stack.push((ny, nx));
+                    }
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+/// Adds the constraints of a polyominous problem to `solver` and returns the border variables and
+/// the piece id of each cell (-1 for a cell not covered by any piece), or `None` if the clues are invalid.
+///
+/// Orthogonally connected cells not covered by any piece form a single region without borders inside.
+/// Adjacent pieces are always of different kinds, since two adjacent cells of the same kind are never
+/// separated by a border. If `opts.unique_pieces` is `true`, additionally no piece may be used more than
+/// once in the whole board, even by pieces far apart from each other.
+fn add_constraints(
+    solver: &mut Solver,
+    clues: &[Vec<Option<i32>>],
+    default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
+    piece_set: PieceSet,
+    opts: PolyominousOptions,
+) -> Option<(graph::BoolInnerGridEdges, IntVarArray2D)> {
     let (h, w) = util::infer_shape(clues);
 
     let polyset = get_pieces(piece_set);
     let size_of_set = polyset.len();
     let size_of_piece = polyset[0].1.len();
 
-    let mut solver = Solver::new();
+    // Clues are piece ids, or -1 for a cell not covered by any piece.
+    if clues
+        .iter()
+        .flatten()
+        .any(|&c| matches!(c, Some(n) if !(-1..size_of_set as i32).contains(&n)))
+    {
+        return None;
+    }
+
     let kind_ranges = clues
         .iter()
         .map(|row| {
@@ -124,54 +348,69 @@ fn solve_polyominous(
         .collect::<Vec<_>>();
     let kind = &solver.int_var_2d_from_ranges((h, w), &kind_ranges);
 
-    let is_border = graph::BoolInnerGridEdges::new(&mut solver, (h, w));
+    // Orthogonally connected hole cells form a single inert region.
+    let is_hole = |y: usize, x: usize| clues[y][x] == Some(-1);
+    let (holes, hole_id) = graph::borders_to_rooms_with_ids(&graph::InnerGridEdges {
+        horizontal: (0..(h - 1))
+            .map(|y| {
+                (0..w)
+                    .map(|x| !(is_hole(y, x) && is_hole(y + 1, x)))
+                    .collect()
+            })
+            .collect(),
+        vertical: (0..h)
+            .map(|y| {
+                (0..(w - 1))
+                    .map(|x| !(is_hole(y, x) && is_hole(y, x + 1)))
+                    .collect()
+            })
+            .collect(),
+    });
+
+    let is_border = graph::BoolInnerGridEdges::new(solver, (h, w));
     solver.add_answer_key_bool(&is_border.horizontal);
     solver.add_answer_key_bool(&is_border.vertical);
 
     if let Some(default_borders) = default_borders {
         for y in 0..h {
             for x in 0..(w - 1) {
-                if default_borders.vertical[y][x] {
+                if default_borders.vertical[y][x] && !(is_hole(y, x) && is_hole(y, x + 1)) {
                     solver.add_expr(is_border.vertical.at((y, x)));
                 }
             }
         }
         for y in 0..(h - 1) {
             for x in 0..w {
-                if default_borders.horizontal[y][x] {
+                if default_borders.horizontal[y][x] && !(is_hole(y, x) && is_hole(y + 1, x)) {
                     solver.add_expr(is_border.horizontal.at((y, x)));
                 }
             }
         }
     }
 
-    solver.add_expr(
-        &is_border.horizontal
-            ^ (kind.slice((..(h - 1), ..)).ge(0)
-                & (kind.slice((..(h - 1), ..)).eq(kind.slice((1.., ..))))),
-    );
-    solver.add_expr(
-        &is_border.vertical
-            ^ (kind.slice((.., ..(w - 1))).ge(0)
-                & (kind.slice((.., ..(w - 1))).eq(kind.slice((.., 1..))))),
-    );
+    // Hole cells all have kind -1, so there is no border between adjacent hole cells.
+    solver.add_expr(&is_border.horizontal ^ kind.slice((..(h - 1), ..)).eq(kind.slice((1.., ..))));
+    solver.add_expr(&is_border.vertical ^ kind.slice((.., ..(w - 1))).eq(kind.slice((.., 1..))));
 
-    let sizes = clues
-        .iter()
-        .map(|row| {
-            row.iter()
-                .map(|&x| {
-                    if x == Some(-1) {
-                        (1, 1)
+    let sizes = (0..h)
+        .map(|y| {
+            (0..w)
+                .map(|x| {
+                    let size = if is_hole(y, x) {
+                        holes[hole_id[y][x]].len()
                     } else {
-                        (size_of_piece as i32, size_of_piece as i32)
-                    }
+                        size_of_piece
+                    };
+                    (size as i32, size as i32)
                 })
                 .collect::<Vec<_>>()
         })
         .collect::<Vec<_>>();
     let sizes = &solver.int_var_2d_from_ranges((h, w), &sizes);
-    graph::graph_division_2d(&mut solver, sizes, &is_border);
+    match opts.graph_division_mode {
+        Some(mode) => graph::graph_division_2d_with_mode(solver, sizes, &is_border, mode),
+        None => graph::graph_division_2d(solver, sizes, &is_border),
+    }
 
     for y in 0..h {
         for x in 0..w {
@@ -211,6 +450,14 @@ fn solve_polyominous(
                         if ty + ph > h || tx + pw > w {
                             continue;
                         }
+                        // Every cell of the placement would be of kind `i`, so placements covering a cell
+                        // clued with another kind can never hold. Skipping them keeps the encoding small
+                        // when many cells are clued.
+                        if poly_variants[i][j].iter().any(
+                            |&(py, px)| matches!(clues[ty + py][tx + px], Some(c) if c != i as i32),
+                        ) {
+                            continue;
+                        }
 
                         let mut c = vec![kind.at((y, x)).eq(i as i32)];
                         for &(dy, dx) in &poly_adjacent_edges[i][j].0 {
@@ -228,21 +475,94 @@ fn solve_polyominous(
         }
     }
 
-    solver.irrefutable_facts().map(|f| f.get(&is_border))
+    if opts.unique_pieces {
+        let inputs = (0..size_of_set)
+            .flat_map(|i| kind.eq(i as i32).flatten())
+            .collect::<Vec<_>>();
+
+        #[cfg(not(test))]
+        {
+            let constraint = UniquePieceConstraint::new(size_of_set, h * w, size_of_piece);
+            solver.add_custom_constraint(Box::new(constraint), inputs);
+        }
+
+        #[cfg(test)]
+        {
+            solver.add_custom_constraint(
+                Box::new(util::tests::ReasonVerifier::new(
+                    UniquePieceConstraint::new(size_of_set, h * w, size_of_piece),
+                    UniquePieceConstraint::new(size_of_set, h * w, size_of_piece),
+                )),
+                inputs,
+            );
+        }
+    }
+
+    Some((is_border, kind.clone()))
 }
 
 pub fn solve_pentominous(
     clues: &[Vec<Option<i32>>],
     default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
-    solve_polyominous(clues, default_borders, PieceSet::Pentomino)
+    solve_polyominous(
+        clues,
+        default_borders,
+        PieceSet::Pentomino,
+        PolyominousOptions::default(),
+    )
+}
+
+/// `solve_pentominous` with an extra rule switch and a choice of the graph-division encoding.
+/// See `PolyominousOptions` for details.
+pub fn solve_pentominous_with_options(
+    clues: &[Vec<Option<i32>>],
+    default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
+    opts: PolyominousOptions,
+) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+    solve_polyominous(clues, default_borders, PieceSet::Pentomino, opts)
+}
+
+pub fn solve_pentominous_with_kind(
+    clues: &[Vec<Option<i32>>],
+    default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
+) -> Option<(
+    graph::BoolInnerGridEdgesIrrefutableFacts,
+    Vec<Vec<Option<char>>>,
+)> {
+    solve_polyominous_with_kind(clues, default_borders, PieceSet::Pentomino)
 }
 
 pub fn solve_tetrominous(
     clues: &[Vec<Option<i32>>],
     default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
 ) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
-    solve_polyominous(clues, default_borders, PieceSet::Tetromino)
+    solve_polyominous(
+        clues,
+        default_borders,
+        PieceSet::Tetromino,
+        PolyominousOptions::default(),
+    )
+}
+
+/// `solve_tetrominous` with an extra rule switch and a choice of the graph-division encoding.
+/// See `PolyominousOptions` for details.
+pub fn solve_tetrominous_with_options(
+    clues: &[Vec<Option<i32>>],
+    default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
+    opts: PolyominousOptions,
+) -> Option<graph::BoolInnerGridEdgesIrrefutableFacts> {
+    solve_polyominous(clues, default_borders, PieceSet::Tetromino, opts)
+}
+
+pub fn solve_tetrominous_with_kind(
+    clues: &[Vec<Option<i32>>],
+    default_borders: &Option<graph::InnerGridEdges<Vec<Vec<bool>>>>,
+) -> Option<(
+    graph::BoolInnerGridEdgesIrrefutableFacts,
+    Vec<Vec<Option<char>>>,
+)> {
+    solve_polyominous_with_kind(clues, default_borders, PieceSet::Tetromino)
 }
 
 type Problem = (
@@ -252,15 +572,11 @@ type Problem = (
 
 fn combinator() -> impl Combinator<Problem> {
     Size::new(Tuple2::new(
-        ContextBasedGrid::new(Choice::new(vec![
-            Box::new(Spaces::new(None, 'g')),
-            Box::new(Dict::new(Some(-1), "c")),
-            Box::new(Optionalize::new(MultiDigit::new(12, 1))),
-        ])),
-        Choice::new(vec![
-            Box::new(Optionalize::new(Rooms)),
-            Box::new(Dict::new(None, "")),
+        ContextBasedGrid::new(choice![
+            Spaces::new(None, 'g'),
+            ClueOrHole::new(MultiDigit::new(12, 1), "c"),
         ]),
+        choice![Optionalize::new(Rooms), Dict::new(None, "")],
     ))
 }
 
@@ -323,6 +639,60 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_enumerate_variants_z_pentomino() {
+        let z = pentominoes()
+            .into_iter()
+            .find(|(c, _)| *c == 'Z')
+            .unwrap()
+            .1;
+        assert_eq!(
+            enumerate_variants(&z),
+            vec![
+                vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 2)],
+                vec![(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)],
+                vec![(0, 1), (0, 2), (1, 1), (2, 0), (2, 1)],
+                vec![(0, 2), (1, 0), (1, 1), (1, 2), (2, 0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enumerate_variants_symmetric_pieces() {
+        let pieces = pentominoes();
+        let variants_of = |name: char| {
+            let piece = &pieces.iter().find(|(c, _)| *c == name).unwrap().1;
+            enumerate_variants(piece).len()
+        };
+        assert_eq!(variants_of('X'), 1);
+        assert_eq!(variants_of('I'), 2);
+        assert_eq!(variants_of('T'), 4);
+        assert_eq!(variants_of('F'), 8);
+    }
+
+    #[test]
+    fn test_validate_piece_set() {
+        assert_eq!(validate_piece_set(&tetrominoes()), Ok(()));
+        assert_eq!(validate_piece_set(&pentominoes()), Ok(()));
+
+        // J is a reflection of L (placed at a different offset).
+        let mut pieces = tetrominoes();
+        pieces.push(('J', vec![(1, 1), (2, 1), (3, 1), (1, 0)]));
+        assert_eq!(
+            validate_piece_set(&pieces),
+            Err(String::from(
+                "pieces L and J are the same up to rotation and reflection"
+            ))
+        );
+
+        let mut pieces = tetrominoes();
+        pieces.push(('X', vec![(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)]));
+        assert_eq!(
+            validate_piece_set(&pieces),
+            Err(String::from("piece X has 5 cells while piece I has 4"))
+        );
+    }
+
     #[test]
     fn test_pentominous_problem() {
         let (clues, borders) = problem_for_tests_pentominous();
@@ -359,6 +729,218 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pentominous_serializer_hole() {
+        let mut clues = vec![vec![None; 5]; 5];
+        clues[0][0] = Some(-1);
+        clues[2][3] = Some(7);
+        let url = "https://puzz.link/p?pentominous/5/5/cr7q";
+        util::tests::serializer_test(
+            (clues, None),
+            url,
+            serialize_pentominous_problem,
+            deserialize_pentominous_problem,
+        );
+    }
+
+    #[test]
+    fn test_pentominous_graph_division_mode() {
+        let (clues, borders) = problem_for_tests_pentominous();
+        let expected = solve_pentominous(&clues, &borders);
+        assert!(expected.is_some());
+
+        let mut stats = vec![];
+        for mode in [GraphDivisionMode::Cpp, GraphDivisionMode::Rust] {
+            let opts = PolyominousOptions {
+                graph_division_mode: Some(mode),
+                ..PolyominousOptions::default()
+            };
+            assert_eq!(
+                solve_pentominous_with_options(&clues, &borders, opts),
+                expected
+            );
+
+            let mut solver = Solver::new();
+            add_constraints(&mut solver, &clues, &borders, PieceSet::Pentomino, opts).unwrap();
+            stats.push(solver.instance_stats());
+        }
+
+        // Both modes produce the same clauses, but only the Rust mode encodes the division
+        // as a custom propagator.
+        assert_eq!(stats[0].num_clauses, stats[1].num_clauses);
+        assert_eq!(stats[0].num_custom_constraints, 0);
+        assert_eq!(stats[1].num_custom_constraints, 1);
+    }
+
+    #[test]
+    fn test_pentominous_heavily_clued() {
+        // Every cell is clued with the kind of the piece covering it in the answer of `problem_for_tests_pentominous`,
+        // so most placements are pruned before encoding. The answer must not change.
+        let (clues, borders) = problem_for_tests_pentominous();
+        let expected = solve_pentominous(&clues, &borders);
+        assert!(expected.is_some());
+
+        let clues = vec![
+            vec![Some(7), Some(2), Some(2), Some(2), Some(2)],
+            vec![Some(7), Some(2), Some(5), Some(5), Some(5)],
+            vec![Some(7), Some(7), Some(7), Some(5), Some(7)],
+            vec![Some(4), Some(4), Some(4), Some(5), Some(7)],
+            vec![Some(4), Some(4), Some(7), Some(7), Some(7)],
+        ];
+        assert_eq!(solve_pentominous(&clues, &borders), expected);
+    }
+
+    #[test]
+    fn test_pentominous_piece_usage() {
+        let (clues, borders) = problem_for_tests_pentominous();
+        let ans = solve_pentominous_with_kind(&clues, &borders);
+        assert!(ans.is_some());
+        let (is_border, kind) = ans.unwrap();
+
+        let v = Some('V');
+        let l = Some('L');
+        let t = Some('T');
+        let p = Some('P');
+        assert_eq!(
+            kind,
+            vec![
+                vec![v, l, l, l, l],
+                vec![v, l, t, t, t],
+                vec![v, v, v, t, v],
+                vec![p, p, p, t, v],
+                vec![p, p, v, v, v],
+            ]
+        );
+
+        // The two V pentominoes are counted separately.
+        let usage = piece_usage(&kind, &is_border);
+        assert_eq!(
+            usage,
+            HashMap::from([('V', 2), ('L', 1), ('T', 1), ('P', 1)])
+        );
+    }
+
+    #[test]
+    fn test_pentominous_unique_pieces() {
+        // The answer of `problem_for_tests_pentominous` uses V twice.
+        let (clues, borders) = problem_for_tests_pentominous();
+        let standard = PolyominousOptions::default();
+        let unique = PolyominousOptions {
+            unique_pieces: true,
+            ..PolyominousOptions::default()
+        };
+        assert!(solve_pentominous_with_options(&clues, &borders, standard).is_some());
+        assert_eq!(
+            solve_pentominous_with_options(&clues, &borders, unique),
+            None
+        );
+
+        // V P P U U
+        // V P P P U
+        // V V V U U
+        let mut clues = vec![vec![None; 5]; 3];
+        clues[0][1] = Some(4);
+        clues[1][3] = Some(4);
+
+        // P and T / P and Z are also possible if P can be used twice.
+        let ans = solve_pentominous_with_options(&clues, &None, standard);
+        assert!(ans.is_some());
+        assert!(ans.unwrap().vertical[0][0].is_none());
+
+        let ans = solve_pentominous_with_options(&clues, &None, unique);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = graph::BoolInnerGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([[0, 0, 0, 1, 0], [0, 1, 1, 1, 0]]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [1, 0, 1, 0],
+                [1, 0, 0, 1],
+                [0, 0, 1, 0],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_tetrominous_unique_pieces() {
+        // The answer of `problem_for_tests_tetrominous` uses L twice.
+        let (clues, borders) = problem_for_tests_tetrominous();
+        let standard = PolyominousOptions::default();
+        let unique = PolyominousOptions {
+            unique_pieces: true,
+            ..PolyominousOptions::default()
+        };
+        assert!(solve_tetrominous_with_options(&clues, &borders, standard).is_some());
+        assert_eq!(
+            solve_tetrominous_with_options(&clues, &borders, unique),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unique_piece_constraint() {
+        // 2 pieces of size 2 on a board with 3 cells
+        let mut constraint = UniquePieceConstraint::new(2, 3, 2);
+        constraint.initialize_sat(6);
+
+        constraint.notify(0, true);
+        constraint.notify(4, true);
+        constraint.notify(1, true);
+        assert!(constraint.find_inconsistency().is_none());
+
+        constraint.notify(2, true);
+        assert_eq!(
+            constraint.find_inconsistency(),
+            Some(vec![(0, true), (1, true), (2, true)])
+        );
+
+        constraint.undo();
+        constraint.notify(2, false);
+        assert!(constraint.find_inconsistency().is_none());
+    }
+
+    #[test]
+    fn test_pentominous_multi_cell_hole() {
+        // # L L L L V
+        // # # N N L V
+        // N N N V V V
+        let mut clues = vec![vec![None; 6]; 3];
+        clues[0][0] = Some(-1);
+        clues[1][0] = Some(-1);
+        clues[1][1] = Some(-1);
+        clues[0][1] = Some(2);
+
+        let ans = solve_pentominous_with_kind(&clues, &None);
+        assert!(ans.is_some());
+        let (is_border, kind) = ans.unwrap();
+        let expected = graph::BoolInnerGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [0, 1, 1, 1, 0, 0],
+                [1, 1, 0, 1, 1, 0],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [1, 0, 0, 0, 1],
+                [0, 1, 0, 1, 1],
+                [0, 0, 1, 0, 0],
+            ]),
+        };
+        assert_eq!(is_border, expected);
+
+        let usage = piece_usage(&kind, &is_border);
+        assert_eq!(usage, HashMap::from([('L', 1), ('N', 1), ('V', 1)]));
+    }
+
+    #[test]
+    fn test_polyominous_out_of_range_clue() {
+        let (mut clues, borders) = problem_for_tests_tetrominous();
+        clues[2][0] = Some(5);
+        assert_eq!(solve_tetrominous(&clues, &borders), None);
+
+        let (mut clues, borders) = problem_for_tests_pentominous();
+        clues[0][0] = Some(-2);
+        assert_eq!(solve_pentominous(&clues, &borders), None);
+    }
+
     #[test]
     fn test_tetrominous_problem() {
         let (clues, borders) = problem_for_tests_tetrominous();
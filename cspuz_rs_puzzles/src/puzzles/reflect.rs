@@ -39,6 +39,11 @@ impl ReflectLinkClue {
     }
 }
 
+/// Reflect Link (puzz.link `reflect`): draw a loop through the cell centers which passes every triangle cell,
+/// bouncing off its hypotenuse. A number in a triangle is the total number of cells of the two straight segments
+/// through the cell (including the cell itself). The loop crosses itself exactly at the cross cells.
+///
+/// The mirrors (triangles) are all given in the problem, so only the loop is returned.
 pub fn solve_reflect_link(
     clues: &[Vec<ReflectLinkClue>],
 ) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
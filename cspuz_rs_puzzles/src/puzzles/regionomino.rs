@@ -0,0 +1,237 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::solver::Solver;
+
+use super::anymino::AnyminoConstraint;
+
+/// The number of shaded cells in each room.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RegionominoSize {
+    /// Every room has at least the given number of shaded cells.
+    AtLeast(i32),
+    /// Every room has exactly the given number of shaded cells.
+    Exactly(i32),
+    /// The `i`-th room (in the order of `graph::borders_to_rooms`) has exactly `sizes[i]` shaded cells.
+    PerRoom(Vec<i32>),
+}
+
+/// Restriction on polyominoes in different rooms touching each other.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RegionominoAdjacency {
+    /// No restriction.
+    Free,
+    /// No two polyominoes in different rooms share an edge.
+    NoTouch,
+    /// No two polyominoes in different rooms sharing an edge are congruent (rotations and reflections
+    /// are considered congruent). If `anywhere` is `true`, no two polyominoes are congruent even when
+    /// they do not touch each other.
+    CongruenceBan { anywhere: bool },
+}
+
+/// Rules of a puzzle in which exactly one connected polyomino is shaded in each room.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RegionominoSpec {
+    pub size: RegionominoSize,
+    pub adjacency: RegionominoAdjacency,
+    /// If `true`, all shaded cells are connected.
+    pub connected: bool,
+    /// If `true`, no 2x2 area is fully shaded.
+    pub no_2x2: bool,
+    /// If `true`, each room adjacent to some other room has its polyomino touching a polyomino of
+    /// the same size in another room (as in Anymino). Note that this requires equal sizes rather than
+    /// forbidding them: touching polyominoes of different sizes are allowed as long as each of them
+    /// also touches one of its own size.
+    pub touch_same_size: bool,
+}
+
+/// Shades exactly one connected polyomino in each room of `borders` according to `spec`.
+pub fn solve_regionomino(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    spec: &RegionominoSpec,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    if spec.connected {
+        graph::active_vertices_connected_2d(&mut solver, is_black);
+    }
+    if spec.no_2x2 {
+        graph::no_2x2_block(&mut solver, is_black);
+    }
+
+    let (rooms, room_id) = graph::borders_to_rooms_with_ids(borders);
+    if let RegionominoSize::PerRoom(sizes) = &spec.size {
+        if sizes.len() != rooms.len() {
+            return None;
+        }
+    }
+
+    let room_sizes = &solver.int_var_1d(rooms.len(), 1, (h * w) as i32);
+    for (i, room) in rooms.iter().enumerate() {
+        graph::active_vertices_connected_2d_region(&mut solver, is_black, room);
+        solver.add_expr(is_black.select(room).count_true().eq(room_sizes.at(i)));
+
+        match &spec.size {
+            RegionominoSize::AtLeast(n) => solver.add_expr(room_sizes.at(i).ge(*n)),
+            RegionominoSize::Exactly(n) => solver.add_expr(room_sizes.at(i).eq(*n)),
+            RegionominoSize::PerRoom(sizes) => solver.add_expr(room_sizes.at(i).eq(sizes[i])),
+        }
+    }
+
+    if spec.touch_same_size {
+        util::add_adjacent_rooms_value_constraints(
+            &mut solver,
+            &rooms,
+            &room_id,
+            is_black,
+            room_sizes,
+            util::AdjacentRoomsRule::Share,
+        );
+    }
+
+    match spec.adjacency {
+        RegionominoAdjacency::Free => (),
+        RegionominoAdjacency::NoTouch => {
+            for y in 0..h {
+                for x in 0..w {
+                    if y < h - 1 && room_id[y][x] != room_id[y + 1][x] {
+                        solver.add_expr(!(is_black.at((y, x)) & is_black.at((y + 1, x))));
+                    }
+                    if x < w - 1 && room_id[y][x] != room_id[y][x + 1] {
+                        solver.add_expr(!(is_black.at((y, x)) & is_black.at((y, x + 1))));
+                    }
+                }
+            }
+        }
+        RegionominoAdjacency::CongruenceBan { anywhere } => {
+            let room_adjacency = graph::room_adjacency(borders);
+            let constraint = AnyminoConstraint::new(h, w, rooms, room_id, room_adjacency, anywhere);
+            solver.add_custom_constraint(Box::new(constraint), is_black);
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_black))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::tests::{to_bool_2d, to_option_bool_2d};
+
+    fn base_spec(size: RegionominoSize, adjacency: RegionominoAdjacency) -> RegionominoSpec {
+        RegionominoSpec {
+            size,
+            adjacency,
+            connected: false,
+            no_2x2: false,
+            touch_same_size: false,
+        }
+    }
+
+    #[test]
+    fn test_regionomino_no_touch() {
+        // 0 1 1 1
+        // 0 1 2 2
+        // 1 1 2 2
+        let borders = graph::InnerGridEdges {
+            horizontal: to_bool_2d([[0, 0, 1, 1], [1, 0, 0, 0]]),
+            vertical: to_bool_2d([[1, 0, 0], [1, 1, 0], [0, 1, 0]]),
+        };
+
+        let ans = solve_regionomino(
+            &borders,
+            &base_spec(RegionominoSize::Exactly(2), RegionominoAdjacency::NoTouch),
+        );
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = to_option_bool_2d([[1, 0, 1, 1], [1, 0, 0, 0], [0, 0, 1, 1]]);
+        assert_eq!(ans, expected);
+
+        let ans = solve_regionomino(
+            &borders,
+            &base_spec(RegionominoSize::Exactly(2), RegionominoAdjacency::Free),
+        );
+        assert!(ans.is_some());
+        assert_ne!(ans.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_regionomino_congruence_ban() {
+        // 0 0 1 1
+        // 0 0 0 1
+        // 2 2 3 3
+        // 2 3 3 3
+        let borders = graph::InnerGridEdges {
+            horizontal: to_bool_2d([[0, 0, 1, 0], [1, 1, 1, 1], [0, 1, 0, 0]]),
+            vertical: to_bool_2d([[0, 1, 0], [0, 0, 1], [0, 1, 0], [1, 0, 0]]),
+        };
+        let mut spec = base_spec(
+            RegionominoSize::Exactly(3),
+            RegionominoAdjacency::CongruenceBan { anywhere: false },
+        );
+        spec.connected = true;
+
+        let ans = solve_regionomino(&borders, &spec);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = to_option_bool_2d([[0, 0, 1, 1], [1, 1, 1, 1], [1, 1, 0, 0], [1, 1, 1, 1]]);
+        assert_eq!(ans, expected);
+
+        // there are only two trominoes, so four pairwise non-congruent ones cannot exist
+        spec.adjacency = RegionominoAdjacency::CongruenceBan { anywhere: true };
+        assert_eq!(solve_regionomino(&borders, &spec), None);
+    }
+
+    #[test]
+    fn test_regionomino_touch_same_size() {
+        // 0 0 1 1
+        let borders = graph::InnerGridEdges {
+            horizontal: vec![],
+            vertical: to_bool_2d([[0, 1, 0]]),
+        };
+        let mut spec = base_spec(
+            RegionominoSize::PerRoom(vec![2, 2]),
+            RegionominoAdjacency::Free,
+        );
+        spec.touch_same_size = true;
+
+        // The polyominoes must touch one of the same size, not one of a different size.
+        let ans = solve_regionomino(&borders, &spec);
+        assert_eq!(ans, Some(to_option_bool_2d([[1, 1, 1, 1]])));
+
+        spec.size = RegionominoSize::PerRoom(vec![2, 1]);
+        assert_eq!(solve_regionomino(&borders, &spec), None);
+
+        spec.touch_same_size = false;
+        assert!(solve_regionomino(&borders, &spec).is_some());
+    }
+
+    #[test]
+    fn test_regionomino_per_room_size() {
+        // 0 0 1 1
+        // 0 2 2 1
+        // 0 3 2 3
+        // 0 3 3 3
+        let borders = graph::InnerGridEdges {
+            horizontal: to_bool_2d([[0, 1, 1, 0], [0, 1, 0, 1], [0, 0, 1, 0]]),
+            vertical: to_bool_2d([[0, 1, 0], [1, 0, 1], [1, 1, 1], [1, 0, 0]]),
+        };
+        let mut spec = base_spec(
+            RegionominoSize::PerRoom(vec![3, 1, 1, 3]),
+            RegionominoAdjacency::Free,
+        );
+        spec.connected = true;
+
+        let ans = solve_regionomino(&borders, &spec);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = to_option_bool_2d([[1, 1, 1, 0], [1, 1, 0, 0], [0, 1, 0, 0], [0, 1, 1, 0]]);
+        assert_eq!(ans, expected);
+
+        spec.size = RegionominoSize::PerRoom(vec![3, 1, 1]);
+        assert_eq!(solve_regionomino(&borders, &spec), None);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::puzzles::star_battle;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{problem_to_url_with_context, url_to_problem, Context};
+use cspuz_rs::solver::Solver;
+use std::collections::BTreeMap;
+
+pub fn solve_regionstar(
+    star_amount: i32,
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let height = borders.vertical.len();
+
+    if height != borders.vertical[0].len() + 1 {
+        return None;
+    }
+
+    let mut solver = Solver::new();
+
+    let has_star = &solver.bool_var_2d((height, height));
+    solver.add_answer_key_bool(has_star);
+
+    star_battle::add_constraints(&mut solver, has_star, star_amount, borders);
+
+    // No two stars in the same room may lie on a common diagonal.
+    let rooms = graph::borders_to_rooms(borders);
+    for room in &rooms {
+        let mut diagonals = BTreeMap::<(bool, i32), Vec<(usize, usize)>>::new();
+        for &(y, x) in room {
+            diagonals
+                .entry((false, y as i32 - x as i32))
+                .or_default()
+                .push((y, x));
+            diagonals
+                .entry((true, (y + x) as i32))
+                .or_default()
+                .push((y, x));
+        }
+        for cells in diagonals.values() {
+            if cells.len() >= 2 {
+                solver.add_expr(has_star.select(cells).count_true().le(1));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(has_star))
+}
+
+type Problem = star_battle::Problem;
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url_with_context(
+        star_battle::combinator(),
+        "regionstar",
+        problem.clone(),
+        &Context::sized(problem.1.vertical.len(), problem.1.vertical[0].len() + 1),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(star_battle::combinator(), &["regionstar"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+
+    fn problem_for_tests() -> Problem {
+        let star_amount = 2;
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([
+                [0, 0, 0, 1, 1, 0, 0, 0, 1],
+                [0, 0, 1, 0, 0, 1, 1, 1, 0],
+                [1, 1, 1, 1, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 1, 1, 0, 1, 1],
+                [0, 1, 1, 1, 1, 0, 1, 0, 0],
+                [0, 0, 0, 0, 1, 0, 0, 1, 1],
+                [1, 0, 1, 0, 0, 0, 1, 0, 0],
+                [0, 0, 1, 1, 0, 1, 0, 0, 0],
+            ]),
+            vertical: crate::util::tests::to_bool_2d([
+                [0, 0, 0, 0, 1, 0, 0, 0],
+                [0, 0, 1, 0, 1, 0, 0, 1],
+                [0, 1, 0, 0, 1, 0, 0, 0],
+                [0, 0, 0, 1, 1, 0, 0, 0],
+                [0, 0, 0, 0, 0, 1, 1, 0],
+                [1, 0, 1, 0, 1, 1, 0, 0],
+                [1, 0, 1, 1, 0, 1, 1, 0],
+                [0, 1, 0, 1, 0, 1, 0, 0],
+                [0, 0, 0, 1, 1, 0, 0, 0],
+            ]),
+        };
+        (star_amount, borders)
+    }
+
+    #[test]
+    fn test_regionstar_problem() {
+        let (star_amount, borders) = problem_for_tests();
+        let ans = solve_regionstar(star_amount, &borders);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_bool_2d([
+            [0, 0, 0, 1, 0, 0, 0, 1, 0],
+            [1, 0, 0, 0, 0, 1, 0, 0, 0],
+            [0, 0, 1, 0, 0, 0, 0, 1, 0],
+            [1, 0, 0, 0, 1, 0, 0, 0, 0],
+            [0, 0, 1, 0, 0, 0, 1, 0, 0],
+            [0, 0, 0, 0, 1, 0, 0, 0, 1],
+            [0, 1, 0, 0, 0, 0, 1, 0, 0],
+            [0, 0, 0, 1, 0, 0, 0, 0, 1],
+            [0, 1, 0, 0, 0, 1, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_regionstar_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?regionstar/9/9/2/10kkg606lir5860329ro0dnk178gq0";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::util;
 use cspuz_rs::graph;
-use cspuz_rs::serializer::strip_prefix;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, BooleanRunLength, Combinator, Context, Size,
+};
 use cspuz_rs::solver::{count_true, Solver, FALSE};
 
 pub fn solve_ringring(
@@ -109,43 +111,23 @@ pub fn solve_ringring(
 
 type Problem = Vec<Vec<bool>>;
 
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(BooleanRunLength)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.len();
+    let width = problem.first()?.len();
+    problem_to_url_with_context(
+        combinator(),
+        "ringring",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
 pub fn deserialize_problem(url: &str) -> Option<Problem> {
-    let serialized = strip_prefix(url)?;
-    let pos = serialized.find('/')?;
-    let kind = &serialized[0..pos];
-    if kind != "ringring" {
-        return None;
-    }
-    let body = &serialized[(pos + 1)..];
-    let toks = body.split("/").collect::<Vec<_>>();
-    if toks.len() < 3 {
-        return None;
-    }
-    let width = toks[0].parse::<usize>().ok()?;
-    let height = toks[1].parse::<usize>().ok()?;
-    let mut ret = vec![vec![false; width]; height];
-    let body = toks[2].as_bytes();
-    let mut pos = 0;
-    for &b in body {
-        if b == '.' as u8 {
-            pos += 36;
-        } else if '0' as u8 <= b && b <= '9' as u8 {
-            pos += (b - '0' as u8) as usize;
-            if pos >= height * width {
-                return None;
-            }
-            ret[pos / width][pos % width] = true;
-            pos += 1;
-        } else if 'a' as u8 <= b && b <= 'z' as u8 {
-            pos += (b - 'a' as u8) as usize + 10;
-            if pos >= height * width {
-                return None;
-            }
-            ret[pos / width][pos % width] = true;
-            pos += 1;
-        }
-    }
-    Some(ret)
+    url_to_problem(combinator(), &["ringring"], url)
 }
 
 #[cfg(test)]
@@ -191,8 +173,29 @@ mod tests {
     }
 
     #[test]
-    fn test_ringring_deserializer() {
+    fn test_ringring_serializer() {
+        let problem = problem_for_tests();
         let url = "https://puzz.link/p?ringring/8/6/063cd4";
-        assert_eq!(deserialize_problem(url), Some(problem_for_tests()));
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+
+    #[test]
+    fn test_ringring_serializer_trailing_run() {
+        // The only black cell is the last cell of the grid, preceded by a run longer than 36.
+        let mut problem = vec![vec![false; 8]; 6];
+        problem[5][7] = true;
+        let url = "https://puzz.link/p?ringring/8/6/.b";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+
+        // White cells after the last black cell are not encoded.
+        let mut problem = vec![vec![false; 8]; 6];
+        problem[0][0] = true;
+        problem[5][5] = true;
+        let url = "https://puzz.link/p?ringring/8/6/0.8";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+
+        let problem = vec![vec![false; 8]; 6];
+        let url = "https://puzz.link/p?ringring/8/6/";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
     }
 }
@@ -0,0 +1,106 @@
+use crate::puzzles::lits;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    choice, problem_to_url_with_context, url_to_problem, Combinator, Context, HexInt, Optionalize,
+    RoomsWithValues, Size, Spaces,
+};
+use cspuz_rs::solver::Solver;
+
+/// Regional LITS: LITS where a room may have a clue specifying the tetromino placed in it.
+/// Clues are 0 for L, 1 for I, 2 for T and 3 for S (reflections and rotations are allowed as usual).
+pub fn solve_rlits(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Option<i32>],
+) -> Option<Vec<Vec<Option<bool>>>> {
+    let h = borders.vertical.len();
+    assert!(h > 0);
+    let w = borders.vertical[0].len() + 1;
+
+    let mut solver = Solver::new();
+    let is_black = &solver.bool_var_2d((h, w));
+    solver.add_answer_key_bool(is_black);
+
+    let room_kind = &lits::add_constraints(&mut solver, is_black, borders);
+    assert_eq!(room_kind.len(), clues.len());
+    for i in 0..clues.len() {
+        if let Some(n) = clues[i] {
+            if !(0..=3).contains(&n) {
+                return None;
+            }
+            solver.add_expr(room_kind.at(i).eq(n));
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_black))
+}
+
+type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(RoomsWithValues::new(choice![
+        Optionalize::new(HexInt),
+        Spaces::new(None, 'g'),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "rlits",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["rlits"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[1, 0, 0, 0], [0, 1, 1, 0], [0, 0, 1, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 0, 1], [1, 0, 1], [0, 0, 1], [0, 1, 0]]),
+        };
+        (borders, vec![None, None, Some(3)])
+    }
+
+    #[test]
+    fn test_rlits_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_rlits(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        // The clued room (bottom-left) is filled with an S tetromino.
+        let expected = crate::util::tests::to_option_bool_2d([
+            [1, 1, 1, 1],
+            [0, 1, 0, 1],
+            [0, 1, 1, 1],
+            [1, 1, 0, 1],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_rlits_without_clue() {
+        let (borders, _) = problem_for_tests();
+        let ans = solve_rlits(&borders, &[None, None, None]);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        assert!(ans.iter().flatten().any(|x| x.is_none()));
+    }
+
+    #[test]
+    fn test_rlits_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?rlits/4/4/6iggogh3";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
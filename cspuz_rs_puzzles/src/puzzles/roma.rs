@@ -0,0 +1,163 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    choice, problem_to_url_with_context, url_to_problem, AlphaToNum, Combinator, Context,
+    ContextBasedGrid, Map, Optionalize, Rooms, Size, Spaces, Tuple2,
+};
+use cspuz_rs::solver::Solver;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RomaCell {
+    Up,
+    Down,
+    Left,
+    Right,
+    Goal,
+}
+
+const CELLS: [RomaCell; 5] = [
+    RomaCell::Up,
+    RomaCell::Down,
+    RomaCell::Left,
+    RomaCell::Right,
+    RomaCell::Goal,
+];
+
+/// Roma: put an arrow in each cell other than the goals so that arrows in the same room point to different
+/// directions, and following the arrows from any cell eventually reaches a goal.
+pub fn solve_roma(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Vec<Option<RomaCell>>],
+) -> Option<Vec<Vec<Option<RomaCell>>>> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    // The value of each cell is the index of the corresponding `RomaCell` in `CELLS`.
+    let cell = &solver.int_var_2d((h, w), 0, 4);
+    solver.add_answer_key_int(cell);
+
+    // Each arrow points to a cell with a smaller rank, which ensures that the arrows have no cycle.
+    let rank = &solver.int_var_2d((h, w), 0, (h * w - 1) as i32);
+    for y in 0..h {
+        for x in 0..w {
+            match clues[y][x] {
+                Some(RomaCell::Goal) => {
+                    solver.add_expr(cell.at((y, x)).eq(RomaCell::Goal as i32));
+                    solver.add_expr(rank.at((y, x)).eq(0));
+                    continue;
+                }
+                Some(c) => solver.add_expr(cell.at((y, x)).eq(c as i32)),
+                None => solver.add_expr(cell.at((y, x)).ne(RomaCell::Goal as i32)),
+            }
+
+            let neighbors = [
+                (RomaCell::Up, y > 0, y.wrapping_sub(1), x),
+                (RomaCell::Down, y < h - 1, y + 1, x),
+                (RomaCell::Left, x > 0, y, x.wrapping_sub(1)),
+                (RomaCell::Right, x < w - 1, y, x + 1),
+            ];
+            for (dir, inside, y2, x2) in neighbors {
+                let is_dir = cell.at((y, x)).eq(dir as i32);
+                if inside {
+                    solver.add_expr(is_dir.imp(rank.at((y2, x2)).lt(rank.at((y, x)))));
+                } else {
+                    solver.add_expr(!is_dir);
+                }
+            }
+        }
+    }
+
+    let rooms = graph::borders_to_rooms(borders);
+    for room in &rooms {
+        let arrows = room
+            .iter()
+            .filter(|&&(y, x)| clues[y][x] != Some(RomaCell::Goal))
+            .map(|&p| cell.at(p))
+            .collect::<Vec<_>>();
+        solver.all_different(arrows);
+    }
+
+    solver.irrefutable_facts().map(|f| {
+        f.get(cell)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|v| v.map(|n| CELLS[n as usize]))
+                    .collect()
+            })
+            .collect()
+    })
+}
+
+pub type Problem = (
+    graph::InnerGridEdges<Vec<Vec<bool>>>,
+    Vec<Vec<Option<RomaCell>>>,
+);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(Tuple2::new(
+        Rooms,
+        ContextBasedGrid::new(choice![
+            Optionalize::new(Map::new(
+                AlphaToNum::new('1', '5', 1),
+                |c: RomaCell| Some(c as i32 + 1),
+                |n: i32| CELLS.get((n - 1) as usize).copied(),
+            )),
+            Spaces::new(None, 'a'),
+        ]),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (h, w) = util::infer_shape(&problem.1);
+    problem_to_url_with_context(combinator(), "roma", problem.clone(), &Context::sized(h, w))
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["roma"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[1, 0, 0, 1], [0, 0, 0, 0], [0, 1, 1, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 1, 0], [1, 1, 1], [1, 1, 1], [0, 1, 0]]),
+        };
+        let mut clues = vec![vec![None; 4]; 4];
+        clues[0][2] = Some(RomaCell::Goal);
+        clues[3][0] = Some(RomaCell::Up);
+        (borders, clues)
+    }
+
+    #[test]
+    fn test_roma_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_roma(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        use RomaCell::*;
+        let expected = vec![
+            vec![Down, Left, Goal, Down],
+            vec![Down, Right, Up, Down],
+            vec![Right, Up, Left, Left],
+            vec![Up, Left, Right, Up],
+        ];
+        assert_eq!(ans, crate::util::tests::to_option_2d(expected));
+    }
+
+    #[test]
+    fn test_roma_serializer() {
+        let problem = problem_for_tests();
+        // Encoded by hand (not by `serialize_problem`) following the puzz.link format:
+        // - `bug`: the 12 vertical borders row by row, 5 bits per base-32 digit (01011 11110 10)
+        // - `i1g`: the 12 horizontal borders in the same way (10010 00001 10)
+        // - `b5i1c`: the cells, with 1 = up, 2 = down, 3 = left, 4 = right, 5 = goal and `a`-`z` for
+        //   runs of 1-26 empty cells: 2 empty cells, goal, 9 empty cells, up, 3 empty cells
+        let url = "https://puzz.link/p?roma/4/4/bugi1gb5i1c";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    choice, problem_to_url, url_to_problem, Combinator, Grid, HexInt, Optionalize, Spaces,
+};
+use cspuz_rs::solver::{Solver, FALSE};
+
+/// Adds a constraint that the loop drawn on `is_line` is point-symmetric about the center of the board.
+fn add_point_symmetry(solver: &mut Solver, is_line: &graph::BoolGridEdges) {
+    let (h, w) = is_line.base_shape();
+    let (h, w) = (h + 1, w + 1);
+
+    for y in 0..h {
+        for x in 0..(w - 1) {
+            solver.add_expr(
+                is_line
+                    .horizontal
+                    .at((y, x))
+                    .iff(is_line.horizontal.at((h - 1 - y, w - 2 - x))),
+            );
+        }
+    }
+    for y in 0..(h - 1) {
+        for x in 0..w {
+            solver.add_expr(
+                is_line
+                    .vertical
+                    .at((y, x))
+                    .iff(is_line.vertical.at((h - 2 - y, w - 1 - x))),
+            );
+        }
+    }
+}
+
+/// Sashiko: draw a point-symmetric loop through the cell centers.
+/// A numbered cell is a stitch: the loop goes straight through it, and the straight segment containing it
+/// consists of exactly that many edges.
+pub fn solve_sashiko(clues: &[Vec<Option<i32>>]) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+    add_point_symmetry(&mut solver, is_line);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                solver.add_expr(is_passed.at((y, x)));
+
+                let has_left = if x > 0 {
+                    is_line.horizontal.at((y, x - 1)).expr()
+                } else {
+                    FALSE
+                };
+                let has_right = if x < w - 1 {
+                    is_line.horizontal.at((y, x)).expr()
+                } else {
+                    FALSE
+                };
+                let has_up = if y > 0 {
+                    is_line.vertical.at((y - 1, x)).expr()
+                } else {
+                    FALSE
+                };
+                let has_down = if y < h - 1 {
+                    is_line.vertical.at((y, x)).expr()
+                } else {
+                    FALSE
+                };
+
+                solver.add_expr(has_left.iff(&has_right));
+                solver.add_expr(has_up.iff(&has_down));
+
+                solver.add_expr(
+                    has_left.imp(
+                        (is_line
+                            .horizontal
+                            .slice_fixed_y((y, ..x))
+                            .reverse()
+                            .consecutive_prefix_true()
+                            + is_line
+                                .horizontal
+                                .slice_fixed_y((y, x..))
+                                .consecutive_prefix_true())
+                        .eq(n),
+                    ),
+                );
+                solver.add_expr(
+                    has_up.imp(
+                        (is_line
+                            .vertical
+                            .slice_fixed_x((..y, x))
+                            .reverse()
+                            .consecutive_prefix_true()
+                            + is_line
+                                .vertical
+                                .slice_fixed_x((y.., x))
+                                .consecutive_prefix_true())
+                        .eq(n),
+                    ),
+                );
+            }
+        }
+    }
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(choice![Optionalize::new(HexInt), Spaces::new(None, 'g')])
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "sashiko", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["sashiko"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let mut ret = vec![vec![None; 5]; 5];
+        ret[0][2] = Some(4);
+        ret[1][4] = Some(2);
+        ret
+    }
+
+    #[test]
+    fn test_sashiko_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_sashiko(&problem);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [1, 1, 1, 1],
+                [1, 0, 0, 0],
+                [1, 0, 0, 1],
+                [0, 0, 0, 1],
+                [1, 1, 1, 1],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [1, 0, 0, 0, 1],
+                [0, 1, 0, 0, 1],
+                [1, 0, 0, 1, 0],
+                [1, 0, 0, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_sashiko_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?sashiko/5/5/h4l2u";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::util;
 use cspuz_rs::serializer::{
-    problem_to_url, url_to_problem, Choice, Combinator, Grid, HexInt, Optionalize, Spaces,
+    problem_to_url, url_to_problem, Choice, Combinator, Dict, Grid, HexInt, Optionalize, Spaces,
 };
 use cspuz_rs::solver::{any, Solver};
 
@@ -34,14 +34,83 @@ pub enum ShugakuDirection {
     South,
 }
 
+/// 数字が数える枕の範囲
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum ShugakuClueMode {
+    /// 上下左右に隣接する4マス (通常のルール)
+    #[default]
+    FourNeighbor,
+    /// 周囲8マス
+    EightNeighbor,
+}
+
+/// 各マスの数字。`Some(-1)` は数字のない柱を表す。
 pub type Problem = Vec<Vec<Option<i32>>>;
 
 // kind と dir を返す
 pub fn solve_shugaku(
     problem: &Problem,
-) -> Option<(Vec<Vec<Option<ShugakuKind>>>, Vec<Vec<Option<ShugakuDirection>>>)> {
+) -> Option<(
+    Vec<Vec<Option<ShugakuKind>>>,
+    Vec<Vec<Option<ShugakuDirection>>>,
+)> {
+    solve_shugaku_with_clue_mode(problem, ShugakuClueMode::default())
+}
+
+pub fn solve_shugaku_with_clue_mode(
+    problem: &Problem,
+    clue_mode: ShugakuClueMode,
+) -> Option<(
+    Vec<Vec<Option<ShugakuKind>>>,
+    Vec<Vec<Option<ShugakuDirection>>>,
+)> {
+    solve_shugaku_impl(problem, clue_mode, None)
+}
+
+/// 途中まで埋めた盤面 (`partial_kind`, `partial_dir` の `Some` のマス) を前提として確定するマスを求める。
+/// 途中の盤面と矛盾しない解が存在しない場合 (盤面の大きさが問題と異なる場合を含む) は `None` を返す。
+/// 返り値には与えたマス自身も含まれる。
+pub fn solve_shugaku_with_progress(
+    problem: &Problem,
+    partial_kind: &[Vec<Option<ShugakuKind>>],
+    partial_dir: &[Vec<Option<ShugakuDirection>>],
+) -> Option<(
+    Vec<Vec<Option<ShugakuKind>>>,
+    Vec<Vec<Option<ShugakuDirection>>>,
+)> {
+    solve_shugaku_impl(
+        problem,
+        ShugakuClueMode::default(),
+        Some((partial_kind, partial_dir)),
+    )
+}
+
+fn solve_shugaku_impl(
+    problem: &Problem,
+    clue_mode: ShugakuClueMode,
+    progress: Option<(
+        &[Vec<Option<ShugakuKind>>],
+        &[Vec<Option<ShugakuDirection>>],
+    )>,
+) -> Option<(
+    Vec<Vec<Option<ShugakuKind>>>,
+    Vec<Vec<Option<ShugakuDirection>>>,
+)> {
     let (h, w) = util::infer_shape(problem);
 
+    // 範囲外の数字を含む問題は解なしとして即座に弾く (-1は数字なしの柱)
+    let max_clue = match clue_mode {
+        ShugakuClueMode::FourNeighbor => 4,
+        ShugakuClueMode::EightNeighbor => 8,
+    };
+    if problem
+        .iter()
+        .flatten()
+        .any(|&c| matches!(c, Some(n) if n != -1 && !(0..=max_clue).contains(&n)))
+    {
+        return None;
+    }
+
     let mut solver = Solver::new();
     let kind = solver.int_var_2d((h, w), 0, 3);
     let direction = solver.int_var_2d((h, w), 0, 3);
@@ -49,8 +118,26 @@ pub fn solve_shugaku(
     solver.add_answer_key_int(&kind);
     solver.add_answer_key_int(&direction);
 
+    // --- 途中の盤面 ---
+    if let Some((partial_kind, partial_dir)) = progress {
+        if util::infer_shape(partial_kind) != (h, w) || util::infer_shape(partial_dir) != (h, w) {
+            return None;
+        }
+        for y in 0..h {
+            for x in 0..w {
+                if let Some(k) = partial_kind[y][x] {
+                    solver.add_expr(kind.at((y, x)).eq(k as i32));
+                }
+                if let Some(d) = partial_dir[y][x] {
+                    solver.add_expr(direction.at((y, x)).eq(d as i32));
+                }
+            }
+        }
+    }
+
+    // 通路は上下左右に隣接するマス同士でのみつながる (斜めに接しているだけでは連結とみなさない)
     cspuz_rs::graph::active_vertices_connected_2d(&mut solver, &kind.eq(ShugakuKind::Aisle as i32));
-    solver.add_expr(!kind.eq(ShugakuKind::Aisle as i32).conv2d_and((2, 2)));
+    cspuz_rs::graph::no_2x2_block(&mut solver, &kind.eq(ShugakuKind::Aisle as i32));
 
     // 柱(Pillar)または通路(Aisle)であることと、向きがNoneであることは同値
     solver.add_expr(
@@ -62,18 +149,24 @@ pub fn solve_shugaku(
     for y in 0..h {
         for x in 0..w {
             match problem[y][x] {
-                // 5は柱
-                Some(5) => solver.add_expr(kind.at((y, x)).eq(ShugakuKind::Pillar as i32)),
+                // 数字なしの柱
+                Some(-1) => solver.add_expr(kind.at((y, x)).eq(ShugakuKind::Pillar as i32)),
                 // その他の数字マス
                 Some(n) => {
                     solver.add_expr(kind.at((y, x)).eq(ShugakuKind::Pillar as i32));
                     // 数字は周囲にある枕(Pillow)の数を示す
-                    solver.add_expr(
-                        kind.four_neighbors((y, x))
-                            .eq(ShugakuKind::Pillow as i32)
-                            .count_true()
-                            .eq(n),
-                    );
+                    let neighbors = match clue_mode {
+                        ShugakuClueMode::FourNeighbor => kind.four_neighbors((y, x)),
+                        ShugakuClueMode::EightNeighbor => {
+                            // 中心のマスは柱なので、3x3の範囲をそのまま数えてよい
+                            let y0 = (y as i32 - 1).max(0) as usize;
+                            let y1 = (y + 2).min(h);
+                            let x0 = (x as i32 - 1).max(0) as usize;
+                            let x1 = (x + 2).min(w);
+                            kind.slice((y0..y1, x0..x1)).flatten()
+                        }
+                    };
+                    solver.add_expr(neighbors.eq(ShugakuKind::Pillow as i32).count_true().eq(n));
                 }
                 // 空白マスは柱ではない
                 None => solver.add_expr(kind.at((y, x)).ne(ShugakuKind::Pillar as i32)),
@@ -134,6 +227,7 @@ pub fn solve_shugaku(
 
 
     // --- 枕と通路の隣接ルール ---
+    // 枕同士が隣接することはルール上許される (並んで寝る布団など) ため、その制約は課さない
     let neighbor_defs: &[(ShugakuDirection, (&[i32], &[i32]))] = &[
         (
             ShugakuDirection::South,
@@ -254,19 +348,41 @@ pub fn solve_shugaku(
 
 // --- シリアライズ/デシリアライズ ---
 
-fn combinator() -> impl Combinator<Problem> {
+// puzz.link の形式では数字なしの柱を 5 で表し、空白の連続を 6 以降の文字で表す。
+// 8近傍の数字は 5 以上にもなるため、数字なしの柱を . で、空白の連続を a 以降の文字で表す。
+fn combinator(clue_mode: ShugakuClueMode) -> impl Combinator<Problem> {
+    let (space_start, pillar) = match clue_mode {
+        ShugakuClueMode::FourNeighbor => ('6', "5"),
+        ShugakuClueMode::EightNeighbor => ('a', "."),
+    };
     Grid::new(Choice::new(vec![
-        Box::new(Spaces::new(None, '6')),
+        Box::new(Spaces::new(None, space_start)),
+        Box::new(Dict::new(Some(-1), pillar)),
         Box::new(Optionalize::new(HexInt)),
     ]))
 }
 
 pub fn serialize_problem(problem: &Problem) -> Option<String> {
-    problem_to_url(combinator(), "shugaku", problem.clone())
+    serialize_problem_with_clue_mode(problem, ShugakuClueMode::default())
 }
 
 pub fn deserialize_problem(url: &str) -> Option<Problem> {
-    url_to_problem(combinator(), &["shugaku"], url)
+    deserialize_problem_with_clue_mode(url, ShugakuClueMode::default())
+}
+
+/// URL は数字の数え方を含まないため、呼び出し側が `clue_mode` を指定する。
+pub fn serialize_problem_with_clue_mode(
+    problem: &Problem,
+    clue_mode: ShugakuClueMode,
+) -> Option<String> {
+    problem_to_url(combinator(clue_mode), "shugaku", problem.clone())
+}
+
+pub fn deserialize_problem_with_clue_mode(
+    url: &str,
+    clue_mode: ShugakuClueMode,
+) -> Option<Problem> {
+    url_to_problem(combinator(clue_mode), &["shugaku"], url)
 }
 
 // main関数の代わり、またはmain関数から呼び出す
@@ -313,3 +429,255 @@ pub fn print_solution(
 
     println!("{}", format!("└{}───┘", "───┴".repeat(w - 1)));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cspuz_rs::serializer::Context;
+
+    #[test]
+    fn test_shugaku_adjacent_pillows() {
+        // The unique solution has two West-facing futons side by side, so their pillows touch.
+        let problem = vec![
+            vec![Some(1), None, None, None],
+            vec![Some(2), None, None, None],
+            vec![None, None, None, None],
+        ];
+        let (kind, direction) = solve_shugaku(&problem).unwrap();
+
+        use ShugakuDirection as D;
+        use ShugakuKind as K;
+        let expected_kind = vec![
+            vec![K::Pillar, K::Pillow, K::Futon, K::Aisle],
+            vec![K::Pillar, K::Pillow, K::Futon, K::Aisle],
+            vec![K::Pillow, K::Futon, K::Aisle, K::Aisle],
+        ];
+        let expected_direction = vec![
+            vec![D::None, D::West, D::West, D::None],
+            vec![D::None, D::West, D::West, D::None],
+            vec![D::West, D::West, D::None, D::None],
+        ];
+        assert_eq!(kind, crate::util::tests::to_option_2d(expected_kind));
+        assert_eq!(
+            direction,
+            crate::util::tests::to_option_2d(expected_direction)
+        );
+    }
+
+    #[test]
+    fn test_shugaku_diagonal_aisles() {
+        // The only placement satisfying all the other rules is the following, where the aisle at (0, 1)
+        // touches the other aisles only diagonally. Aisles must be orthogonally connected, so it is rejected.
+        //   0 . ■
+        //   . 2 ▼
+        //   . ◀ ■
+        let problem = vec![
+            vec![Some(0), None, None],
+            vec![None, Some(2), None],
+            vec![None, None, None],
+        ];
+        assert_eq!(solve_shugaku(&problem), None);
+    }
+
+    #[test]
+    fn test_shugaku_out_of_range_clue() {
+        let mut problem = vec![vec![None; 4]; 4];
+        problem[1][2] = Some(9);
+        assert_eq!(solve_shugaku(&problem), None);
+        assert_eq!(
+            solve_shugaku_with_clue_mode(&problem, ShugakuClueMode::EightNeighbor),
+            None
+        );
+
+        problem[1][2] = Some(-2);
+        assert_eq!(solve_shugaku(&problem), None);
+
+        problem[1][2] = Some(5);
+        assert_eq!(solve_shugaku(&problem), None);
+    }
+
+    #[test]
+    fn test_shugaku_unnumbered_pillar() {
+        // 8近傍では 5 も数字として URL に書き、数字なしの柱は -1 で表す
+        let problem = vec![
+            vec![None, None, None],
+            vec![None, Some(5), None],
+            vec![None, None, None],
+        ];
+        let url = serialize_problem_with_clue_mode(&problem, ShugakuClueMode::EightNeighbor);
+        assert_eq!(
+            url,
+            Some(String::from("https://puzz.link/p?shugaku/3/3/d5d"))
+        );
+        assert_eq!(
+            deserialize_problem_with_clue_mode(
+                "https://puzz.link/p?shugaku/3/3/d5d",
+                ShugakuClueMode::EightNeighbor
+            ),
+            Some(problem.clone())
+        );
+
+        let problem = vec![
+            vec![None, None, None],
+            vec![None, Some(-1), None],
+            vec![None, None, None],
+        ];
+        for clue_mode in [
+            ShugakuClueMode::FourNeighbor,
+            ShugakuClueMode::EightNeighbor,
+        ] {
+            assert!(solve_shugaku_with_clue_mode(&problem, clue_mode).is_some());
+        }
+        assert_eq!(
+            deserialize_problem("https://puzz.link/p?shugaku/3/3/959"),
+            Some(problem.clone())
+        );
+        assert_eq!(
+            deserialize_problem_with_clue_mode(
+                "https://puzz.link/p?shugaku/3/3/d.d",
+                ShugakuClueMode::EightNeighbor
+            ),
+            Some(problem)
+        );
+    }
+
+    #[test]
+    fn test_shugaku_clue_mode() {
+        let problem = vec![
+            vec![None, Some(1), None, None],
+            vec![None, None, Some(1), None],
+            vec![None, None, None, None],
+        ];
+
+        use ShugakuDirection as D;
+        use ShugakuKind as K;
+
+        let (kind, direction) =
+            solve_shugaku_with_clue_mode(&problem, ShugakuClueMode::FourNeighbor).unwrap();
+        assert_eq!(
+            solve_shugaku(&problem),
+            Some((kind.clone(), direction.clone()))
+        );
+        let expected_kind = vec![
+            vec![K::Futon, K::Pillar, K::Pillow, K::Futon],
+            vec![K::Pillow, K::Aisle, K::Pillar, K::Aisle],
+            vec![K::Aisle, K::Aisle, K::Aisle, K::Aisle],
+        ];
+        let expected_direction = vec![
+            vec![D::South, D::None, D::West, D::West],
+            vec![D::South, D::None, D::None, D::None],
+            vec![D::None, D::None, D::None, D::None],
+        ];
+        assert_eq!(kind, crate::util::tests::to_option_2d(expected_kind));
+        assert_eq!(
+            direction,
+            crate::util::tests::to_option_2d(expected_direction)
+        );
+
+        // (0, 1) now also sees the pillow at (1, 0), so the futon in the top right has to turn around.
+        let (kind, direction) =
+            solve_shugaku_with_clue_mode(&problem, ShugakuClueMode::EightNeighbor).unwrap();
+        let expected_kind = vec![
+            vec![K::Futon, K::Pillar, K::Futon, K::Pillow],
+            vec![K::Pillow, K::Aisle, K::Pillar, K::Aisle],
+            vec![K::Aisle, K::Aisle, K::Aisle, K::Aisle],
+        ];
+        let expected_direction = vec![
+            vec![D::South, D::None, D::East, D::East],
+            vec![D::South, D::None, D::None, D::None],
+            vec![D::None, D::None, D::None, D::None],
+        ];
+        assert_eq!(kind, crate::util::tests::to_option_2d(expected_kind));
+        assert_eq!(
+            direction,
+            crate::util::tests::to_option_2d(expected_direction)
+        );
+    }
+
+    #[test]
+    fn test_shugaku_with_progress() {
+        let problem = vec![
+            vec![None, None, None],
+            vec![None, Some(2), None],
+            vec![None, None, None],
+        ];
+
+        use ShugakuDirection as D;
+        use ShugakuKind as K;
+
+        // Without any progress, only the clue cell itself is determined.
+        let (kind, _) = solve_shugaku(&problem).unwrap();
+        assert_eq!(kind.iter().flatten().filter(|k| k.is_some()).count(), 1);
+
+        let mut partial_kind = vec![vec![None; 3]; 3];
+        let mut partial_dir = vec![vec![None; 3]; 3];
+        partial_kind[2][1] = Some(K::Pillow);
+        partial_dir[2][1] = Some(D::West);
+        let (kind, direction) =
+            solve_shugaku_with_progress(&problem, &partial_kind, &partial_dir).unwrap();
+        let expected_kind = vec![
+            vec![K::Aisle, K::Aisle, K::Futon],
+            vec![K::Aisle, K::Pillar, K::Pillow],
+            vec![K::Aisle, K::Pillow, K::Futon],
+        ];
+        let expected_direction = vec![
+            vec![D::None, D::None, D::South],
+            vec![D::None, D::None, D::South],
+            vec![D::None, D::West, D::West],
+        ];
+        assert_eq!(kind, crate::util::tests::to_option_2d(expected_kind));
+        assert_eq!(
+            direction,
+            crate::util::tests::to_option_2d(expected_direction)
+        );
+
+        // A pillar can only be placed on a clue cell.
+        let mut partial_kind = vec![vec![None; 3]; 3];
+        let empty_dir = vec![vec![None; 3]; 3];
+        partial_kind[0][0] = Some(K::Pillar);
+        assert_eq!(
+            solve_shugaku_with_progress(&problem, &partial_kind, &empty_dir),
+            None
+        );
+
+        let narrow_kind = vec![vec![None; 2]; 3];
+        assert_eq!(
+            solve_shugaku_with_progress(&problem, &narrow_kind, &empty_dir),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shugaku_serializer_roundtrip() {
+        for (clue_mode, max_clue) in [
+            (ShugakuClueMode::FourNeighbor, 4),
+            (ShugakuClueMode::EightNeighbor, 8),
+        ] {
+            util::tests::roundtrip_property(
+                combinator(clue_mode),
+                |rng| {
+                    let height = rng.next(6) + 1;
+                    let width = rng.next(6) + 1;
+                    // sparse problems produce long runs of spaces, including trailing ones
+                    let density = rng.next(4) + 1;
+                    let problem = (0..height)
+                        .map(|_| {
+                            (0..width)
+                                .map(|_| {
+                                    if rng.next(density + 1) == 0 {
+                                        // -1 は数字なしの柱
+                                        Some(rng.next_in(-1, max_clue))
+                                    } else {
+                                        None
+                                    }
+                                })
+                                .collect()
+                        })
+                        .collect::<Problem>();
+                    (Context::new(), problem)
+                },
+                200,
+            );
+        }
+    }
+}
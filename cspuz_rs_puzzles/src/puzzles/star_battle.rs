@@ -3,7 +3,7 @@ use cspuz_rs::serializer::{
     problem_to_url_with_context, url_to_problem, Combinator, Context, DecInt, PrefixAndSuffix,
     Rooms, Size, Tuple2,
 };
-use cspuz_rs::solver::Solver;
+use cspuz_rs::solver::{BoolVarArray2D, Solver};
 
 pub fn solve_star_battle(
     star_amount: i32,
@@ -21,6 +21,19 @@ pub fn solve_star_battle(
     let has_star = solver.bool_var_2d((height, height));
     solver.add_answer_key_bool(&has_star);
 
+    add_constraints(&mut solver, &has_star, star_amount, borders);
+
+    solver.irrefutable_facts().map(|f| f.get(&has_star))
+}
+
+pub(super) fn add_constraints(
+    solver: &mut Solver,
+    has_star: &BoolVarArray2D,
+    star_amount: i32,
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+) {
+    let height = borders.vertical.len();
+
     let rooms = graph::borders_to_rooms(borders);
 
     for i in 0..height {
@@ -37,13 +50,11 @@ pub fn solve_star_battle(
     for room in &rooms {
         solver.add_expr(has_star.select(room).count_true().eq(star_amount));
     }
-
-    solver.irrefutable_facts().map(|f| f.get(&has_star))
 }
 
 pub type Problem = (i32, graph::InnerGridEdges<Vec<Vec<bool>>>);
 
-fn combinator() -> impl Combinator<Problem> {
+pub(super) fn combinator() -> impl Combinator<Problem> {
     Size::new(Tuple2::new(PrefixAndSuffix::new("", DecInt, "/"), Rooms))
 }
 
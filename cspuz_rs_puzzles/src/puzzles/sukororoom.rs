@@ -0,0 +1,114 @@
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, HexInt, Optionalize,
+    RoomsWithValues, Size, Spaces,
+};
+use cspuz_rs::solver::{count_true, Solver};
+
+/// Solves a Sukoro variant whose grid is divided into rooms.
+///
+/// As in Sukoro, each numbered cell holds the number of orthogonally adjacent numbered cells,
+/// adjacent numbered cells do not hold the same number and all numbered cells are connected.
+/// Additionally, a room with a clue must contain numbers whose sum is equal to the clue.
+/// Empty cells are reported as `Some(0)`.
+pub fn solve_sukororoom(
+    borders: &graph::InnerGridEdges<Vec<Vec<bool>>>,
+    clues: &[Option<i32>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = borders.base_shape();
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((h, w), 0, 4);
+    solver.add_answer_key_int(num);
+
+    let is_num = &num.ne(0);
+    graph::active_vertices_connected_2d(&mut solver, is_num);
+
+    for y in 0..h {
+        for x in 0..w {
+            solver.add_expr(
+                is_num
+                    .at((y, x))
+                    .imp(num.at((y, x)).eq(count_true(is_num.four_neighbors((y, x))))),
+            );
+        }
+    }
+    solver.add_expr(
+        (is_num.slice((.., ..(w - 1))) & is_num.slice((.., 1..)))
+            .imp(num.slice((.., ..(w - 1))).ne(num.slice((.., 1..)))),
+    );
+    solver.add_expr(
+        (is_num.slice((..(h - 1), ..)) & is_num.slice((1.., ..)))
+            .imp(num.slice((..(h - 1), ..)).ne(num.slice((1.., ..)))),
+    );
+
+    let rooms = graph::borders_to_rooms(borders);
+    assert_eq!(rooms.len(), clues.len());
+    for (room, &clue) in rooms.iter().zip(clues) {
+        if let Some(n) = clue {
+            solver.add_expr(num.select(room).sum().eq(n));
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(num))
+}
+
+pub type Problem = (graph::InnerGridEdges<Vec<Vec<bool>>>, Vec<Option<i32>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(RoomsWithValues::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ])))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.0.vertical.len();
+    let width = problem.0.vertical[0].len() + 1;
+    problem_to_url_with_context(
+        combinator(),
+        "sukororoom",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["sukororoom"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let borders = graph::InnerGridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[0, 0, 0, 0], [1, 1, 1, 1], [0, 0, 0, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 1, 0], [0, 1, 0], [0, 1, 0], [0, 1, 0]]),
+        };
+        (borders, vec![Some(12), Some(5), Some(5), Some(2)])
+    }
+
+    #[test]
+    fn test_sukororoom_problem() {
+        let (borders, clues) = problem_for_tests();
+        let ans = solve_sukororoom(&borders, &clues);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = crate::util::tests::to_option_2d([
+            [2, 3, 2, 0],
+            [3, 4, 3, 0],
+            [2, 3, 2, 0],
+            [0, 0, 0, 0],
+        ]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_sukororoom_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?sukororoom/4/4/94g1s0c552";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
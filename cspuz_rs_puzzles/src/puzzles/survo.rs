@@ -0,0 +1,159 @@
+use crate::util;
+use cspuz_rs::serializer::{
+    choice, problem_to_url_with_context, url_to_problem, Combinator, Context, ContextBasedGrid,
+    HexInt, Optionalize, Seq, Sequencer, Size, Spaces,
+};
+use cspuz_rs::solver::Solver;
+
+pub fn solve_survo(
+    column_sums: &[Option<i32>],
+    row_sums: &[Option<i32>],
+    cells: &[Vec<Option<i32>>],
+) -> Option<Vec<Vec<Option<i32>>>> {
+    let (h, w) = util::infer_shape(cells);
+    if row_sums.len() != h || column_sums.len() != w {
+        return None;
+    }
+
+    let mut solver = Solver::new();
+    let num = &solver.int_var_2d((h, w), 1, (h * w) as i32);
+    solver.add_answer_key_int(num);
+
+    solver.all_different_2d(num);
+
+    for y in 0..h {
+        if let Some(n) = row_sums[y] {
+            solver.add_expr(num.slice_fixed_y((y, ..)).sum().eq(n));
+        }
+    }
+    for x in 0..w {
+        if let Some(n) = column_sums[x] {
+            solver.add_expr(num.slice_fixed_x((.., x)).sum().eq(n));
+        }
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = cells[y][x] {
+                solver.add_expr(num.at((y, x)).eq(n));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(num))
+}
+
+pub type Problem = (
+    Vec<Option<i32>>,      // column sums
+    Vec<Option<i32>>,      // row sums
+    Vec<Vec<Option<i32>>>, // cells
+);
+
+fn internal_combinator() -> impl Combinator<Option<i32>> {
+    choice![Optionalize::new(HexInt), Spaces::new(None, 'g')]
+}
+
+struct SurvoCombinator;
+
+impl Combinator<Problem> for SurvoCombinator {
+    fn serialize(&self, ctx: &Context, input: &[Problem]) -> Option<(usize, Vec<u8>)> {
+        if input.is_empty() {
+            return None;
+        }
+
+        let height = ctx.height?;
+        let width = ctx.width?;
+
+        let (column_sums, row_sums, cells) = &input[0];
+
+        let sums = [&column_sums[..], &row_sums[..]].concat();
+        let mut ret = Seq::new(internal_combinator(), width + height)
+            .serialize(ctx, &[sums])?
+            .1;
+
+        // the grid part is omitted if there is no given number
+        if cells.iter().any(|row| row.iter().any(|n| n.is_some())) {
+            ret.extend(
+                ContextBasedGrid::new(internal_combinator())
+                    .serialize(ctx, std::slice::from_ref(cells))?
+                    .1,
+            );
+        }
+
+        Some((1, ret))
+    }
+
+    fn deserialize(&self, ctx: &Context, input: &[u8]) -> Option<(usize, Vec<Problem>)> {
+        let mut sequencer = Sequencer::new(input);
+
+        let height = ctx.height?;
+        let width = ctx.width?;
+
+        let sums =
+            sequencer.deserialize_one_elem(ctx, Seq::new(internal_combinator(), width + height))?;
+        let column_sums = sums[..width].to_vec();
+        let row_sums = sums[width..].to_vec();
+
+        let cells = if sequencer.n_remaining() > 0 {
+            sequencer.deserialize_one_elem(ctx, ContextBasedGrid::new(internal_combinator()))?
+        } else {
+            vec![vec![None; width]; height]
+        };
+
+        Some((sequencer.n_read(), vec![(column_sums, row_sums, cells)]))
+    }
+}
+
+fn combinator() -> impl Combinator<Problem> {
+    Size::new(SurvoCombinator)
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let height = problem.1.len();
+    let width = problem.0.len();
+
+    problem_to_url_with_context(
+        combinator(),
+        "survo",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["survo"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        (
+            vec![Some(10), Some(17), Some(18)],
+            vec![Some(17), Some(10), Some(18)],
+            vec![
+                vec![None, None, None],
+                vec![None, None, None],
+                vec![Some(6), None, None],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_survo_problem() {
+        let (column_sums, row_sums, cells) = problem_for_tests();
+        let ans = solve_survo(&column_sums, &row_sums, &cells);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+        let expected = crate::util::tests::to_option_2d([[1, 7, 9], [3, 2, 5], [6, 8, 4]]);
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_survo_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?survo/3/3/a-11-12-11a-12l6h";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
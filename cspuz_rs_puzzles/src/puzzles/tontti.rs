@@ -0,0 +1,118 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url_with_context, url_to_problem, Choice, Combinator, Context, ContextBasedGrid,
+    Dict, Map, Rooms, Size, Spaces, Tuple2,
+};
+use cspuz_rs::solver::Solver;
+
+pub fn solve_tontti(
+    given_lines: &graph::BoolGridEdgesModel,
+    marked: &[Vec<bool>],
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(marked);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    let is_passed = &graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    for y in 0..h {
+        for x in 0..w {
+            if x < w - 1 && given_lines.horizontal[y][x] {
+                solver.add_expr(is_line.horizontal.at((y, x)));
+            }
+            if y < h - 1 && given_lines.vertical[y][x] {
+                solver.add_expr(is_line.vertical.at((y, x)));
+            }
+        }
+    }
+    for (y, row) in marked.iter().enumerate() {
+        for (x, &m) in row.iter().enumerate() {
+            if m {
+                solver.add_expr(is_passed.at((y, x)));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = (graph::BoolGridEdgesModel, Vec<Vec<bool>>);
+
+fn combinator() -> impl Combinator<Problem> {
+    // Given line segments are encoded in the same way as room borders.
+    // Note that a line segment between horizontally adjacent cells corresponds to a vertical border.
+    Size::new(Tuple2::new(
+        Map::new(
+            Rooms,
+            |lines: graph::BoolGridEdgesModel| {
+                Some(graph::InnerGridEdges {
+                    horizontal: lines.vertical,
+                    vertical: lines.horizontal,
+                })
+            },
+            |borders: graph::InnerGridEdges<Vec<Vec<bool>>>| {
+                Some(graph::GridEdges {
+                    horizontal: borders.vertical,
+                    vertical: borders.horizontal,
+                })
+            },
+        ),
+        ContextBasedGrid::new(Choice::new(vec![
+            Box::new(Dict::new(true, "1")),
+            Box::new(Spaces::new(false, 'g')),
+        ])),
+    ))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    let (height, width) = util::infer_shape(&problem.1);
+    problem_to_url_with_context(
+        combinator(),
+        "tontti",
+        problem.clone(),
+        &Context::sized(height, width),
+    )
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["tontti"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        let given_lines = graph::GridEdges {
+            horizontal: crate::util::tests::to_bool_2d([[0, 1], [0, 0], [0, 0]]),
+            vertical: crate::util::tests::to_bool_2d([[0, 0, 0], [1, 0, 0]]),
+        };
+        let marked = crate::util::tests::to_bool_2d([[1, 0, 0], [0, 0, 0], [0, 0, 1]]);
+        (given_lines, marked)
+    }
+
+    #[test]
+    fn test_tontti_problem() {
+        let (given_lines, marked) = problem_for_tests();
+        let ans = solve_tontti(&given_lines, &marked);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([[1, 1], [0, 0], [1, 1]]),
+            vertical: crate::util::tests::to_option_bool_2d([[1, 0, 1], [1, 0, 1]]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_tontti_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?tontti/3/3/80201m1";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::util;
+use cspuz_rs::graph;
+use cspuz_rs::serializer::{
+    problem_to_url, url_to_problem, Choice, Combinator, Grid, HexInt, Optionalize, Spaces,
+};
+use cspuz_rs::solver::Solver;
+
+pub fn solve_vertexloop(
+    clues: &[Vec<Option<i32>>],
+) -> Option<graph::BoolGridEdgesIrrefutableFacts> {
+    let (h, w) = util::infer_shape(clues);
+
+    let mut solver = Solver::new();
+    let is_line = &graph::BoolGridEdges::new(&mut solver, (h - 1, w - 1));
+    solver.add_answer_key_bool(&is_line.horizontal);
+    solver.add_answer_key_bool(&is_line.vertical);
+
+    graph::single_cycle_grid_edges(&mut solver, is_line);
+
+    for y in 0..h {
+        for x in 0..w {
+            if let Some(n) = clues[y][x] {
+                // A clue of 0 means that the loop does not visit this vertex at all.
+                solver.add_expr(is_line.vertex_neighbors((y, x)).count_true().eq(n));
+            }
+        }
+    }
+
+    solver.irrefutable_facts().map(|f| f.get(is_line))
+}
+
+type Problem = Vec<Vec<Option<i32>>>;
+
+fn combinator() -> impl Combinator<Problem> {
+    Grid::new(Choice::new(vec![
+        Box::new(Optionalize::new(HexInt)),
+        Box::new(Spaces::new(None, 'g')),
+    ]))
+}
+
+pub fn serialize_problem(problem: &Problem) -> Option<String> {
+    problem_to_url(combinator(), "vertexloop", problem.clone())
+}
+
+pub fn deserialize_problem(url: &str) -> Option<Problem> {
+    url_to_problem(combinator(), &["vertexloop"], url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        vec![
+            vec![Some(2), None, None, Some(0)],
+            vec![None, Some(0), Some(2), None],
+            vec![None, None, None, Some(0)],
+        ]
+    }
+
+    #[test]
+    fn test_vertexloop_problem() {
+        let problem = problem_for_tests();
+        let ans = solve_vertexloop(&problem);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::BoolGridEdgesIrrefutableFacts {
+            horizontal: crate::util::tests::to_option_bool_2d([[1, 1, 0], [0, 0, 0], [1, 1, 0]]),
+            vertical: crate::util::tests::to_option_bool_2d([[1, 0, 1, 0], [1, 0, 1, 0]]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_vertexloop_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?vertexloop/4/3/2h0g02j0";
+        crate::util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
@@ -75,7 +75,7 @@ pub fn solve_waterwalk(
             }
             if water[y][x] {
                 solver.add_expr(line_rank.at((y, x)).le(1));
-            } 
+            }
 
             let mut inbound = vec![];
             let mut outbound = vec![];
@@ -133,66 +133,54 @@ pub fn deserialize_problem(url: &str) -> Option<Problem> {
     url_to_problem(combinator(), &["waterwalk"], url)
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-// 
-//     fn problem_for_tests() -> Problem {
-//         (
-//             crate::util::tests::to_bool_2d([
-//                 [1, 0, 0, 0, 0, 0],
-//                 [0, 1, 1, 0, 0, 0],
-//                 [0, 1, 1, 0, 1, 0],
-//                 [0, 0, 0, 0, 1, 0],
-//                 [0, 0, 0, 1, 0, 1],
-//                 [1, 1, 0, 1, 0, 1],
-//                 [0, 0, 0, 0, 0, 0],
-//             ]),
-//             vec![
-//                 vec![None, None, None, None, None, None],
-//                 vec![Some(2), None, None, Some(2), None, None],
-//                 vec![None, None, None, Some(3), None, None],
-//                 vec![None, None, None, None, None, None],
-//                 vec![None, None, Some(5), None, Some(1), None],
-//                 vec![None, None, None, None, Some(3), None],
-//                 vec![None, None, None, None, None, Some(3)],
-//             ],
-//         )
-//     }
-// 
-//  #[test]
-//     fn test_icewalk_problem() {
-//         let (icebarn, num) = problem_for_tests();
-//         let ans = solve_icewalk(&icebarn, &num);
-//         assert!(ans.is_some());
-//         let ans = ans.unwrap();
-// 
-//         let expected = graph::GridEdges {
-//             horizontal: crate::util::tests::to_option_bool_2d([
-//                 [0, 0, 1, 1, 1],
-//                 [1, 1, 1, 1, 0],
-//                 [1, 1, 1, 0, 0],
-//                 [1, 0, 1, 0, 0],
-//                 [0, 1, 1, 1, 0],
-//                 [0, 0, 1, 1, 0],
-//                 [1, 1, 0, 0, 1],
-//             ]),
-//             vertical: crate::util::tests::to_option_bool_2d([
-//                 [0, 0, 1, 0, 0, 1],
-//                 [1, 0, 1, 0, 1, 1],
-//                 [0, 0, 1, 1, 1, 1],
-//                 [1, 1, 0, 0, 1, 1],
-//                 [1, 0, 0, 0, 0, 1],
-//                 [1, 0, 1, 0, 1, 1],
-//             ]),
-//         };
-//         assert_eq!(ans, expected);
-//     }
-// 
-//     #[test]
-//     fn test_icewalk_serializer() {
-//         let problem = problem_for_tests();
-//         let url = "https://puzz.link/p?icewalk/6/7/g63845qg0l2h2k3p5g1k3l3";
-//         util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem_for_tests() -> Problem {
+        (
+            crate::util::tests::to_bool_2d([
+                [0, 0, 1, 0, 0],
+                [1, 0, 0, 1, 0],
+                [0, 0, 0, 0, 0],
+                [1, 0, 0, 0, 1],
+            ]),
+            vec![
+                vec![None, None, None, None, None],
+                vec![None, None, None, None, Some(3)],
+                vec![Some(5), None, None, None, None],
+                vec![None, None, Some(3), Some(3), None],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_waterwalk_problem() {
+        let (water, num) = problem_for_tests();
+        let ans = solve_waterwalk(&water, &num);
+        assert!(ans.is_some());
+        let ans = ans.unwrap();
+
+        let expected = graph::GridEdges {
+            horizontal: crate::util::tests::to_option_bool_2d([
+                [1, 1, 1, 1],
+                [1, 1, 0, 1],
+                [1, 1, 0, 1],
+                [1, 1, 1, 1],
+            ]),
+            vertical: crate::util::tests::to_option_bool_2d([
+                [1, 0, 0, 0, 1],
+                [0, 0, 1, 1, 0],
+                [1, 0, 0, 0, 1],
+            ]),
+        };
+        assert_eq!(ans, expected);
+    }
+
+    #[test]
+    fn test_waterwalk_serializer() {
+        let problem = problem_for_tests();
+        let url = "https://puzz.link/p?waterwalk/5/4/4i0ho35l33g";
+        util::tests::serializer_test(problem, url, serialize_problem, deserialize_problem);
+    }
+}
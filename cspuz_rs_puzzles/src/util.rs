@@ -1,3 +1,4 @@
+use cspuz_rs::solver::{any, BoolVarArray2D, IntVarArray1D, IntVarArray2D, Solver};
 use std::ops::{Index, IndexMut};
 
 pub fn infer_shape<T>(array: &[Vec<T>]) -> (usize, usize) {
@@ -67,9 +68,329 @@ impl<T: Clone> IndexMut<(usize, usize)> for Grid<T> {
     }
 }
 
+/// How the values of rooms touching each other are related in `add_adjacent_rooms_value_constraints`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AdjacentRoomsRule {
+    /// Each room which is adjacent to some other room must touch at least one room having the same value.
+    Share,
+    /// No two rooms touching each other have the same value.
+    Differ,
+}
+
+/// Adds constraints comparing `values` (one for each room) between rooms touching each other,
+/// as specified by `rule`.
+///
+/// Two rooms are considered to touch each other when there is a pair of orthogonally adjacent
+/// cells, one in each room, such that both cells are active (`is_active`).
+/// `room_id[y][x]` must be the index of the room containing the cell (y, x) in `rooms`.
+pub fn add_adjacent_rooms_value_constraints(
+    solver: &mut Solver,
+    rooms: &[Vec<(usize, usize)>],
+    room_id: &[Vec<usize>],
+    is_active: &BoolVarArray2D,
+    values: &IntVarArray1D,
+    rule: AdjacentRoomsRule,
+) {
+    let (h, w) = infer_shape(room_id);
+
+    for (i, room) in rooms.iter().enumerate() {
+        let mut touching = vec![];
+        for &(y, x) in room {
+            for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                let (ny, nx) = (y as i32 + dy, x as i32 + dx);
+                if ny < 0 || ny >= h as i32 || nx < 0 || nx >= w as i32 {
+                    continue;
+                }
+
+                let (ny, nx) = (ny as usize, nx as usize);
+                let j = room_id[ny][nx];
+                if i == j {
+                    continue;
+                }
+                match rule {
+                    AdjacentRoomsRule::Share => touching.push(
+                        is_active.at((y, x))
+                            & is_active.at((ny, nx))
+                            & values.at(i).eq(values.at(j)),
+                    ),
+                    AdjacentRoomsRule::Differ => {
+                        if i < j {
+                            solver.add_expr(
+                                (is_active.at((y, x)) & is_active.at((ny, nx)))
+                                    .imp(values.at(i).ne(values.at(j))),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        if !touching.is_empty() {
+            solver.add_expr(any(&touching));
+        }
+    }
+}
+
+/// The direction of the lines considered in `add_exactly_once_per_line`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineAxis {
+    Row,
+    Column,
+}
+
+/// Adds a constraint that `value` appears exactly once in each row (or column, depending on `axis`) of `grid`.
+pub fn add_exactly_once_per_line(
+    solver: &mut Solver,
+    grid: &IntVarArray2D,
+    value: i32,
+    axis: LineAxis,
+) {
+    let (h, w) = grid.shape();
+    match axis {
+        LineAxis::Row => {
+            for y in 0..h {
+                solver.add_expr(grid.slice_fixed_y((y, ..)).eq(value).count_true().eq(1));
+            }
+        }
+        LineAxis::Column => {
+            for x in 0..w {
+                solver.add_expr(grid.slice_fixed_x((.., x)).eq(value).count_true().eq(1));
+            }
+        }
+    }
+}
+
+/// Same as `add_exactly_once_per_line` for each of `values`, but puts the constraints on each line into
+/// a constraint group of its own (see `Solver::begin_group`).
+///
+/// `on_line(solver, i)` is called inside the group of the `i`-th line, so that other constraints on the line
+/// (such as clues outside the grid) can be added to the same group.
+pub fn add_exactly_once_per_line_grouped<F>(
+    solver: &mut Solver,
+    grid: &IntVarArray2D,
+    values: &[i32],
+    axis: LineAxis,
+    mut on_line: F,
+) where
+    F: FnMut(&mut Solver, usize),
+{
+    let (h, w) = grid.shape();
+    let num_lines = match axis {
+        LineAxis::Row => h,
+        LineAxis::Column => w,
+    };
+    for i in 0..num_lines {
+        let line = match axis {
+            LineAxis::Row => grid.slice_fixed_y((i, ..)),
+            LineAxis::Column => grid.slice_fixed_x((.., i)),
+        };
+        solver.begin_group();
+        for &value in values {
+            solver.add_expr(line.eq(value).count_true().eq(1));
+        }
+        on_line(solver, i);
+        solver.end_group();
+    }
+}
+
+/// Canonicalization of polyominoes under translation, rotation and reflection.
+///
+/// A polyomino is given as a list of cells `(y, x)`. Cells may be given in any order and at any offset.
+pub mod polyomino {
+    /// Translates `cells` so that the minimum y and x are 0, and sorts them.
+    fn normalize(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        let min_y = cells.iter().map(|&(y, _)| y).min().unwrap_or(0);
+        let min_x = cells.iter().map(|&(_, x)| x).min().unwrap_or(0);
+        let mut ret = cells
+            .iter()
+            .map(|&(y, x)| (y - min_y, x - min_x))
+            .collect::<Vec<_>>();
+        ret.sort();
+        ret
+    }
+
+    fn rotate(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        normalize(&cells.iter().map(|&(y, x)| (x, -y)).collect::<Vec<_>>())
+    }
+
+    fn flip(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        normalize(&cells.iter().map(|&(y, x)| (-y, x)).collect::<Vec<_>>())
+    }
+
+    /// Enumerates all distinct placements of `cells` under rotation and reflection.
+    ///
+    /// Each placement is translated so that its minimum y and x are 0 and its cells are sorted in
+    /// lexicographic (y, x) order. The placements are returned in lexicographic order, so the first
+    /// one is the `canonical_form`.
+    pub fn all_variants(cells: &[(i32, i32)]) -> Vec<Vec<(i32, i32)>> {
+        let mut ret = vec![normalize(cells)];
+        for i in 0..3 {
+            ret.push(rotate(&ret[i]));
+        }
+        for i in 0..4 {
+            ret.push(flip(&ret[i]));
+        }
+        ret.sort();
+        ret.dedup();
+        ret
+    }
+
+    /// Returns the representative of `cells` which is shared by all polyominoes congruent to it.
+    /// Two polyominoes are congruent iff their canonical forms are equal.
+    pub fn canonical_form(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        all_variants(cells).swap_remove(0)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::util::tests::TestRng;
+
+        // The implementation previously in `anymino.rs`.
+        fn reference_normalize_block(mut block: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+            fn flip_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
+                let ymax = block.iter().map(|&(y, _)| y).max().unwrap_or(0);
+                let mut ret = block
+                    .iter()
+                    .map(|&(y, x)| (ymax - y, x))
+                    .collect::<Vec<_>>();
+                ret.sort();
+                ret
+            }
+
+            fn rotate_block(block: &[(i32, i32)]) -> Vec<(i32, i32)> {
+                let ymax = block.iter().map(|&(y, _)| y).max().unwrap_or(0);
+                let mut ret = block
+                    .iter()
+                    .map(|&(y, x)| (x, ymax - y))
+                    .collect::<Vec<_>>();
+                ret.sort();
+                ret
+            }
+
+            if block.is_empty() {
+                return vec![];
+            }
+            block = normalize(&block);
+
+            let mut ret = block.clone();
+            for i in 0..4 {
+                ret = ret.min(block.clone());
+                ret = ret.min(flip_block(&block));
+                if i < 3 {
+                    block = rotate_block(&block);
+                }
+            }
+            ret
+        }
+
+        // The implementation previously in `polyominous.rs`, which assumes that the minimum y and x
+        // of `piece` are 0.
+        fn reference_enumerate_variants(piece: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+            fn rotate(piece: &[(usize, usize)]) -> Vec<(usize, usize)> {
+                let h = piece.iter().map(|&(y, _)| y + 1).max().unwrap_or(0);
+                piece.iter().map(|&(y, x)| (x, h - y - 1)).collect()
+            }
+
+            fn flip(piece: &[(usize, usize)]) -> Vec<(usize, usize)> {
+                let h = piece.iter().map(|&(y, _)| y + 1).max().unwrap_or(0);
+                piece.iter().map(|&(y, x)| (h - y - 1, x)).collect()
+            }
+
+            let mut cands = vec![piece.to_owned()];
+            for i in 0..3 {
+                cands.push(rotate(&cands[i]));
+            }
+            for i in 0..4 {
+                cands.push(flip(&cands[i]));
+            }
+            for cand in &mut cands {
+                cand.sort();
+            }
+            cands.sort();
+            cands.dedup();
+            cands
+        }
+
+        fn random_block(rng: &mut TestRng) -> Vec<(i32, i32)> {
+            let mut block = vec![];
+            let (oy, ox) = (rng.next_in(-3, 3), rng.next_in(-3, 3));
+            for y in 0..4 {
+                for x in 0..4 {
+                    if rng.next(3) == 0 {
+                        block.push((y + oy, x + ox));
+                    }
+                }
+            }
+            if block.is_empty() {
+                block.push((oy, ox));
+            }
+            block
+        }
+
+        #[test]
+        fn test_canonical_form() {
+            // L tetromino in two different orientations and positions
+            let a = canonical_form(&[(0, 0), (1, 0), (2, 0), (2, 1)]);
+            let b = canonical_form(&[(5, 3), (5, 4), (5, 5), (6, 3)]);
+            assert_eq!(a, b);
+            assert_eq!(a, vec![(0, 0), (0, 1), (0, 2), (1, 0)]);
+
+            // S and O tetrominoes
+            assert_ne!(
+                canonical_form(&[(0, 0), (0, 1), (1, 1), (1, 2)]),
+                canonical_form(&[(0, 0), (0, 1), (1, 0), (1, 1)])
+            );
+        }
+
+        #[test]
+        fn test_all_variants() {
+            assert_eq!(all_variants(&[(3, 3)]), vec![vec![(0, 0)]]);
+            assert_eq!(all_variants(&[(0, 0), (0, 1), (1, 0), (1, 1)]).len(), 1);
+            assert_eq!(all_variants(&[(0, 0), (0, 1), (0, 2), (1, 1)]).len(), 4);
+            assert_eq!(all_variants(&[(0, 0), (0, 1), (1, 1), (1, 2)]).len(), 4);
+            assert_eq!(all_variants(&[(0, 0), (0, 1), (0, 2), (1, 0)]).len(), 8);
+        }
+
+        #[test]
+        fn test_canonical_form_matches_reference() {
+            let mut rng = TestRng::new(42);
+            for _ in 0..500 {
+                let block = random_block(&mut rng);
+                assert_eq!(
+                    canonical_form(&block),
+                    reference_normalize_block(block.clone())
+                );
+            }
+        }
+
+        #[test]
+        fn test_all_variants_matches_reference() {
+            let mut rng = TestRng::new(7);
+            for _ in 0..500 {
+                let block = normalize(&random_block(&mut rng));
+                let piece = block
+                    .iter()
+                    .map(|&(y, x)| (y as usize, x as usize))
+                    .collect::<Vec<_>>();
+                let expected = reference_enumerate_variants(&piece)
+                    .into_iter()
+                    .map(|v| {
+                        v.into_iter()
+                            .map(|(y, x)| (y as i32, x as i32))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(all_variants(&block), expected);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use cspuz_core::custom_constraints::SimpleCustomConstraint;
+    use cspuz_rs::serializer::{Combinator, Context};
 
     pub fn to_option_2d<X, Y, T>(array: X) -> Vec<Vec<Option<T>>>
     where
@@ -140,6 +461,67 @@ pub mod tests {
         assert_eq!(reserialized, url);
     }
 
+    /// A deterministic pseudo-random number generator for property tests.
+    pub struct TestRng {
+        state: u64,
+    }
+
+    impl TestRng {
+        pub fn new(seed: u64) -> TestRng {
+            // the state must be odd so that it never becomes 0
+            TestRng { state: seed | 1 }
+        }
+
+        /// Returns a number in `0..max`.
+        pub fn next(&mut self, max: usize) -> usize {
+            self.state = self.state.wrapping_mul(0x123456789);
+            ((self.state >> 16) % (max as u64)) as usize
+        }
+
+        /// Returns a number in `low..=high`.
+        pub fn next_in(&mut self, low: i32, high: i32) -> i32 {
+            low + self.next((high - low + 1) as usize) as i32
+        }
+    }
+
+    /// Checks that `combinator` deserializes the serialization of a problem back to the same problem,
+    /// for `iterations` problems generated by `gen_problem` with a fixed seed.
+    ///
+    /// `gen_problem` returns the context used for serialization (e.g. `Context::sized(h, w)` for
+    /// combinators wrapped in `Size`) along with the problem. Deserialization always starts from an
+    /// empty context, as `url_to_problem` does.
+    pub fn roundtrip_property<T, C, G>(combinator: C, mut gen_problem: G, iterations: usize)
+    where
+        T: PartialEq + std::fmt::Debug,
+        C: Combinator<T>,
+        G: FnMut(&mut TestRng) -> (Context, T),
+    {
+        let mut rng = TestRng::new(0x123456789abcdef);
+        for _ in 0..iterations {
+            let (ctx, problem) = gen_problem(&mut rng);
+            let (_, body) = combinator
+                .serialize(&ctx, std::slice::from_ref(&problem))
+                .unwrap_or_else(|| panic!("failed to serialize {:?}", problem));
+            let (n_read, deserialized) = combinator
+                .deserialize(&Context::new(), &body)
+                .unwrap_or_else(|| {
+                    panic!("failed to deserialize {:?}", String::from_utf8_lossy(&body))
+                });
+            assert_eq!(
+                n_read,
+                body.len(),
+                "trailing data in {:?}",
+                String::from_utf8_lossy(&body)
+            );
+            assert_eq!(
+                deserialized,
+                vec![problem],
+                "round trip failed for {:?}",
+                String::from_utf8_lossy(&body)
+            );
+        }
+    }
+
     pub struct ReasonVerifier<T: SimpleCustomConstraint> {
         constraint: T,
         cloned_constraint: T,
@@ -195,4 +577,248 @@ pub mod tests {
             reason
         }
     }
+
+    #[test]
+    fn test_adjacent_rooms_value_constraints() {
+        use super::{add_adjacent_rooms_value_constraints, AdjacentRoomsRule};
+        use cspuz_rs::solver::Solver;
+
+        // Two single-cell rooms side by side, each taking a value in 0..=1
+        let count_answers = |rule: AdjacentRoomsRule, active: bool| {
+            let mut solver = Solver::new();
+            let is_active = &solver.bool_var_2d((1, 2));
+            solver.add_expr(is_active.iff(active));
+            let values = &solver.int_var_1d(2, 0, 1);
+            solver.add_answer_key_int(values);
+            add_adjacent_rooms_value_constraints(
+                &mut solver,
+                &[vec![(0, 0)], vec![(0, 1)]],
+                &[vec![0, 1]],
+                is_active,
+                values,
+                rule,
+            );
+            solver.answer_iter().count()
+        };
+
+        assert_eq!(count_answers(AdjacentRoomsRule::Share, true), 2);
+        assert_eq!(count_answers(AdjacentRoomsRule::Differ, true), 2);
+        assert_eq!(count_answers(AdjacentRoomsRule::Differ, false), 4);
+    }
+
+    /// Real puzz.link URLs paired with the expected solutions, checked all at once by
+    /// `run_all_fixtures` to catch serializer / solver regressions across puzzles.
+    pub mod fixtures {
+        use crate::puzzles::{easyasabc, kurarin, polyominous, shugaku};
+        use cspuz_rs::graph;
+
+        pub struct Fixture {
+            pub kind: &'static str,
+            pub url: &'static str,
+            /// The irrefutable facts of the solution rendered by `solve_and_render`.
+            pub expected: &'static [&'static str],
+        }
+
+        pub const FIXTURES: &[Fixture] = &[
+            Fixture {
+                kind: "pentominous",
+                url: "https://puzz.link/p?pentominous/5/5/72zi",
+                expected: &[
+                    ".|. . . .",
+                    " + +-+-+-",
+                    ".|.|. . .",
+                    " +-+-+ +-",
+                    ". . .|.|.",
+                    "-+-+-+ + ",
+                    ". . .|.|.",
+                    " + +-+-+ ",
+                    ". .|. . .",
+                ],
+            },
+            Fixture {
+                kind: "tetrominous",
+                url: "https://puzz.link/p?tetrominous/4/4/n3m",
+                expected: &[
+                    ". . .|.", " +-+-+ ", ".|. .|.", "-+ +-+ ", ". .|.|.", "-+-+ + ", ". . .|.",
+                ],
+            },
+            Fixture {
+                kind: "shugaku",
+                url: "https://puzz.link/p?shugaku/4/4/a03e",
+                expected: &[".eEs", ".##S", ".eE.", "...."],
+            },
+            Fixture {
+                kind: "kurarin",
+                url: "https://puzz.link/p?kurarin/3/3/n2hcg",
+                expected: &[".-.-.", "|+ +|", ". # .", "|+ +|", ".-.-."],
+            },
+            Fixture {
+                kind: "easyasabc",
+                url: "https://puzz.link/p?easyasabc/4/4/3/h3i1i21h1g",
+                expected: &[".ACB", "C.BA", "BCA.", "AB.C"],
+            },
+        ];
+
+        /// Renders cells and the edges between them into a (2H - 1) x (2W - 1) text grid.
+        ///
+        /// `right(y, x)` is the edge between (y, x) and (y, x + 1), drawn as `right_char`, and
+        /// `down(y, x)` is the edge between (y, x) and (y + 1, x), drawn as `down_char`.
+        /// Absent edges are drawn as ` ` and undecided ones as `?`.
+        fn render_with_edges<C, R, D>(
+            (height, width): (usize, usize),
+            cell: C,
+            (right, right_char): (R, char),
+            (down, down_char): (D, char),
+        ) -> Vec<String>
+        where
+            C: Fn(usize, usize) -> char,
+            R: Fn(usize, usize) -> Option<bool>,
+            D: Fn(usize, usize) -> Option<bool>,
+        {
+            let edge = |e: Option<bool>, c: char| match e {
+                Some(true) => c,
+                Some(false) => ' ',
+                None => '?',
+            };
+            (0..(height * 2 - 1))
+                .map(|y| {
+                    (0..(width * 2 - 1))
+                        .map(|x| match (y % 2, x % 2) {
+                            (0, 0) => cell(y / 2, x / 2),
+                            (0, _) => edge(right(y / 2, x / 2), right_char),
+                            (_, 0) => edge(down(y / 2, x / 2), down_char),
+                            _ => '+',
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+
+        fn render_borders(borders: &graph::BoolInnerGridEdgesIrrefutableFacts) -> Vec<String> {
+            let height = borders.vertical.len();
+            let width = borders.horizontal[0].len();
+            render_with_edges(
+                (height, width),
+                |_, _| '.',
+                (|y, x| borders.vertical[y][x], '|'),
+                (|y, x| borders.horizontal[y][x], '-'),
+            )
+        }
+
+        fn render_kurarin(
+            is_line: &graph::BoolGridEdgesIrrefutableFacts,
+            is_black: &[Vec<Option<bool>>],
+        ) -> Vec<String> {
+            let height = is_black.len();
+            let width = is_black[0].len();
+            render_with_edges(
+                (height, width),
+                |y, x| match is_black[y][x] {
+                    Some(true) => '#',
+                    Some(false) => '.',
+                    None => '?',
+                },
+                (|y, x| is_line.horizontal[y][x], '-'),
+                (|y, x| is_line.vertical[y][x], '|'),
+            )
+        }
+
+        fn render_shugaku(
+            kind: &[Vec<Option<shugaku::ShugakuKind>>],
+            direction: &[Vec<Option<shugaku::ShugakuDirection>>],
+        ) -> Vec<String> {
+            use shugaku::{ShugakuDirection as D, ShugakuKind as K};
+
+            kind.iter()
+                .zip(direction)
+                .map(|(kind_row, direction_row)| {
+                    kind_row
+                        .iter()
+                        .zip(direction_row)
+                        .map(|(k, d)| match (k, d) {
+                            (Some(K::Pillar), _) => '#',
+                            (Some(K::Aisle), _) => '.',
+                            (Some(K::Pillow), Some(D::West)) => 'W',
+                            (Some(K::Pillow), Some(D::East)) => 'E',
+                            (Some(K::Pillow), Some(D::South)) => 'S',
+                            (Some(K::Futon), Some(D::West)) => 'w',
+                            (Some(K::Futon), Some(D::East)) => 'e',
+                            (Some(K::Futon), Some(D::South)) => 's',
+                            _ => '?',
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+
+        fn render_letters(letters: &[Vec<Option<i32>>]) -> Vec<String> {
+            letters
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|v| match v {
+                            Some(0) => '.',
+                            Some(n) => (b'A' + (*n - 1) as u8) as char,
+                            None => '?',
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+
+        /// Deserializes `url` as a puzzle of `kind`, solves it and renders the irrefutable facts.
+        ///
+        /// Returns `None` if the URL is invalid or the puzzle has no solution.
+        pub fn solve_and_render(kind: &str, url: &str) -> Option<Vec<String>> {
+            match kind {
+                "pentominous" => {
+                    let (clues, borders) = polyominous::deserialize_pentominous_problem(url)?;
+                    let ans = polyominous::solve_pentominous(&clues, &borders)?;
+                    Some(render_borders(&ans))
+                }
+                "tetrominous" => {
+                    let (clues, borders) = polyominous::deserialize_tetrominous_problem(url)?;
+                    let ans = polyominous::solve_tetrominous(&clues, &borders)?;
+                    Some(render_borders(&ans))
+                }
+                "shugaku" => {
+                    let problem = shugaku::deserialize_problem(url)?;
+                    let (kind, direction) = shugaku::solve_shugaku(&problem)?;
+                    Some(render_shugaku(&kind, &direction))
+                }
+                "kurarin" => {
+                    let problem = kurarin::deserialize_problem(url)?;
+                    let (is_line, is_black) = kurarin::solve_kurarin(&problem, None)?;
+                    Some(render_kurarin(&is_line, &is_black))
+                }
+                "easyasabc" => {
+                    let (key_size, key_up, key_right, key_down, key_left, center) =
+                        easyasabc::deserialize_problem(url)?;
+                    let ans = easyasabc::solve_easyasabc(
+                        key_size, &key_up, &key_right, &key_down, &key_left, &center,
+                    )?;
+                    Some(render_letters(&ans))
+                }
+                _ => panic!("unknown fixture kind: {}", kind),
+            }
+        }
+
+        #[test]
+        fn run_all_fixtures() {
+            for fixture in FIXTURES {
+                let expected = fixture
+                    .expected
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    solve_and_render(fixture.kind, fixture.url),
+                    Some(expected),
+                    "fixture {} ({})",
+                    fixture.kind,
+                    fixture.url
+                );
+            }
+        }
+    }
 }
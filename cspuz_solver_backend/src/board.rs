@@ -1,7 +1,10 @@
 use crate::uniqueness::Uniqueness;
 use cspuz_rs::graph;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Compass {
     pub up: Option<i32>,
     pub down: Option<i32>,
@@ -9,7 +12,7 @@ pub struct Compass {
     pub right: Option<i32>,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum FireflyDir {
     Up,
     Down,
@@ -18,7 +21,7 @@ pub enum FireflyDir {
 }
 
 #[allow(unused)]
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ItemKind {
     Dot,
     Block,
@@ -70,8 +73,12 @@ pub enum ItemKind {
     Backslash,
     DottedSlash,
     DottedBackslash,
+    /// Line segment between the centers of diagonally adjacent cells, going from lower left to upper right.
+    DiagonalLineUp,
+    /// Line segment between the centers of diagonally adjacent cells, going from upper left to lower right.
+    DiagonalLineDown,
     Plus,
-    Text(&'static str),
+    Text(String),
     Num(i32),
     NumUpperLeft(i32),
     NumUpperRight(i32),
@@ -85,182 +92,386 @@ pub enum ItemKind {
 }
 
 impl ItemKind {
+    /// Serializes the item kind for the frontend (see the `Serialize` implementation for the schema).
     pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+fn serialize_text<S: Serializer>(
+    serializer: S,
+    data: &str,
+    pos: Option<&'static str>,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(None)?;
+    map.serialize_entry("kind", "text")?;
+    map.serialize_entry("data", data)?;
+    if let Some(pos) = pos {
+        map.serialize_entry("pos", pos)?;
+    }
+    map.end()
+}
+
+/// Glyph-only kinds (including the Shugaku glyphs) are emitted as a camelCase string such as
+/// `"shugakuPillow"`. Kinds carrying data are emitted as an object whose `"kind"` field
+/// names the variant; numbers are emitted as `"text"` objects with an optional `"pos"`.
+impl Serialize for ItemKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
-            &ItemKind::Dot => String::from("\"dot\""),
-            &ItemKind::Block => String::from("\"block\""),
-            &ItemKind::Square => String::from("\"square\""),
-            &ItemKind::Triangle => String::from("\"triangle\""),
-            &ItemKind::Fill => String::from("\"fill\""),
-            &ItemKind::Circle => String::from("\"circle\""),
-            &ItemKind::FilledCircle => String::from("\"filledCircle\""),
-            &ItemKind::SmallCircle => String::from("\"smallCircle\""),
-            &ItemKind::SmallFilledCircle => String::from("\"smallFilledCircle\""),
-            &ItemKind::SideArrowUp => String::from("\"sideArrowUp\""),
-            &ItemKind::SideArrowDown => String::from("\"sideArrowDown\""),
-            &ItemKind::SideArrowLeft => String::from("\"sideArrowLeft\""),
-            &ItemKind::SideArrowRight => String::from("\"sideArrowRight\""),
-            &ItemKind::ArrowUp => String::from("\"arrowUp\""),
-            &ItemKind::ArrowDown => String::from("\"arrowDown\""),
-            &ItemKind::ArrowLeft => String::from("\"arrowLeft\""),
-            &ItemKind::ArrowRight => String::from("\"arrowRight\""),
-            &ItemKind::AboloUpperLeft => String::from("\"aboloUpperLeft\""),
-            &ItemKind::AboloUpperRight => String::from("\"aboloUpperRight\""),
-            &ItemKind::AboloLowerLeft => String::from("\"aboloLowerLeft\""),
-            &ItemKind::AboloLowerRight => String::from("\"aboloLowerRight\""),
-            &ItemKind::PencilUp => String::from("\"pencilUp\""),
-            &ItemKind::PencilDown => String::from("\"pencilDown\""),
-            &ItemKind::PencilLeft => String::from("\"pencilLeft\""),
-            &ItemKind::PencilRight => String::from("\"pencilRight\""),
-            &ItemKind::ShugakuPillow => String::from("\"shugakuPillow\""),
-            &ItemKind::ShugakuFuton => String::from("\"shugakuFuton\""),
-            &ItemKind::ShugakuWest => String::from("\"shugakuWest\""),
-            &ItemKind::ShugakuEast => String::from("\"shugakuEast\""),
-            &ItemKind::ShugakuSouth => String::from("\"shugakuSouth\""),
-            &ItemKind::Cross => String::from("\"cross\""),
-            &ItemKind::Line => String::from("\"line\""),
-            &ItemKind::DottedLine => String::from("\"dottedLine\""),
-            &ItemKind::DoubleLine => String::from("\"doubleLine\""),
-            &ItemKind::Wall => String::from("\"wall\""),
-            &ItemKind::BoldWall => String::from("\"boldWall\""),
-            &ItemKind::DottedWall => String::from("\"dottedWall\""),
-            &ItemKind::Slash => String::from("\"slash\""),
-            &ItemKind::Backslash => String::from("\"backslash\""),
-            &ItemKind::DottedSlash => String::from("\"dottedSlash\""),
-            &ItemKind::DottedBackslash => String::from("\"dottedBackslash\""),
-            &ItemKind::Plus => String::from("\"plus\""),
-            &ItemKind::DottedHorizontalWall => String::from("\"dottedHorizontalWall\""),
-            &ItemKind::DottedVerticalWall => String::from("\"dottedVerticalWall\""),
-            &ItemKind::FirewalkCellUnknown => String::from("\"firewalkCellUnknown\""),
-            &ItemKind::FirewalkCellUl => String::from("\"firewalkCellUl\""),
-            &ItemKind::FirewalkCellUr => String::from("\"firewalkCellUr\""),
-            &ItemKind::FirewalkCellDl => String::from("\"firewalkCellDl\""),
-            &ItemKind::FirewalkCellDr => String::from("\"firewalkCellDr\""),
-            &ItemKind::FirewalkCellUlDr => String::from("\"firewalkCellUlDr\""),
-            &ItemKind::FirewalkCellUrDl => String::from("\"firewalkCellUrDl\""),
-            &ItemKind::Text(text) => format!("{{\"kind\":\"text\",\"data\":\"{}\"}}", text),
-            &ItemKind::Num(num) => format!("{{\"kind\":\"text\",\"data\":\"{}\"}}", num),
-            &ItemKind::NumUpperLeft(num) => format!(
-                "{{\"kind\":\"text\",\"data\":\"{}\",\"pos\":\"upperLeft\"}}",
-                num
-            ),
-            &ItemKind::NumUpperRight(num) => format!(
-                "{{\"kind\":\"text\",\"data\":\"{}\",\"pos\":\"upperRight\"}}",
-                num
-            ),
-            &ItemKind::NumLowerLeft(num) => format!(
-                "{{\"kind\":\"text\",\"data\":\"{}\",\"pos\":\"lowerLeft\"}}",
-                num
-            ),
-            &ItemKind::NumLowerRight(num) => format!(
-                "{{\"kind\":\"text\",\"data\":\"{}\",\"pos\":\"lowerRight\"}}",
-                num
-            ),
-            ItemKind::Compass(compass) => format!(
-                "{{\"kind\":\"compass\",\"up\":{},\"down\":{},\"left\":{},\"right\":{}}}",
-                compass.up.unwrap_or(-1),
-                compass.down.unwrap_or(-1),
-                compass.left.unwrap_or(-1),
-                compass.right.unwrap_or(-1)
-            ),
-            ItemKind::TapaClue(clues) => format!(
-                "{{\"kind\":\"tapaClue\",\"value\":[{},{},{},{}]}}",
-                clues[0], clues[1], clues[2], clues[3]
-            ),
-            ItemKind::SudokuCandidateSet(size, cands) => format!(
-                "{{\"kind\":\"sudokuCandidateSet\",\"size\":{},\"values\":[{}]}}",
-                *size,
-                cands
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ),
-            ItemKind::Firefly(dir, n) => format!(
-                "{{\"kind\":\"firefly\",\"dot\":\"{}\",\"value\":{}}}",
-                match *dir {
-                    FireflyDir::Up => "up",
-                    FireflyDir::Down => "down",
-                    FireflyDir::Left => "left",
-                    FireflyDir::Right => "right",
-                },
-                n
-            ),
-            ItemKind::LineTo(dy, dx) => format!(
-                "{{\"kind\":\"lineTo\",\"destY\":{},\"destX\":{}}}",
-                *dy, *dx
-            ),
+            ItemKind::Dot => serializer.serialize_str("dot"),
+            ItemKind::Block => serializer.serialize_str("block"),
+            ItemKind::Square => serializer.serialize_str("square"),
+            ItemKind::Triangle => serializer.serialize_str("triangle"),
+            ItemKind::Fill => serializer.serialize_str("fill"),
+            ItemKind::Circle => serializer.serialize_str("circle"),
+            ItemKind::FilledCircle => serializer.serialize_str("filledCircle"),
+            ItemKind::SmallCircle => serializer.serialize_str("smallCircle"),
+            ItemKind::SmallFilledCircle => serializer.serialize_str("smallFilledCircle"),
+            ItemKind::SideArrowUp => serializer.serialize_str("sideArrowUp"),
+            ItemKind::SideArrowDown => serializer.serialize_str("sideArrowDown"),
+            ItemKind::SideArrowLeft => serializer.serialize_str("sideArrowLeft"),
+            ItemKind::SideArrowRight => serializer.serialize_str("sideArrowRight"),
+            ItemKind::ArrowUp => serializer.serialize_str("arrowUp"),
+            ItemKind::ArrowDown => serializer.serialize_str("arrowDown"),
+            ItemKind::ArrowLeft => serializer.serialize_str("arrowLeft"),
+            ItemKind::ArrowRight => serializer.serialize_str("arrowRight"),
+            ItemKind::AboloUpperLeft => serializer.serialize_str("aboloUpperLeft"),
+            ItemKind::AboloUpperRight => serializer.serialize_str("aboloUpperRight"),
+            ItemKind::AboloLowerLeft => serializer.serialize_str("aboloLowerLeft"),
+            ItemKind::AboloLowerRight => serializer.serialize_str("aboloLowerRight"),
+            ItemKind::PencilUp => serializer.serialize_str("pencilUp"),
+            ItemKind::PencilDown => serializer.serialize_str("pencilDown"),
+            ItemKind::PencilLeft => serializer.serialize_str("pencilLeft"),
+            ItemKind::PencilRight => serializer.serialize_str("pencilRight"),
+            ItemKind::ShugakuPillow => serializer.serialize_str("shugakuPillow"),
+            ItemKind::ShugakuFuton => serializer.serialize_str("shugakuFuton"),
+            ItemKind::ShugakuWest => serializer.serialize_str("shugakuWest"),
+            ItemKind::ShugakuEast => serializer.serialize_str("shugakuEast"),
+            ItemKind::ShugakuSouth => serializer.serialize_str("shugakuSouth"),
+            ItemKind::Cross => serializer.serialize_str("cross"),
+            ItemKind::Line => serializer.serialize_str("line"),
+            ItemKind::DottedLine => serializer.serialize_str("dottedLine"),
+            ItemKind::DoubleLine => serializer.serialize_str("doubleLine"),
+            ItemKind::Wall => serializer.serialize_str("wall"),
+            ItemKind::BoldWall => serializer.serialize_str("boldWall"),
+            ItemKind::DottedWall => serializer.serialize_str("dottedWall"),
+            ItemKind::Slash => serializer.serialize_str("slash"),
+            ItemKind::Backslash => serializer.serialize_str("backslash"),
+            ItemKind::DottedSlash => serializer.serialize_str("dottedSlash"),
+            ItemKind::DottedBackslash => serializer.serialize_str("dottedBackslash"),
+            ItemKind::DiagonalLineUp => serializer.serialize_str("diagonalLineUp"),
+            ItemKind::DiagonalLineDown => serializer.serialize_str("diagonalLineDown"),
+            ItemKind::Plus => serializer.serialize_str("plus"),
+            ItemKind::DottedHorizontalWall => serializer.serialize_str("dottedHorizontalWall"),
+            ItemKind::DottedVerticalWall => serializer.serialize_str("dottedVerticalWall"),
+            ItemKind::FirewalkCellUnknown => serializer.serialize_str("firewalkCellUnknown"),
+            ItemKind::FirewalkCellUl => serializer.serialize_str("firewalkCellUl"),
+            ItemKind::FirewalkCellUr => serializer.serialize_str("firewalkCellUr"),
+            ItemKind::FirewalkCellDl => serializer.serialize_str("firewalkCellDl"),
+            ItemKind::FirewalkCellDr => serializer.serialize_str("firewalkCellDr"),
+            ItemKind::FirewalkCellUlDr => serializer.serialize_str("firewalkCellUlDr"),
+            ItemKind::FirewalkCellUrDl => serializer.serialize_str("firewalkCellUrDl"),
+            ItemKind::Text(text) => serialize_text(serializer, text, None),
+            ItemKind::Num(num) => serialize_text(serializer, &num.to_string(), None),
+            ItemKind::NumUpperLeft(num) => {
+                serialize_text(serializer, &num.to_string(), Some("upperLeft"))
+            }
+            ItemKind::NumUpperRight(num) => {
+                serialize_text(serializer, &num.to_string(), Some("upperRight"))
+            }
+            ItemKind::NumLowerLeft(num) => {
+                serialize_text(serializer, &num.to_string(), Some("lowerLeft"))
+            }
+            ItemKind::NumLowerRight(num) => {
+                serialize_text(serializer, &num.to_string(), Some("lowerRight"))
+            }
+            ItemKind::Compass(compass) => {
+                let mut map = serializer.serialize_map(Some(5))?;
+                map.serialize_entry("kind", "compass")?;
+                map.serialize_entry("up", &compass.up.unwrap_or(-1))?;
+                map.serialize_entry("down", &compass.down.unwrap_or(-1))?;
+                map.serialize_entry("left", &compass.left.unwrap_or(-1))?;
+                map.serialize_entry("right", &compass.right.unwrap_or(-1))?;
+                map.end()
+            }
+            ItemKind::TapaClue(clues) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "tapaClue")?;
+                map.serialize_entry("value", clues)?;
+                map.end()
+            }
+            ItemKind::SudokuCandidateSet(size, cands) => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("kind", "sudokuCandidateSet")?;
+                map.serialize_entry("size", size)?;
+                map.serialize_entry("values", cands)?;
+                map.end()
+            }
+            ItemKind::Firefly(dir, n) => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("kind", "firefly")?;
+                map.serialize_entry(
+                    "dot",
+                    match *dir {
+                        FireflyDir::Up => "up",
+                        FireflyDir::Down => "down",
+                        FireflyDir::Left => "left",
+                        FireflyDir::Right => "right",
+                    },
+                )?;
+                map.serialize_entry("value", n)?;
+                map.end()
+            }
+            ItemKind::LineTo(dy, dx) => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("kind", "lineTo")?;
+                map.serialize_entry("destY", dy)?;
+                map.serialize_entry("destX", dx)?;
+                map.end()
+            }
+        }
+    }
+}
+
+/// Colors used to render the solver output.
+///
+/// Only the puzzles solved by [`solve_url_with_palette`](crate::solve_url_with_palette) take a palette;
+/// the other backends always draw with the colors of `Palette::default()`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Palette {
+    /// Color of deduced items other than lines (filled cells, letters, etc.).
+    pub answer: Cow<'static, str>,
+    /// Color of deduced lines.
+    pub line: Cow<'static, str>,
+    /// Color of the clues given in the problem.
+    pub clue: Cow<'static, str>,
+    /// Color of the clues drawn apart from the other ones (e.g. gray circles in Kurarin).
+    pub secondary_clue: Cow<'static, str>,
+    /// Color of cells shaded in the problem (e.g. water cells).
+    pub shading: Cow<'static, str>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            answer: Cow::Borrowed("green"),
+            line: Cow::Borrowed("green"),
+            clue: Cow::Borrowed("black"),
+            secondary_clue: Cow::Borrowed("gray"),
+            shading: Cow::Borrowed("#e0e0ff"),
         }
     }
 }
 
+#[derive(Clone, Serialize)]
 pub struct Item {
     pub y: usize,
     pub x: usize,
-    pub color: &'static str,
+    pub color: Cow<'static, str>,
+    #[serde(rename = "item")]
     pub kind: ItemKind,
 }
 
 impl Item {
-    pub fn cell(cell_y: usize, cell_x: usize, color: &'static str, kind: ItemKind) -> Item {
+    pub fn cell(
+        cell_y: usize,
+        cell_x: usize,
+        color: impl Into<Cow<'static, str>>,
+        kind: ItemKind,
+    ) -> Item {
         Item {
             y: cell_y * 2 + 1,
             x: cell_x * 2 + 1,
-            color,
+            color: color.into(),
             kind,
         }
     }
 
     pub fn to_json(&self) -> String {
-        format!(
-            "{{\"y\":{},\"x\":{},\"color\":\"{}\",\"item\":{}}}",
-            self.y,
-            self.x,
-            self.color,
-            self.kind.to_json()
-        )
+        serde_json::to_string(self).unwrap()
     }
 }
 
 #[allow(unused)]
+#[derive(Clone)]
 pub enum BoardKind {
     Empty,
     Grid,
     OuterGrid,
     DotGrid,
+    /// A grid surrounded by margins for clues outside the grid. Each margin is given as the number of cells.
+    GridWithMargins {
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+    },
 }
 
+#[derive(Clone)]
 pub struct Board {
     kind: BoardKind,
     height: usize,
     width: usize,
     data: Vec<Item>,
+    /// Indices in `data` of the items added by `push_answer`.
+    answers: Vec<usize>,
     uniqueness: Uniqueness,
+    undecided_count: Option<usize>,
 }
 
 impl Board {
-    pub fn new(kind: BoardKind, height: usize, width: usize, uniqueness: Uniqueness) -> Board {
+    pub fn new(
+        kind: BoardKind,
+        height: usize,
+        width: usize,
+        uniqueness: impl Into<Uniqueness>,
+    ) -> Board {
         Board {
             kind,
             height,
             width,
             data: vec![],
-            uniqueness,
+            answers: vec![],
+            uniqueness: uniqueness.into(),
+            undecided_count: None,
         }
     }
 
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns `false` if the puzzle is known to have no solution.
+    pub fn has_solution(&self) -> bool {
+        !matches!(self.uniqueness, Uniqueness::NoSolution)
+    }
+
+    /// Returns the offset of the inner grid from the top-left corner of the board in the doubled coordinate system.
+    fn offset(&self) -> (usize, usize) {
+        match self.kind {
+            BoardKind::GridWithMargins { top, left, .. } => (top * 2, left * 2),
+            _ => (0, 0),
+        }
+    }
+
+    /// Returns the items placed at the center of the cell `(y, x)`, in the order they were pushed.
+    pub fn items_at_cell(&self, y: usize, x: usize) -> impl Iterator<Item = &Item> {
+        let (oy, ox) = self.offset();
+        self.data
+            .iter()
+            .filter(move |item| item.y == y * 2 + 1 + oy && item.x == x * 2 + 1 + ox)
+    }
+
+    /// Attaches the number of cells whose answer is not decided by the irrefutable facts.
+    /// A cell with several answer values (such as the kind and the direction of a Shugaku cell) is
+    /// undecided if any of them is.
+    pub fn set_undecided_count(&mut self, count: usize) {
+        self.undecided_count = Some(count);
+    }
+
+    /// Adds an item. The coordinates of `item` are relative to the inner grid, even if the board has margins.
     pub fn push(&mut self, item: Item) {
-        self.data.push(item);
+        let (oy, ox) = self.offset();
+        self.data.push(Item {
+            y: item.y + oy,
+            x: item.x + ox,
+            ..item
+        });
+    }
+
+    /// Same as `push`, but marks `item` as a part of the answer (rather than a clue or an auxiliary mark),
+    /// so that it can be found by [`Board::answers_at_cell`].
+    pub fn push_answer(&mut self, item: Item) {
+        self.answers.push(self.data.len());
+        self.push(item);
+    }
+
+    /// Returns the items added by `push_answer` at the center of the cell `(y, x)`, in the order they were pushed.
+    pub fn answers_at_cell(&self, y: usize, x: usize) -> impl Iterator<Item = &Item> {
+        let (oy, ox) = self.offset();
+        self.answers
+            .iter()
+            .map(|&i| &self.data[i])
+            .filter(move |item| item.y == y * 2 + 1 + oy && item.x == x * 2 + 1 + ox)
     }
 
     pub fn extend<I>(&mut self, items: I)
     where
         I: IntoIterator<Item = Item>,
     {
-        self.data.extend(items);
+        for item in items {
+            self.push(item);
+        }
     }
 
-    pub fn add_borders(&mut self, borders: &graph::BoolInnerGridEdgesModel, color: &'static str) {
+    /// Adds an item at the center of the cell `(y, x)`, which may be in the margins of the board:
+    /// for example, `y == -1` refers to the row just above the inner grid, and `x == width` to the column just
+    /// to the right of it.
+    ///
+    /// Panics if the cell is outside the board including its margins.
+    pub fn push_margin_cell(
+        &mut self,
+        y: i32,
+        x: i32,
+        color: impl Into<Cow<'static, str>>,
+        kind: ItemKind,
+    ) {
+        let (top, bottom, left, right) = match self.kind {
+            BoardKind::GridWithMargins {
+                top,
+                bottom,
+                left,
+                right,
+            } => (top as i32, bottom as i32, left as i32, right as i32),
+            _ => (0, 0, 0, 0),
+        };
+        assert!(-top <= y && y < self.height as i32 + bottom);
+        assert!(-left <= x && x < self.width as i32 + right);
+        self.data.push(Item {
+            y: ((y + top) * 2 + 1) as usize,
+            x: ((x + left) * 2 + 1) as usize,
+            color: color.into(),
+            kind,
+        });
+    }
+
+    /// Adds an item to each cell of `grid`: `true_kind` for `true` cells and `false_kind` for `false` cells.
+    /// Undecided (`None`) cells, and cells whose kind is `None`, are skipped.
+    pub fn add_bool_grid<T>(
+        &mut self,
+        grid: &[Vec<T>],
+        true_kind: Option<ItemKind>,
+        false_kind: Option<ItemKind>,
+        color: impl Into<Cow<'static, str>>,
+    ) where
+        T: Copy + Into<Option<bool>>,
+    {
+        let color: Cow<'static, str> = color.into();
+        for y in 0..grid.len() {
+            for x in 0..grid[y].len() {
+                let kind = match grid[y][x].into() {
+                    Some(true) => &true_kind,
+                    Some(false) => &false_kind,
+                    None => continue,
+                };
+                if let Some(kind) = kind {
+                    self.push(Item::cell(y, x, color.clone(), kind.clone()));
+                }
+            }
+        }
+    }
+
+    pub fn add_borders(
+        &mut self,
+        borders: &graph::BoolInnerGridEdgesModel,
+        color: impl Into<Cow<'static, str>>,
+    ) {
+        let color: Cow<'static, str> = color.into();
         let height = self.height;
         let width = self.width;
         for y in 0..height {
@@ -269,7 +480,7 @@ impl Board {
                     self.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color,
+                        color: color.clone(),
                         kind: ItemKind::BoldWall,
                     });
                 }
@@ -277,7 +488,7 @@ impl Board {
                     self.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color,
+                        color: color.clone(),
                         kind: ItemKind::BoldWall,
                     });
                 }
@@ -285,12 +496,37 @@ impl Board {
         }
     }
 
+    /// Adds a line segment connecting the centers of the diagonally adjacent cells `cell1` and `cell2`.
+    /// The segment is placed on the grid vertex shared by the two cells.
+    pub fn push_diagonal_line(
+        &mut self,
+        cell1: (usize, usize),
+        cell2: (usize, usize),
+        color: impl Into<Cow<'static, str>>,
+    ) {
+        let (y1, x1) = cell1;
+        let (y2, x2) = cell2;
+        assert!(y1.abs_diff(y2) == 1 && x1.abs_diff(x2) == 1);
+
+        self.push(Item {
+            y: y1.max(y2) * 2,
+            x: x1.max(x2) * 2,
+            color: color.into(),
+            kind: if (y1 < y2) == (x1 < x2) {
+                ItemKind::DiagonalLineDown
+            } else {
+                ItemKind::DiagonalLineUp
+            },
+        });
+    }
+
     pub fn add_lines_irrefutable_facts(
         &mut self,
         lines: &graph::BoolGridEdgesIrrefutableFacts,
-        color: &'static str,
+        color: impl Into<Cow<'static, str>>,
         skip: Option<&Vec<Vec<bool>>>,
     ) {
+        let color: Cow<'static, str> = color.into();
         for y in 0..(self.height - 1) {
             for x in 0..self.width {
                 if let Some(skip) = skip {
@@ -302,7 +538,7 @@ impl Board {
                     self.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color,
+                        color: color.clone(),
                         kind: if b { ItemKind::Line } else { ItemKind::Cross },
                     });
                 }
@@ -319,7 +555,7 @@ impl Board {
                     self.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color,
+                        color: color.clone(),
                         kind: if b { ItemKind::Line } else { ItemKind::Cross },
                     });
                 }
@@ -327,30 +563,270 @@ impl Board {
         }
     }
 
+    /// Adds the decided region borders in `borders`: a bold wall for each border and a cross for each edge
+    /// which is known not to be a border. Undecided edges are skipped.
+    pub fn add_borders_irrefutable_facts(
+        &mut self,
+        borders: &graph::BoolInnerGridEdgesIrrefutableFacts,
+        color: impl Into<Cow<'static, str>>,
+    ) {
+        let color: Cow<'static, str> = color.into();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if y < self.height - 1 {
+                    if let Some(b) = borders.horizontal[y][x] {
+                        self.push(Item {
+                            y: y * 2 + 2,
+                            x: x * 2 + 1,
+                            color: color.clone(),
+                            kind: if b {
+                                ItemKind::BoldWall
+                            } else {
+                                ItemKind::Cross
+                            },
+                        });
+                    }
+                }
+                if x < self.width - 1 {
+                    if let Some(b) = borders.vertical[y][x] {
+                        self.push(Item {
+                            y: y * 2 + 1,
+                            x: x * 2 + 2,
+                            color: color.clone(),
+                            kind: if b {
+                                ItemKind::BoldWall
+                            } else {
+                                ItemKind::Cross
+                            },
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Serializes the board as
+    /// `{"kind":"grid","height":..,"width":..,"defaultStyle":..,"data":[..],"isUnique":..}`.
+    ///
+    /// Each entry of `data` is `{"y":..,"x":..,"color":..,"item":..}`, where `y` and `x` are in
+    /// the doubled coordinate system (cell centers are odd) and `item` is [`ItemKind::to_json`].
+    /// For [`BoardKind::GridWithMargins`], `height` and `width` include the margins, the coordinates are
+    /// measured from the top-left corner of the margins, and `"margin":{"top":..,"bottom":..,"left":..,"right":..}`
+    /// is added after `defaultStyle`.
+    /// `isUnique` is omitted when uniqueness is not applicable, and `"hasSolution":false` is added when
+    /// the puzzle is known to have no solution. `"undecidedCount"` is added only when it has been set
+    /// by [`Board::set_undecided_count`].
     pub fn to_json(&self) -> String {
-        let kind = "grid";
-        let height = self.height;
-        let width = self.width;
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[derive(Serialize)]
+struct Margin {
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut height = self.height;
+        let mut width = self.width;
+        let mut margin = None;
         let default_style = match self.kind {
             BoardKind::Empty => "empty",
             BoardKind::Grid => "grid",
             BoardKind::OuterGrid => "outer_grid",
             BoardKind::DotGrid => "dots",
+            BoardKind::GridWithMargins {
+                top,
+                bottom,
+                left,
+                right,
+            } => {
+                height += top + bottom;
+                width += left + right;
+                margin = Some(Margin {
+                    top,
+                    bottom,
+                    left,
+                    right,
+                });
+                "grid"
+            }
         };
-        let data = self
-            .data
-            .iter()
-            .map(|item| item.to_json())
-            .collect::<Vec<_>>()
-            .join(",");
-        let uniqueness = match self.uniqueness {
-            Uniqueness::Unique => ",\"isUnique\":true",
-            Uniqueness::NonUnique => ",\"isUnique\":false",
-            Uniqueness::NotApplicable => "",
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", "grid")?;
+        map.serialize_entry("height", &height)?;
+        map.serialize_entry("width", &width)?;
+        map.serialize_entry("defaultStyle", default_style)?;
+        if let Some(margin) = &margin {
+            map.serialize_entry("margin", margin)?;
+        }
+        map.serialize_entry("data", &self.data)?;
+        match self.uniqueness {
+            Uniqueness::Unique => map.serialize_entry("isUnique", &true)?,
+            Uniqueness::NonUnique => map.serialize_entry("isUnique", &false)?,
+            Uniqueness::NoSolution => {
+                map.serialize_entry("isUnique", &false)?;
+                map.serialize_entry("hasSolution", &false)?;
+            }
+            Uniqueness::NotApplicable => (),
+        }
+        if let Some(n) = self.undecided_count {
+            map.serialize_entry("undecidedCount", &n)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cspuz_rs_puzzles::puzzles::easyasabc;
+
+    #[test]
+    fn test_board_to_json_easyasabc() {
+        let url = easyasabc::serialize_problem(&(
+            2,
+            vec![Some(1), None],
+            vec![None, None],
+            vec![None, None],
+            vec![None, None],
+            vec![vec![None, None], vec![None, None]],
+        ))
+        .unwrap();
+        let board = crate::puzzle::easyasabc::solve(&url).unwrap();
+
+        assert_eq!(
+            board.to_json(),
+            concat!(
+                "{\"kind\":\"grid\",\"height\":4,\"width\":4,\"defaultStyle\":\"grid\",",
+                "\"margin\":{\"top\":1,\"bottom\":1,\"left\":1,\"right\":1},\"data\":[",
+                "{\"y\":1,\"x\":3,\"color\":\"black\",\"item\":{\"kind\":\"text\",\"data\":\"A\"}},",
+                "{\"y\":3,\"x\":3,\"color\":\"green\",\"item\":{\"kind\":\"text\",\"data\":\"A\"}},",
+                "{\"y\":3,\"x\":5,\"color\":\"green\",\"item\":{\"kind\":\"text\",\"data\":\"B\"}},",
+                "{\"y\":5,\"x\":3,\"color\":\"green\",\"item\":{\"kind\":\"text\",\"data\":\"B\"}},",
+                "{\"y\":5,\"x\":5,\"color\":\"green\",\"item\":{\"kind\":\"text\",\"data\":\"A\"}}",
+                "],\"isUnique\":true}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_item_kind_to_json_glyphs() {
+        use super::ItemKind;
+
+        assert_eq!(ItemKind::ShugakuPillow.to_json(), "\"shugakuPillow\"");
+        assert_eq!(ItemKind::ShugakuFuton.to_json(), "\"shugakuFuton\"");
+        assert_eq!(
+            ItemKind::Num(10).to_json(),
+            "{\"kind\":\"text\",\"data\":\"10\"}"
+        );
+        assert_eq!(
+            ItemKind::Text(10.to_string()).to_json(),
+            ItemKind::Num(10).to_json()
+        );
+        assert_eq!(
+            ItemKind::Text("AB".to_string()).to_json(),
+            "{\"kind\":\"text\",\"data\":\"AB\"}"
+        );
+        assert_eq!(
+            ItemKind::NumUpperLeft(3).to_json(),
+            "{\"kind\":\"text\",\"data\":\"3\",\"pos\":\"upperLeft\"}"
+        );
+        assert_eq!(
+            ItemKind::Text("\"".to_string()).to_json(),
+            "{\"kind\":\"text\",\"data\":\"\\\"\"}"
+        );
+    }
+
+    #[test]
+    fn test_add_bool_grid() {
+        use super::{Board, BoardKind, ItemKind};
+        use crate::uniqueness::Uniqueness;
+
+        let grid = vec![
+            vec![Some(true), None, Some(false)],
+            vec![Some(false), Some(true), None],
+        ];
+
+        let mut board = Board::new(BoardKind::Grid, 2, 3, Uniqueness::NotApplicable);
+        board.add_bool_grid(&grid, Some(ItemKind::Block), Some(ItemKind::Dot), "green");
+        assert_eq!(board.data.len(), 4);
+        assert_eq!(
+            board
+                .data
+                .iter()
+                .filter(|item| item.kind == ItemKind::Block)
+                .count(),
+            2
+        );
+
+        let mut board = Board::new(BoardKind::Grid, 2, 3, Uniqueness::NotApplicable);
+        board.add_bool_grid(&grid, Some(ItemKind::Fill), None, "green");
+        assert_eq!(board.data.len(), 2);
+
+        let mut board = Board::new(BoardKind::Grid, 2, 3, Uniqueness::NotApplicable);
+        board.add_bool_grid(
+            &[vec![true, false, true]],
+            Some(ItemKind::Fill),
+            None,
+            "green",
+        );
+        assert_eq!(board.data.len(), 2);
+    }
+
+    #[test]
+    fn test_grid_with_margins() {
+        use super::{Board, BoardKind, Item, ItemKind};
+        use crate::uniqueness::Uniqueness;
+
+        let kind = BoardKind::GridWithMargins {
+            top: 1,
+            bottom: 0,
+            left: 2,
+            right: 1,
         };
-        format!(
-            "{{\"kind\":\"{}\",\"height\":{},\"width\":{},\"defaultStyle\":\"{}\",\"data\":[{}]{}}}",
-            kind, height, width, default_style, data, uniqueness
-        )
+        let mut board = Board::new(kind, 2, 2, Uniqueness::NotApplicable);
+        board.push_margin_cell(-1, 0, "black", ItemKind::Num(1));
+        board.push_margin_cell(1, -2, "black", ItemKind::Num(2));
+        board.push_margin_cell(0, 2, "black", ItemKind::Num(3));
+        board.push(Item::cell(1, 1, "green", ItemKind::Dot));
+
+        assert_eq!(board.items_at_cell(1, 1).count(), 1);
+        assert_eq!(
+            board.to_json(),
+            concat!(
+                "{\"kind\":\"grid\",\"height\":3,\"width\":5,\"defaultStyle\":\"grid\",",
+                "\"margin\":{\"top\":1,\"bottom\":0,\"left\":2,\"right\":1},\"data\":[",
+                "{\"y\":1,\"x\":5,\"color\":\"black\",\"item\":{\"kind\":\"text\",\"data\":\"1\"}},",
+                "{\"y\":5,\"x\":1,\"color\":\"black\",\"item\":{\"kind\":\"text\",\"data\":\"2\"}},",
+                "{\"y\":3,\"x\":9,\"color\":\"black\",\"item\":{\"kind\":\"text\",\"data\":\"3\"}},",
+                "{\"y\":5,\"x\":7,\"color\":\"green\",\"item\":\"dot\"}",
+                "]}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_push_diagonal_line() {
+        use super::{Board, BoardKind};
+        use crate::uniqueness::Uniqueness;
+
+        let mut board = Board::new(BoardKind::Grid, 2, 3, Uniqueness::NotApplicable);
+        board.push_diagonal_line((0, 0), (1, 1), "green");
+        board.push_diagonal_line((0, 2), (1, 1), "green");
+
+        assert_eq!(
+            board.to_json(),
+            concat!(
+                "{\"kind\":\"grid\",\"height\":2,\"width\":3,\"defaultStyle\":\"grid\",\"data\":[",
+                "{\"y\":2,\"x\":2,\"color\":\"green\",\"item\":\"diagonalLineDown\"},",
+                "{\"y\":2,\"x\":4,\"color\":\"green\",\"item\":\"diagonalLineUp\"}",
+                "]}"
+            )
+        );
     }
 }
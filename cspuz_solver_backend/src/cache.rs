@@ -0,0 +1,165 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::board::Board;
+use crate::SolveError;
+use cspuz_rs::solver::Config;
+
+/// LRU cache of solved boards keyed by the problem URL.
+/// Only successful results are stored, so errors such as timeouts are always retried. A board
+/// without a solution is not stored either while a deadline is set, since the solver may have given up.
+pub(crate) struct SolveCache {
+    capacity: usize,
+    // Most recently used entries are at the back.
+    entries: VecDeque<(String, Board)>,
+    num_hits: usize,
+}
+
+impl SolveCache {
+    pub(crate) const fn new() -> SolveCache {
+        SolveCache {
+            capacity: 0,
+            entries: VecDeque::new(),
+            num_hits: 0,
+        }
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub(crate) fn get(&mut self, url: &str) -> Option<Board> {
+        let idx = self.entries.iter().position(|(key, _)| key == url)?;
+        let entry = self.entries.remove(idx).unwrap();
+        let board = entry.1.clone();
+        self.entries.push_back(entry);
+        self.num_hits += 1;
+        Some(board)
+    }
+
+    pub(crate) fn insert(&mut self, url: &str, board: &Board) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(idx) = self.entries.iter().position(|(key, _)| key == url) {
+            self.entries.remove(idx);
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((url.to_string(), board.clone()));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+thread_local! {
+    static SOLVE_CACHE: RefCell<SolveCache> = const { RefCell::new(SolveCache::new()) };
+}
+
+/// Returns the cached board for `url`, or runs `solve` and caches its result if it succeeds.
+pub(crate) fn solve_cached<F>(url: &str, solve: F) -> Result<Board, SolveError>
+where
+    F: FnOnce() -> Result<Board, SolveError>,
+{
+    if let Some(board) = SOLVE_CACHE.with(|cache| cache.borrow_mut().get(url)) {
+        return Ok(board);
+    }
+
+    let res = solve();
+    if let Ok(board) = &res {
+        // An interrupted SAT call is reported as "no solution", which must not outlive the deadline.
+        if board.has_solution() || Config::default().deadline.is_none() {
+            SOLVE_CACHE.with(|cache| cache.borrow_mut().insert(url, board));
+        }
+    }
+    res
+}
+
+/// Sets the maximum number of solved boards kept in the cache. A capacity of 0 (the default)
+/// disables caching.
+pub fn set_cache_capacity(capacity: usize) {
+    SOLVE_CACHE.with(|cache| cache.borrow_mut().set_capacity(capacity));
+}
+
+pub fn cache_capacity() -> usize {
+    SOLVE_CACHE.with(|cache| cache.borrow().capacity)
+}
+
+/// Removes all solved boards from the cache.
+pub fn clear_cache() {
+    SOLVE_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+#[cfg(test)]
+pub(crate) fn num_cache_hits() -> usize {
+    SOLVE_CACHE.with(|cache| cache.borrow().num_hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cspuz_rs_puzzles::puzzles::waterwalk;
+
+    fn waterwalk_url(water_y: usize, water_x: usize) -> String {
+        let mut is_water = vec![vec![false, false], vec![false, false]];
+        is_water[water_y][water_x] = true;
+        waterwalk::serialize_problem(&(is_water, vec![vec![None, None], vec![None, None]])).unwrap()
+    }
+
+    #[test]
+    fn test_solve_url_cache_hit() {
+        set_cache_capacity(4);
+        let url = waterwalk_url(1, 1);
+
+        let hits = num_cache_hits();
+        let first = crate::solve_url(&url).unwrap();
+        assert_eq!(num_cache_hits(), hits);
+        let second = crate::solve_url(&url).unwrap();
+        assert_eq!(num_cache_hits(), hits + 1);
+        assert_eq!(first.to_json(), second.to_json());
+
+        clear_cache();
+        crate::solve_url(&url).unwrap();
+        assert_eq!(num_cache_hits(), hits + 1);
+    }
+
+    #[test]
+    fn test_solve_url_cache_eviction() {
+        set_cache_capacity(1);
+        assert_eq!(cache_capacity(), 1);
+        let url1 = waterwalk_url(1, 1);
+        let url2 = waterwalk_url(0, 0);
+
+        let hits = num_cache_hits();
+        assert!(crate::solve_url(&url1).is_ok());
+        assert!(crate::solve_url(&url2).is_ok());
+        assert!(crate::solve_url(&url1).is_ok());
+        assert_eq!(num_cache_hits(), hits);
+
+        set_cache_capacity(0);
+        assert!(crate::solve_url(&url1).is_ok());
+        assert!(crate::solve_url(&url1).is_ok());
+        assert_eq!(num_cache_hits(), hits);
+    }
+
+    #[test]
+    fn test_solve_url_cache_after_timeout() {
+        set_cache_capacity(4);
+        let url = waterwalk_url(1, 1);
+
+        assert_eq!(
+            crate::solve_with_timeout(&url, std::time::Duration::ZERO).map(|board| board.to_json()),
+            Err(SolveError::Timeout)
+        );
+        let board = crate::solve_url(&url).unwrap();
+        assert!(board.has_solution());
+        clear_cache();
+        assert_eq!(board.to_json(), crate::solve_url(&url).unwrap().to_json());
+    }
+}
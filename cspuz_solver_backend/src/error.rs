@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// The reason why a puzzle could not be solved.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SolveError {
+    /// The URL is not valid UTF-8.
+    InvalidUtf8,
+    /// The puzzle type could not be detected from the URL.
+    PuzzleKindNotDetected,
+    /// The URL could not be decoded into a problem.
+    InvalidUrl,
+    /// The problem has no answer.
+    NoAnswer,
+    /// The solver gave up before finding out whether the problem has an answer.
+    Timeout,
+    /// No solver is registered for the puzzle type of the URL.
+    Unsupported,
+    /// The URL was decoded, but the problem violates an assumption of the solver (e.g. an unsupported board size).
+    /// The field describes the violated assumption and is used as the message.
+    MalformedProblem(&'static str),
+    /// The requested cell is outside the board.
+    CellOutOfRange,
+    /// The answer of the requested cell is not determined.
+    CellUndetermined,
+}
+
+impl SolveError {
+    /// Returns the message reported to the frontend for this error.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SolveError::InvalidUtf8 => "failed to decode URL as UTF-8",
+            SolveError::PuzzleKindNotDetected => "puzzle type not detected",
+            SolveError::InvalidUrl => "invalid url",
+            SolveError::NoAnswer => "no answer",
+            SolveError::Timeout => "timeout",
+            SolveError::Unsupported => "unknown puzzle type",
+            SolveError::MalformedProblem(reason) => reason,
+            SolveError::CellOutOfRange => "cell out of range",
+            SolveError::CellUndetermined => "cell is undetermined",
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<SolveError> for &'static str {
+    fn from(err: SolveError) -> &'static str {
+        err.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_error_message() {
+        assert_eq!(SolveError::InvalidUrl.to_string(), "invalid url");
+        assert_eq!(SolveError::NoAnswer.to_string(), "no answer");
+        assert_eq!(SolveError::Timeout.to_string(), "timeout");
+        assert_eq!(SolveError::Unsupported.to_string(), "unknown puzzle type");
+        assert_eq!(
+            SolveError::InvalidUtf8.to_string(),
+            "failed to decode URL as UTF-8"
+        );
+        assert_eq!(
+            SolveError::PuzzleKindNotDetected.to_string(),
+            "puzzle type not detected"
+        );
+        assert_eq!(
+            SolveError::MalformedProblem("asymmetry room").to_string(),
+            "asymmetry room"
+        );
+
+        let message: &'static str = SolveError::NoAnswer.into();
+        assert_eq!(message, "no answer");
+    }
+}
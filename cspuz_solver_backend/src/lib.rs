@@ -3,20 +3,49 @@
 extern crate cspuz_rs;
 
 pub mod board;
+mod cache;
+mod error;
 mod puzzle;
+mod timeout;
 mod uniqueness;
 
-use board::Board;
-use cspuz_rs::serializer::{get_kudamono_url_info_detailed, url_to_puzzle_kind};
+use std::time::Duration;
+
+use board::{Board, ItemKind, Palette};
+pub use cache::{cache_capacity, clear_cache, set_cache_capacity};
+use cspuz_rs::serializer::{get_kudamono_url_info_detailed, strip_prefix, url_to_puzzle_kind};
+pub use error::SolveError;
 pub use puzzle::{list_puzzles_for_enumerate, list_puzzles_for_solve};
 
 static mut SHARED_ARRAY: Vec<u8> = vec![];
 
-fn decode_and_solve(url: &[u8]) -> Result<Board, &'static str> {
-    let url = std::str::from_utf8(url).map_err(|_| "failed to decode URL as UTF-8")?;
+fn decode_and_solve(url: &[u8]) -> Result<Board, SolveError> {
+    let url = std::str::from_utf8(url).map_err(|_| SolveError::InvalidUtf8)?;
+    cache::solve_cached(url, || solve_uncached(url))
+}
+
+/// Extracts the puzzle kind token from `url`.
+///
+/// For puzz.link-style URLs this is the path component right after `p?` (which may be an alias such as
+/// `"mashu"`); for Kudamono URLs this is the value of the `G` parameter.
+pub fn puzzle_kind(url: &str) -> Option<&str> {
+    if let Some(body) = strip_prefix(url) {
+        let pos = body.find('/')?;
+        return Some(&body[..pos]);
+    }
+
+    get_kudamono_url_info_detailed(url)?.get("G").copied()
+}
+
+/// Returns the puzz.link kind tokens (including aliases such as `"mashu"`) the backend can solve.
+pub fn supported_kinds() -> &'static [&'static str] {
+    puzzle::PUZZ_LINK_KINDS
+}
 
+fn solve_uncached(url: &str) -> Result<Board, SolveError> {
     if let Some(puzzle_kind) = url_to_puzzle_kind(url) {
-        return puzzle::dispatch_puzz_link(&puzzle_kind, url).unwrap_or(Err("unknown puzzle type"));
+        return puzzle::dispatch_puzz_link(&puzzle_kind, url)
+            .unwrap_or(Err(SolveError::Unsupported));
     }
 
     if let Some(puzzle_info) = get_kudamono_url_info_detailed(url) {
@@ -24,22 +53,100 @@ fn decode_and_solve(url: &[u8]) -> Result<Board, &'static str> {
         let puzzle_variant = *puzzle_info.get("V").unwrap_or(&"");
 
         return puzzle::dispatch_kudamono(puzzle_kind, puzzle_variant, url)
-            .unwrap_or(Err("unknown puzzle type"));
+            .unwrap_or(Err(SolveError::Unsupported));
     }
 
-    Err("URL cannot be parsed")
+    Err(SolveError::InvalidUrl)
+}
+
+/// Solves the puzzle given by `url`. Results are served from the cache if one is enabled by
+/// [`set_cache_capacity`].
+pub fn solve_url(url: &str) -> Result<Board, SolveError> {
+    decode_and_solve(url.as_bytes())
+}
+
+/// Solves the puzzle given by `url`, drawing the output with the colors of `palette`.
+///
+/// Only Easy as ABC, Kurarin, Shugaku and Waterwalk are supported; other puzzles fail with
+/// [`SolveError::Unsupported`]. Results are not cached.
+pub fn solve_url_with_palette(url: &str, palette: &Palette) -> Result<Board, SolveError> {
+    let puzzle_kind = url_to_puzzle_kind(url).ok_or(SolveError::PuzzleKindNotDetected)?;
+    puzzle::dispatch_puzz_link_with_palette(&puzzle_kind, url, palette)
+        .unwrap_or(Err(SolveError::Unsupported))
+}
+
+/// Solves the puzzle given by `url`, giving up with [`SolveError::Timeout`] once `timeout` has elapsed.
+pub fn solve_with_timeout(url: &str, timeout: Duration) -> Result<Board, SolveError> {
+    timeout::solve_with_timeout(timeout, || decode_and_solve(url.as_bytes()))
+}
+
+/// Solves each puzzle in `urls`, returning the results in the same order.
+///
+/// Every puzzle is solved by its own solver instance. With the `parallel` feature enabled, the URLs are
+/// split among worker threads; each worker inherits the caller's default solver config but uses its own
+/// cache, so caching only pays off when the same URL appears more than once within a worker's share.
+pub fn solve_many(urls: &[&str]) -> Vec<Result<Board, SolveError>> {
+    #[cfg(feature = "parallel")]
+    {
+        let num_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(urls.len());
+        if num_threads > 1 {
+            let config = cspuz_rs::solver::Config::default();
+            let chunk_size = urls.len().div_ceil(num_threads);
+            return std::thread::scope(|scope| {
+                let workers = urls
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            cspuz_rs::solver::Config::set_default(config);
+                            chunk.iter().map(|url| solve_url(url)).collect::<Vec<_>>()
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().unwrap())
+                    .collect()
+            });
+        }
+    }
+
+    urls.iter().map(|url| solve_url(url)).collect()
+}
+
+/// Reveals the answer for the single cell `(y, x)` of the puzzle given by `url`.
+///
+/// Returns the answer item drawn in the cell (e.g. the letter in Easy as ABC, or the kind glyph in Shugaku).
+/// Only the items a backend marks with [`Board::push_answer`] are answers, so clues are never returned.
+/// If several answer items are drawn in the cell, the first one is returned. Fails if the puzzle has no answer,
+/// the cell is out of the board, or the cell is not determined.
+pub fn hint(url: &str, y: usize, x: usize) -> Result<ItemKind, SolveError> {
+    let board = solve_url(url)?;
+    if !board.has_solution() {
+        return Err(SolveError::NoAnswer);
+    }
+    if y >= board.height() || x >= board.width() {
+        return Err(SolveError::CellOutOfRange);
+    }
+    let item = board
+        .answers_at_cell(y, x)
+        .next()
+        .ok_or(SolveError::CellUndetermined)?;
+    Ok(item.kind.clone())
 }
 
 fn decode_and_enumerate(
     url: &[u8],
     num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let url = std::str::from_utf8(url).map_err(|_| "failed to decode URL as UTF-8")?;
+) -> Result<(Board, Vec<Board>), SolveError> {
+    let url = std::str::from_utf8(url).map_err(|_| SolveError::InvalidUtf8)?;
 
-    let puzzle_kind = url_to_puzzle_kind(url).ok_or("puzzle type not detected")?;
+    let puzzle_kind = url_to_puzzle_kind(url).ok_or(SolveError::PuzzleKindNotDetected)?;
 
     puzzle::dispatch_puzz_link_enumerate(&puzzle_kind, url, num_max_answers)
-        .unwrap_or(Err("unknown puzzle type"))
+        .unwrap_or(Err(SolveError::Unsupported))
 }
 
 #[no_mangle]
@@ -105,3 +212,189 @@ fn enumerate_answers_problem(url: *const u8, len: usize, num_max_answers: usize)
         SHARED_ARRAY.as_ptr()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_kind() {
+        assert_eq!(
+            puzzle_kind("https://puzz.link/p?masyu/2/2/0"),
+            Some("masyu")
+        );
+        assert_eq!(
+            puzzle_kind("http://pzv.jp/p.html?mashu/2/2/0"),
+            Some("mashu")
+        );
+        assert_eq!(
+            puzzle_kind("https://pedros.works/paper-puzzle-player?W=3x3&L=x1&G=seiza"),
+            Some("seiza")
+        );
+        assert_eq!(puzzle_kind("https://puzz.link/p?masyu"), None);
+        assert_eq!(puzzle_kind("https://example.com/p?masyu/2/2/0"), None);
+    }
+
+    #[test]
+    fn test_puzzle_kind_alias_dispatch() {
+        let solve_json = |url: &str| solve_url(url).map(|board| board.to_json());
+
+        let expected =
+            solve_json("https://puzz.link/p?masyu/10/10/0600003i06b1300600000a30600i090330");
+        assert!(expected.is_ok());
+        assert_eq!(
+            solve_json("https://puzz.link/p?mashu/10/10/0600003i06b1300600000a30600i090330"),
+            expected
+        );
+        assert_eq!(
+            solve_json("http://pzv.jp/p.html?mashu/10/10/0600003i06b1300600000a30600i090330"),
+            expected
+        );
+
+        assert_eq!(
+            solve_json(
+                "https://puzz.link/p?unknownpuzzle/10/10/0600003i06b1300600000a30600i090330"
+            ),
+            Err(SolveError::Unsupported)
+        );
+        assert_eq!(
+            solve_json("https://puzz.link/p?masyu"),
+            Err(SolveError::InvalidUrl)
+        );
+    }
+
+    #[test]
+    fn test_solve_url_with_palette() {
+        let palette = Palette {
+            answer: "red".into(),
+            line: "orange".into(),
+            clue: "white".into(),
+            secondary_clue: "blue".into(),
+            shading: "yellow".into(),
+        };
+
+        let url = "https://puzz.link/p?kurarin/3/3/n2hcg";
+        let json = solve_url_with_palette(url, &palette).unwrap().to_json();
+        assert_eq!(
+            json,
+            puzzle::kurarin::solve_with_palette(url, &palette)
+                .unwrap()
+                .to_json()
+        );
+        assert!(!json.contains("\"green\""));
+
+        assert_eq!(
+            solve_url_with_palette(
+                "https://puzz.link/p?masyu/10/10/0600003i06b1300600000a30600i090330",
+                &palette
+            )
+            .map(|board| board.to_json()),
+            Err(SolveError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_supported_kinds() {
+        let kinds = supported_kinds();
+        for kind in [
+            "shugaku",
+            "kurarin",
+            "easyasabc",
+            "waterwalk",
+            "lits",
+            "anymino",
+            "pentominous",
+            "tetrominous",
+            "ringring",
+            "mashu",
+        ] {
+            assert!(kinds.contains(&kind), "{} is not listed", kind);
+        }
+        assert!(!kinds.contains(&"seiza"));
+
+        // every listed kind is dispatched to a solver (the URL itself is invalid)
+        for &kind in kinds {
+            let url = format!("https://puzz.link/p?{}/", kind);
+            assert!(puzzle::dispatch_puzz_link(kind, &url).is_some(), "{}", kind);
+        }
+        assert!(
+            puzzle::dispatch_puzz_link("unknownpuzzle", "https://puzz.link/p?unknownpuzzle/")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_solve_many() {
+        let urls = [
+            "https://puzz.link/p?shugaku/4/4/a03e",
+            "https://puzz.link/p?kurarin/3/3/n2hcg",
+            "https://puzz.link/p?easyasabc/4/4/3/h3i1i21h1g",
+            "https://puzz.link/p?unknownpuzzle/3/3/0",
+            "https://puzz.link/p?shugaku/4/4/a03e",
+        ];
+        let results = solve_many(&urls);
+        assert_eq!(results.len(), urls.len());
+
+        for (url, res) in urls.iter().zip(results.iter()) {
+            match (res, solve_uncached(url)) {
+                (Ok(board), Ok(expected)) => assert_eq!(board.to_json(), expected.to_json()),
+                (Err(err), Err(expected)) => assert_eq!(*err, expected),
+                _ => panic!("unexpected result for {}", url),
+            }
+        }
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[2].is_ok());
+        assert_eq!(results[3].as_ref().err(), Some(&SolveError::Unsupported));
+
+        assert!(solve_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_hint_easyasabc() {
+        use cspuz_rs_puzzles::puzzles::easyasabc;
+
+        let url = easyasabc::serialize_problem(&(
+            2,
+            vec![Some(1), None],
+            vec![None, None],
+            vec![None, None],
+            vec![None, None],
+            vec![vec![None, None], vec![None, None]],
+        ))
+        .unwrap();
+        assert_eq!(hint(&url, 0, 0), Ok(ItemKind::Text("A".to_string())));
+        assert_eq!(hint(&url, 1, 0), Ok(ItemKind::Text("B".to_string())));
+        assert_eq!(hint(&url, 2, 0), Err(SolveError::CellOutOfRange));
+
+        let url = easyasabc::serialize_problem(&(
+            2,
+            vec![None, None],
+            vec![None, None],
+            vec![None, None],
+            vec![None, None],
+            vec![vec![None, None], vec![None, None]],
+        ))
+        .unwrap();
+        assert_eq!(hint(&url, 0, 0), Err(SolveError::CellUndetermined));
+    }
+
+    #[test]
+    fn test_hint_shugaku() {
+        use cspuz_rs_puzzles::puzzles::shugaku;
+
+        let url = shugaku::serialize_problem(&vec![
+            vec![Some(1), None, None, None],
+            vec![Some(2), None, None, None],
+            vec![None, None, None, None],
+        ])
+        .unwrap();
+        assert_eq!(hint(&url, 0, 1), Ok(ItemKind::ShugakuPillow));
+        assert_eq!(hint(&url, 0, 2), Ok(ItemKind::ShugakuFuton));
+        assert_eq!(hint(&url, 2, 3), Ok(ItemKind::Fill));
+        assert_eq!(hint(&url, 0, 0), Err(SolveError::CellUndetermined));
+
+        let url = shugaku::serialize_problem(&vec![vec![Some(4), None], vec![None, None]]).unwrap();
+        assert_eq!(hint(&url, 0, 1), Err(SolveError::NoAnswer));
+    }
+}
@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::akari_regions;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     let (borders, clues, has_block) =
-        akari_regions::deserialize_problem(url).ok_or("invalid url")?;
-    let has_light =
-        akari_regions::solve_akari_region(&borders, &clues, &has_block).ok_or("no answer")?;
+        akari_regions::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let has_light = akari_regions::solve_akari_region(&borders, &clues, &has_block)
+        .ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::akari_rgb::{self, AkariRGBClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = akari_rgb::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = akari_rgb::solve_akari_rgb(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = akari_rgb::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = akari_rgb::solve_akari_rgb(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -49,15 +50,15 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 }
                 Some(1) => {
                     board.push(Item::cell(y, x, "#ff0000", ItemKind::FilledCircle));
-                    board.push(Item::cell(y, x, "white", ItemKind::Text("R")));
+                    board.push(Item::cell(y, x, "white", ItemKind::Text("R".to_string())));
                 }
                 Some(2) => {
                     board.push(Item::cell(y, x, "#00ff00", ItemKind::FilledCircle));
-                    board.push(Item::cell(y, x, "white", ItemKind::Text("G")));
+                    board.push(Item::cell(y, x, "white", ItemKind::Text("G".to_string())));
                 }
                 Some(3) => {
                     board.push(Item::cell(y, x, "#0000ff", ItemKind::FilledCircle));
-                    board.push(Item::cell(y, x, "white", ItemKind::Text("B")));
+                    board.push(Item::cell(y, x, "white", ItemKind::Text("B".to_string())));
                 }
                 _ => unreachable!(),
             }
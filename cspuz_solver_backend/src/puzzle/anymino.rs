@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::anymino;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let borders = anymino::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = anymino::solve_anymino(&borders).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let borders = anymino::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = anymino::solve_anymino(&borders).ok_or(SolveError::NoAnswer)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::araf;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = araf::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = araf::solve_araf(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = araf::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = araf::solve_araf(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -25,7 +26,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -40,7 +41,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -51,7 +52,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -66,7 +67,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
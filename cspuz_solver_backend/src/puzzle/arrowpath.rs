@@ -0,0 +1,39 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::arrowpath;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    use cspuz_rs::items::Arrow;
+
+    let problem = arrowpath::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = arrowpath::solve_arrowpath(&problem).ok_or(SolveError::NoAnswer)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some((dir, n)) = problem[y][x] {
+                let arrow = match dir {
+                    Arrow::Unspecified => None,
+                    Arrow::Up => Some(ItemKind::SideArrowUp),
+                    Arrow::Down => Some(ItemKind::SideArrowDown),
+                    Arrow::Left => Some(ItemKind::SideArrowLeft),
+                    Arrow::Right => Some(ItemKind::SideArrowRight),
+                };
+                if let Some(arrow) = arrow {
+                    board.push(Item::cell(y, x, "black", arrow));
+                }
+                if n >= 0 {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+                }
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
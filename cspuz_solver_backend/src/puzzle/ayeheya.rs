@@ -1,3 +1,3 @@
-pub fn solve(url: &str) -> Result<crate::board::Board, &'static str> {
+pub fn solve(url: &str) -> Result<crate::board::Board, crate::SolveError> {
     crate::puzzle::heyawake_internal::solve(url, true)
 }
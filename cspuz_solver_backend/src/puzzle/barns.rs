@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::barns;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (icebarn, borders) = barns::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = barns::solve_barns(&icebarn, &borders).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (icebarn, borders) = barns::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = barns::solve_barns(&icebarn, &borders).ok_or(SolveError::NoAnswer)?;
 
     let height = icebarn.len();
     let width = icebarn[0].len();
@@ -26,7 +27,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         board.push(Item {
                             y: y * 2 + 2,
                             x: x * 2 + 1,
-                            color: "green",
+                            color: "green".into(),
                             kind: if b { ItemKind::Line } else { ItemKind::Cross },
                         });
                     }
@@ -38,7 +39,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         board.push(Item {
                             y: y * 2 + 1,
                             x: x * 2 + 2,
-                            color: "green",
+                            color: "green".into(),
                             kind: if b { ItemKind::Line } else { ItemKind::Cross },
                         });
                     }
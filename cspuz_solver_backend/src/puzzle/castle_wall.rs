@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::castle_wall;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     use castle_wall::Side;
     use cspuz_rs::items::Arrow;
-    let problem = castle_wall::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = castle_wall::solve_castle_wall(&problem).ok_or("no answer")?;
+    let problem = castle_wall::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = castle_wall::solve_castle_wall(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
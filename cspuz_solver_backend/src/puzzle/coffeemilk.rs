@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::coffeemilk;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let clues = coffeemilk::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = coffeemilk::solve_coffeemilk(&clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let clues = coffeemilk::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = coffeemilk::solve_coffeemilk(&clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Compass, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::compass;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = compass::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = compass::solve_compass(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = compass::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = compass::solve_compass(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -34,7 +35,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -49,7 +50,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -60,7 +61,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -75,7 +76,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
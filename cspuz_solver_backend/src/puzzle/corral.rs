@@ -0,0 +1,51 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::corral;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = corral::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = corral::solve_corral(&problem).ok_or(SolveError::NoAnswer)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    for (y, row) in problem.iter().enumerate() {
+        for (x, &clue) in row.iter().enumerate() {
+            if let Some(clue) = clue {
+                if clue > 0 {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
+                } else {
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?".to_string())));
+                }
+            }
+        }
+    }
+    for y in 0..height {
+        for x in 0..=width {
+            if let Some(b) = is_line.vertical[y][x] {
+                board.push(Item {
+                    y: y * 2 + 1,
+                    x: x * 2,
+                    color: "green".into(),
+                    kind: if b { ItemKind::Wall } else { ItemKind::Cross },
+                })
+            }
+        }
+    }
+    for y in 0..=height {
+        for x in 0..width {
+            if let Some(b) = is_line.horizontal[y][x] {
+                board.push(Item {
+                    y: y * 2,
+                    x: x * 2 + 1,
+                    color: "green".into(),
+                    kind: if b { ItemKind::Wall } else { ItemKind::Cross },
+                })
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::creek;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = creek::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = creek::solve_creek(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = creek::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = creek::solve_creek(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = ans.len();
     let width = ans[0].len();
@@ -27,7 +28,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::Wall,
                 });
             }
@@ -35,7 +36,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::Wall,
                 });
             }
@@ -47,19 +48,19 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2,
-                    color: "white",
+                    color: "white".into(),
                     kind: ItemKind::FilledCircle,
                 });
                 board.push(Item {
                     y: y * 2,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::Circle,
                 });
                 board.push(Item {
                     y: y * 2,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::Num(n),
                 });
             }
@@ -1,17 +1,18 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::cross_border_parity_loop::{self, CBPLCell};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     let (cells, clues_black, clues_white, borders) =
-        cross_border_parity_loop::deserialize_problem(url).ok_or("invalid url")?;
+        cross_border_parity_loop::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
     let (is_line, cell_state) = cross_border_parity_loop::solve_cross_border_parity_loop(
         &cells,
         &clues_black,
         &clues_white,
         &borders,
     )
-    .ok_or("no answer")?;
+    .ok_or(SolveError::NoAnswer)?;
 
     let height = cells.len();
     let width = cells[0].len();
@@ -0,0 +1,109 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::crossthestreams::{self, CrossTheStreamsClue};
+
+fn clue_item(clue: CrossTheStreamsClue) -> ItemKind {
+    match clue {
+        CrossTheStreamsClue::Num(n) => ItemKind::Num(n),
+        CrossTheStreamsClue::Unknown => ItemKind::Text("?".to_string()),
+        CrossTheStreamsClue::Any => ItemKind::Text("*".to_string()),
+    }
+}
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (clues_up, clues_left) =
+        crossthestreams::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = crossthestreams::solve_crossthestreams(&clues_up, &clues_left)
+        .ok_or(SolveError::NoAnswer)?;
+
+    let height = clues_left.len();
+    let width = clues_up.len();
+
+    // Clues are placed outside the grid: up to `offset_y` rows above it and `offset_x` columns to its left.
+    let offset_y = clues_up
+        .iter()
+        .map(|c| c.as_ref().map_or(0, |c| c.len()))
+        .max()
+        .unwrap_or(0);
+    let offset_x = clues_left
+        .iter()
+        .map(|c| c.as_ref().map_or(0, |c| c.len()))
+        .max()
+        .unwrap_or(0);
+    let mut board = Board::new(
+        BoardKind::Empty,
+        height + offset_y,
+        width + offset_x,
+        is_unique(&ans),
+    );
+
+    for x in 0..width {
+        if let Some(clue) = &clues_up[x] {
+            for (i, &c) in clue.iter().enumerate() {
+                board.push(Item::cell(
+                    offset_y - clue.len() + i,
+                    x + offset_x,
+                    "black",
+                    clue_item(c),
+                ));
+            }
+        }
+    }
+    for y in 0..height {
+        if let Some(clue) = &clues_left[y] {
+            for (i, &c) in clue.iter().enumerate() {
+                board.push(Item::cell(
+                    y + offset_y,
+                    offset_x - clue.len() + i,
+                    "black",
+                    clue_item(c),
+                ));
+            }
+        }
+    }
+
+    for y in 0..=height {
+        for x in 0..width {
+            board.push(Item {
+                y: (y + offset_y) * 2,
+                x: (x + offset_x) * 2 + 1,
+                color: "black".into(),
+                kind: if y == 0 || y == height {
+                    ItemKind::BoldWall
+                } else {
+                    ItemKind::Wall
+                },
+            })
+        }
+    }
+    for y in 0..height {
+        for x in 0..=width {
+            board.push(Item {
+                y: (y + offset_y) * 2 + 1,
+                x: (x + offset_x) * 2,
+                color: "black".into(),
+                kind: if x == 0 || x == width {
+                    ItemKind::BoldWall
+                } else {
+                    ItemKind::Wall
+                },
+            })
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(b) = ans[y][x] {
+                board.push(Item::cell(
+                    y + offset_y,
+                    x + offset_x,
+                    "green",
+                    if b { ItemKind::Block } else { ItemKind::Dot },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
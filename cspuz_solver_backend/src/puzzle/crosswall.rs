@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::crosswall;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = crosswall::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = crosswall::solve_crosswall(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = crosswall::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = crosswall::solve_crosswall(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -29,7 +30,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -41,7 +42,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
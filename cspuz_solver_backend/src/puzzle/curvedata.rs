@@ -1,10 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::{is_unique, Uniqueness};
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::curvedata;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (piece_id, borders, pieces) = curvedata::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = curvedata::solve_curvedata(&piece_id, &borders, &pieces).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (piece_id, borders, pieces) =
+        curvedata::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line =
+        curvedata::solve_curvedata(&piece_id, &borders, &pieces).ok_or(SolveError::NoAnswer)?;
 
     let height = piece_id.len();
     let width = piece_id[0].len();
@@ -33,13 +36,11 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
     Ok(board)
 }
 
-pub fn enumerate(
-    url: &str,
-    num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let (piece_id, borders, pieces) = curvedata::deserialize_problem(url).ok_or("invalid url")?;
+pub fn enumerate(url: &str, num_max_answers: usize) -> Result<(Board, Vec<Board>), SolveError> {
+    let (piece_id, borders, pieces) =
+        curvedata::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
     let is_line_common =
-        curvedata::solve_curvedata(&piece_id, &borders, &pieces).ok_or("no answer")?;
+        curvedata::solve_curvedata(&piece_id, &borders, &pieces).ok_or(SolveError::NoAnswer)?;
     let answers =
         curvedata::enumerate_answers_curvedata(&piece_id, &borders, &pieces, num_max_answers);
 
@@ -76,7 +77,7 @@ pub fn enumerate(
                     board_answer.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: if ans.vertical[y][x] {
                             ItemKind::Line
                         } else {
@@ -88,7 +89,7 @@ pub fn enumerate(
                     board_answer.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: if ans.horizontal[y][x] {
                             ItemKind::Line
                         } else {
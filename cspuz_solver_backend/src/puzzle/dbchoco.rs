@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::dbchoco;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (color, num) = dbchoco::deserialize_problem(url).ok_or("invalid url")?;
-    let border = dbchoco::solve_doublechoco(&color, &num).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (color, num) = dbchoco::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let border = dbchoco::solve_doublechoco(&color, &num).ok_or(SolveError::NoAnswer)?;
 
     let height = num.len();
     let width = num[0].len();
@@ -29,7 +30,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -44,7 +45,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -55,7 +56,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -70,7 +71,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -1,12 +1,14 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::Uniqueness;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::doppelblock;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     let (clues_up, clues_left, cells) =
-        doppelblock::deserialize_problem(url).ok_or("invalid url")?;
+        doppelblock::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
     let answer: Vec<Vec<Option<i32>>> =
-        doppelblock::solve_doppelblock(&clues_up, &clues_left, &cells).ok_or("no answer")?;
+        doppelblock::solve_doppelblock(&clues_up, &clues_left, &cells)
+            .ok_or(SolveError::NoAnswer)?;
 
     let height = clues_left.len();
     let width = clues_up.len();
@@ -37,7 +39,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: y * 2 + 2,
                 x: x * 2 + 3,
-                color: "black",
+                color: "black".into(),
                 kind: if y == 0 || y == height {
                     ItemKind::BoldWall
                 } else {
@@ -51,7 +53,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: y * 2 + 3,
                 x: x * 2 + 2,
-                color: "black",
+                color: "black".into(),
                 kind: if x == 0 || x == width {
                     ItemKind::BoldWall
                 } else {
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::double_lits;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let borders = double_lits::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = double_lits::solve_double_lits(&borders).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let borders = double_lits::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = double_lits::solve_double_lits(&borders).ok_or(SolveError::NoAnswer)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
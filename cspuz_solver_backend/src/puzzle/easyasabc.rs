@@ -1,14 +1,61 @@
-use crate::board::{Board, BoardKind, Item, ItemKind};
-use crate::uniqueness::is_unique;
+use crate::board::{Board, BoardKind, Item, ItemKind, Palette};
+use crate::uniqueness::solution_multiplicity;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::easyasabc;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = easyasabc::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = easyasabc::solve_easyasabc(problem.0, &problem.1, &problem.2, &problem.3, &problem.4, &problem.5).ok_or("no answer")?;
+/// Renders the letter `n` (1 for A, 2 for B, ...) as its glyph. Numbers beyond Z are rendered as they are.
+fn letter_item(n: i32) -> ItemKind {
+    if (1..=26).contains(&n) {
+        ItemKind::Text(char::from(b'A' + (n - 1) as u8).to_string())
+    } else {
+        ItemKind::Num(n)
+    }
+}
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    solve_with_palette(url, &Palette::default())
+}
+
+pub fn solve_with_palette(url: &str, palette: &Palette) -> Result<Board, SolveError> {
+    let problem = easyasabc::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = easyasabc::solve_easyasabc(problem.0, &problem.1, &problem.2, &problem.3, &problem.4, &problem.5);
 
     let height = problem.2.len();
     let width = problem.1.len();
-    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&ans));
+    let kind = BoardKind::GridWithMargins {
+        top: 1,
+        bottom: 1,
+        left: 1,
+        right: 1,
+    };
+    let mut board = Board::new(
+        kind,
+        height,
+        width,
+        solution_multiplicity(ans.as_ref()),
+    );
+
+    for x in 0..width {
+        if let Some(&Some(n)) = problem.1.get(x) {
+            board.push_margin_cell(-1, x as i32, palette.clue.clone(), letter_item(n));
+        }
+        if let Some(&Some(n)) = problem.3.get(x) {
+            board.push_margin_cell(
+                height as i32,
+                x as i32,
+                palette.clue.clone(),
+                letter_item(n),
+            );
+        }
+    }
+    for y in 0..height {
+        if let Some(&Some(n)) = problem.4.get(y) {
+            board.push_margin_cell(y as i32, -1, palette.clue.clone(), letter_item(n));
+        }
+        if let Some(&Some(n)) = problem.2.get(y) {
+            board.push_margin_cell(y as i32, width as i32, palette.clue.clone(), letter_item(n));
+        }
+    }
     
     for y in 0..height {
         for x in 0..width {
@@ -16,19 +63,19 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item::cell(
                     y,
                     x,
-                    "black",
-                    ItemKind::Num(n),
+                    palette.clue.clone(),
+                    if n == 0 {ItemKind::Cross} else {letter_item(n)},
                 ));
-            } else if let Some(n) = ans[y][x] {
-                board.push(Item::cell(
+            } else if let Some(n) = ans.as_ref().and_then(|ans| ans[y][x]) {
+                board.push_answer(Item::cell(
                     y,
                     x,
-                    "green",
-                    if n == 0 {ItemKind::Cross} else {ItemKind::Num(n)},
+                    palette.answer.clone(),
+                    if n == 0 {ItemKind::Cross} else {letter_item(n)},
                 ));
             }
         }
     }
 
     Ok(board)
-}
\ No newline at end of file
+}
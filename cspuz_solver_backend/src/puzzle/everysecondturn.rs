@@ -0,0 +1,25 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::everysecondturn;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = everysecondturn::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = everysecondturn::solve_everysecondturn(&problem).ok_or(SolveError::NoAnswer)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    for y in 0..height {
+        for x in 0..width {
+            if problem[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Circle));
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::evolmino;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = evolmino::deserialize_problem(url).ok_or("invalid url")?;
-    let is_square = evolmino::solve_evolmino(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = evolmino::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_square = evolmino::solve_evolmino(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.cells.len();
     let width = problem.cells[0].len();
@@ -18,7 +19,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: y1 + y2 + 1,
                 x: x1 + x2 + 1,
-                color: "black",
+                color: "black".into(),
                 kind: ItemKind::Line,
             });
         }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::fillomino;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = fillomino::deserialize_problem(url).ok_or("invalid url")?;
-    let (num, border) = fillomino::solve_fillomino(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = fillomino::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (num, border) = fillomino::solve_fillomino(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = num.len();
     let width = num[0].len();
@@ -33,7 +34,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -48,7 +49,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -59,7 +60,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -74,7 +75,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
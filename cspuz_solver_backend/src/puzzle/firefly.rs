@@ -1,11 +1,12 @@
 use crate::board::{Board, BoardKind, FireflyDir, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs::items::Arrow;
 use cspuz_rs_puzzles::puzzles::firefly;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = firefly::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = firefly::solve_firefly(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = firefly::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = firefly::solve_firefly(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -18,7 +19,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b {
                         ItemKind::BoldWall
                     } else {
@@ -31,7 +32,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::DottedWall,
                 });
             }
@@ -44,7 +45,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b {
                         ItemKind::BoldWall
                     } else {
@@ -57,7 +58,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::DottedWall,
                 });
             }
@@ -77,7 +78,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::Firefly(dir, n),
                 });
             }
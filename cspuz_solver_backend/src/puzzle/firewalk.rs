@@ -1,11 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::firewalk;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (fire_cell, num) = firewalk::deserialize_problem(url).ok_or("invalid url")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (fire_cell, num) = firewalk::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
     let (is_line, fire_cell_mode) =
-        firewalk::solve_firewalk(&fire_cell, &num).ok_or("no answer")?;
+        firewalk::solve_firewalk(&fire_cell, &num).ok_or(SolveError::NoAnswer)?;
 
     let height = fire_cell.len();
     let width = fire_cell[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::n_cells;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = n_cells::deserialize_fivecells_problem(url).ok_or("invalid url")?;
-    let border = n_cells::solve_fivecells(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = n_cells::deserialize_fivecells_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let border = n_cells::solve_fivecells(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -30,7 +31,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -45,7 +46,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -56,7 +57,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -71,7 +72,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::forestwalk;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (forest, num) = forestwalk::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = forestwalk::solve_forestwalk(&forest, &num).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (forest, num) = forestwalk::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = forestwalk::solve_forestwalk(&forest, &num).ok_or(SolveError::NoAnswer)?;
 
     let height = forest.len();
     let width = forest[0].len();
@@ -0,0 +1,34 @@
+use crate::board::{Board, BoardKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::grandtour;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = grandtour::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let mut is_line = grandtour::solve_grandtour(&problem).ok_or(SolveError::NoAnswer)?;
+
+    let height = problem.horizontal.len();
+    let width = problem.horizontal[0].len() + 1;
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    // Hinted edges are shown in black, so they are excluded from the answer.
+    for y in 0..height {
+        for x in 0..(width - 1) {
+            if problem.horizontal[y][x].is_some() {
+                is_line.horizontal[y][x] = None;
+            }
+        }
+    }
+    for y in 0..(height - 1) {
+        for x in 0..width {
+            if problem.vertical[y][x].is_some() {
+                is_line.vertical[y][x] = None;
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&problem, "black", None);
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
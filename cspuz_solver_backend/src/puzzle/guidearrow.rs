@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::guidearrow::{self, GuidearrowClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (ty, tx, clues) = guidearrow::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = guidearrow::solve_guidearrow(ty, tx, &clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (ty, tx, clues) = guidearrow::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = guidearrow::solve_guidearrow(ty, tx, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -23,7 +24,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     GuidearrowClue::Down => ItemKind::ArrowDown,
                     GuidearrowClue::Left => ItemKind::ArrowLeft,
                     GuidearrowClue::Right => ItemKind::ArrowRight,
-                    GuidearrowClue::Unknown => ItemKind::Text("?"),
+                    GuidearrowClue::Unknown => ItemKind::Text("?".to_string()),
                 };
                 board.push(Item::cell(y, x, "black", kind));
             }
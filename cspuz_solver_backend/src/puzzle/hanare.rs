@@ -0,0 +1,36 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::hanare;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = hanare::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = hanare::solve_hanare(&borders, &clues).ok_or(SolveError::NoAnswer)?;
+
+    let height = clues.len();
+    let width = clues[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&ans));
+
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = clues[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+            } else if let Some(n) = ans[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if n == 0 {
+                        ItemKind::Dot
+                    } else {
+                        ItemKind::Num(n)
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::hashi;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let clues = hashi::deserialize_problem(url).ok_or("invalid url")?;
-    let num_line = hashi::solve_hashi(&clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let clues = hashi::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let num_line = hashi::solve_hashi(&clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -17,7 +18,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: match n {
                             0 => ItemKind::Cross,
                             1 => ItemKind::Line,
@@ -32,7 +33,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: match n {
                             0 => ItemKind::Cross,
                             1 => ItemKind::Line,
@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::hebiichigo;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     use cspuz_rs::items::Arrow;
 
-    let problem = hebiichigo::deserialize_problem(url).ok_or("invalid url")?;
-    let answer = hebiichigo::solve_hebiichigo(&problem).ok_or("no answer")?;
+    let problem = hebiichigo::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer = hebiichigo::solve_hebiichigo(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -40,7 +41,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         if n >= 0 {
                             ItemKind::Num(n)
                         } else {
-                            ItemKind::Text("?")
+                            ItemKind::Text("?".to_string())
                         },
                     ));
                 }
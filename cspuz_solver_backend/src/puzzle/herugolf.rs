@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::herugolf;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (pond, clues) = herugolf::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = herugolf::solve_herugolf(&pond, &clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (pond, clues) = herugolf::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = herugolf::solve_herugolf(&pond, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = pond.len();
     let width = pond[0].len();
@@ -20,7 +21,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item::cell(y, x, "black", ItemKind::Circle));
                     board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
                 } else {
-                    board.push(Item::cell(y, x, "black", ItemKind::Text("H")));
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("H".to_string())));
                 }
             }
         }
@@ -1,7 +1,7 @@
-pub fn solve(url: &str) -> Result<crate::board::Board, &'static str> {
+pub fn solve(url: &str) -> Result<crate::board::Board, crate::SolveError> {
     crate::puzzle::heyawake_internal::solve(url, false)
 }
 
-pub fn enumerate(url: &str, num_max_answers: usize) -> Result<(crate::board::Board, Vec<crate::board::Board>), &'static str> {
+pub fn enumerate(url: &str, num_max_answers: usize) -> Result<(crate::board::Board, Vec<crate::board::Board>), crate::SolveError> {
     crate::puzzle::heyawake_internal::enumerate_answers_heyawake(url, num_max_answers)
 }
@@ -1,26 +1,27 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::{is_unique, Uniqueness};
+use crate::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs_puzzles::puzzles::{ayeheya, heyawake};
 
-pub fn solve(url: &str, is_ayeheya: bool) -> Result<Board, &'static str> {
+pub fn solve(url: &str, is_ayeheya: bool) -> Result<Board, SolveError> {
     let borders;
     let clues;
     let is_black;
 
     if is_ayeheya {
-        let problem = ayeheya::deserialize_problem(url).ok_or("invalid url")?;
+        let problem = ayeheya::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
         borders = problem.0;
         clues = problem.1;
         if !ayeheya::all_room_symmetry(&borders) {
-            return Err("asymmetry room");
+            return Err(SolveError::MalformedProblem("asymmetry room"));
         }
-        is_black = ayeheya::solve_ayeheya(&borders, &clues).ok_or("no answer")?;
+        is_black = ayeheya::solve_ayeheya(&borders, &clues).ok_or(SolveError::NoAnswer)?;
     } else {
-        let problem = heyawake::deserialize_problem(url).ok_or("invalid url")?;
+        let problem = heyawake::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
         borders = problem.0;
         clues = problem.1;
-        is_black = heyawake::solve_heyawake(&borders, &clues).ok_or("no answer")?;
+        is_black = heyawake::solve_heyawake(&borders, &clues).ok_or(SolveError::NoAnswer)?;
     }
 
     let height = is_black.len();
@@ -56,9 +57,9 @@ pub fn solve(url: &str, is_ayeheya: bool) -> Result<Board, &'static str> {
 pub fn enumerate_answers_heyawake(
     url: &str,
     num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let (borders, clues) = heyawake::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black_common = heyawake::solve_heyawake(&borders, &clues).ok_or("no answer")?;
+) -> Result<(Board, Vec<Board>), SolveError> {
+    let (borders, clues) = heyawake::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black_common = heyawake::solve_heyawake(&borders, &clues).ok_or(SolveError::NoAnswer)?;
     let answers = heyawake::enumerate_answers_heyawake(&borders, &clues, num_max_answers);
 
     let height = is_black_common.len();
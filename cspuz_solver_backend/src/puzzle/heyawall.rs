@@ -0,0 +1,32 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs::graph;
+use cspuz_rs_puzzles::puzzles::heyawall;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = heyawall::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = heyawall::solve_heyawall(&borders, &clues).ok_or(SolveError::NoAnswer)?;
+
+    let height = is_black.len();
+    let width = is_black[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_black));
+
+    board.add_borders(&borders, "black");
+
+    board.add_bool_grid(
+        &is_black,
+        Some(ItemKind::Block),
+        Some(ItemKind::Dot),
+        "green",
+    );
+    let rooms = graph::borders_to_rooms(&borders);
+    for i in 0..rooms.len() {
+        if let Some(n) = clues[i] {
+            let (y, x) = rooms[i][0];
+            board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+        }
+    }
+
+    Ok(board)
+}
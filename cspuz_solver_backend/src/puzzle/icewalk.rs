@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::icewalk;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (icebarn, num) = icewalk::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = icewalk::solve_icewalk(&icebarn, &num).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (icebarn, num) = icewalk::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = icewalk::solve_icewalk(&icebarn, &num).ok_or(SolveError::NoAnswer)?;
 
     let height = icebarn.len();
     let width = icebarn[0].len();
@@ -1,10 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::kakuro;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = kakuro::deserialize_problem(url).ok_or("invalid url")?;
-    let answer: Vec<Vec<Option<i32>>> = kakuro::solve_kakuro(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = kakuro::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer: Vec<Vec<Option<i32>>> =
+        kakuro::solve_kakuro(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = answer.len();
     let width = answer[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::koburin;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = koburin::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_black) = koburin::solve_koburin(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = koburin::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (is_line, is_black) = koburin::solve_koburin(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -33,7 +34,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     if clue >= 0 {
                         ItemKind::Num(clue)
                     } else {
-                        ItemKind::Text("?")
+                        ItemKind::Text("?".to_string())
                     },
                 ));
             } else if let Some(b) = is_black[y][x] {
@@ -1,10 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::Uniqueness;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::kouchoku;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = kouchoku::deserialize_problem(url).ok_or("invalid url")?;
-    let (fixed_lines, undet_lines) = kouchoku::solve_kouchoku(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = kouchoku::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (fixed_lines, undet_lines) =
+        kouchoku::solve_kouchoku(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -24,7 +26,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 2,
                     x: x * 2 + 1,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::DottedLine,
                 });
             }
@@ -32,7 +34,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2 + 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::DottedLine,
                 });
             }
@@ -56,7 +58,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         board.push(Item {
             y: y1 * 2 + 1,
             x: x1 * 2 + 1,
-            color: "green",
+            color: "green".into(),
             kind: ItemKind::LineTo(y2 as i32 * 2 + 1, x2 as i32 * 2 + 1),
         });
     }
@@ -64,7 +66,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         board.push(Item {
             y: y1 * 2 + 1,
             x: x1 * 2 + 1,
-            color: "#888888",
+            color: "#888888".into(),
             kind: ItemKind::LineTo(y2 as i32 * 2 + 1, x2 as i32 * 2 + 1),
         });
     }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::kropki::{self, KropkiClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = kropki::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = kropki::solve_kropki(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = kropki::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = kropki::solve_kropki(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = ans.len();
     let width = ans[0].len();
@@ -20,14 +21,14 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallCircle,
                     });
                 } else if problem.horizontal[y][x] == KropkiClue::Black {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallFilledCircle,
                     });
                 }
@@ -37,14 +38,14 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallCircle,
                     });
                 } else if problem.vertical[y][x] == KropkiClue::Black {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallFilledCircle,
                     });
                 }
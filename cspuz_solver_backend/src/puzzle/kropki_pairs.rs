@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::kropki_pairs::{self, KropkiClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (walls, cells) = kropki_pairs::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = kropki_pairs::solve_kropki_pairs(&walls, &cells).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (walls, cells) = kropki_pairs::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = kropki_pairs::solve_kropki_pairs(&walls, &cells).ok_or(SolveError::NoAnswer)?;
 
     let height = ans.len();
     let width = ans[0].len();
@@ -26,14 +27,14 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallCircle,
                     });
                 } else if walls.horizontal[y][x] == KropkiClue::Black {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallFilledCircle,
                     });
                 }
@@ -43,14 +44,14 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallCircle,
                     });
                 } else if walls.vertical[y][x] == KropkiClue::Black {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::SmallFilledCircle,
                     });
                 }
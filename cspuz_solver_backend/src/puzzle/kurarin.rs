@@ -1,10 +1,15 @@
-use crate::board::{Board, BoardKind, Item, ItemKind};
-use crate::uniqueness::is_unique;
-use cspuz_rs_puzzles::puzzles::kurarin;
+use crate::board::{Board, BoardKind, Item, ItemKind, Palette};
+use crate::uniqueness::solution_multiplicity;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::kurarin::{self, KurarinClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = kurarin::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_black) = kurarin::solve_kurarin(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    solve_with_palette(url, &Palette::default())
+}
+
+pub fn solve_with_palette(url: &str, palette: &Palette) -> Result<Board, SolveError> {
+    let problem = kurarin::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = kurarin::solve_kurarin(&problem, None);
 
     let height = (problem.len() + 1) / 2;
     let width = (problem[0].len() + 1) / 2;
@@ -12,33 +17,83 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         BoardKind::Grid,
         height,
         width,
-        is_unique(&(&is_line, &is_black)),
+        solution_multiplicity(
+            ans.as_ref()
+                .map(|(is_line, is_black)| (is_line, is_black))
+                .as_ref(),
+        ),
     );
 
-    let mut skip_line = vec![];
-    for y in 0..height {
-        let mut row = vec![];
-        for x in 0..width {
-            row.push(is_black[y][x] == Some(true));
+    if let Some((is_line, is_black)) = &ans {
+        let mut skip_line = vec![];
+        for y in 0..height {
+            let mut row = vec![];
+            for x in 0..width {
+                row.push(is_black[y][x] == Some(true));
+            }
+            skip_line.push(row);
         }
-        skip_line.push(row);
-    }
-    for y in 0..height {
-        for x in 0..width {
-            if let Some(b) = is_black[y][x] {
-                board.push(Item::cell(
-                    y,
-                    x,
-                    "green",
-                    if b { ItemKind::Block } else { ItemKind::Dot },
-                ));
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(b) = is_black[y][x] {
+                    board.push_answer(Item::cell(
+                        y,
+                        x,
+                        palette.answer.clone(),
+                        if b { ItemKind::Block } else { ItemKind::Dot },
+                    ));
+                }
             }
         }
-    }
 
-    board.add_lines_irrefutable_facts(&is_line, "green", Some(&skip_line));
+        board.add_lines_irrefutable_facts(is_line, palette.line.clone(), Some(&skip_line));
+    }
 
-    // TODO: Add clues
+    for (y, row) in problem.iter().enumerate() {
+        for (x, &clue) in row.iter().enumerate() {
+            let (color, kind) = match clue {
+                KurarinClue::None => continue,
+                KurarinClue::White => (palette.clue.clone(), ItemKind::SmallCircle),
+                KurarinClue::Gray => (palette.secondary_clue.clone(), ItemKind::SmallFilledCircle),
+                KurarinClue::Black => (palette.clue.clone(), ItemKind::SmallFilledCircle),
+            };
+            board.push(Item {
+                y: y + 1,
+                x: x + 1,
+                color,
+                kind,
+            });
+        }
+    }
 
     Ok(board)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kurarin_palette() {
+        let url = "https://puzz.link/p?kurarin/3/3/n2hcg";
+        let palette = Palette {
+            answer: "red".into(),
+            line: "orange".into(),
+            clue: "white".into(),
+            secondary_clue: "blue".into(),
+            shading: "yellow".into(),
+        };
+
+        let default_json = solve(url).unwrap().to_json();
+        let custom_json = solve_with_palette(url, &palette).unwrap().to_json();
+        assert!(default_json.contains("\"green\""));
+        assert!(default_json.contains("\"black\""));
+        assert!(default_json.contains("\"gray\""));
+        assert!(!custom_json.contains("\"green\""));
+        assert!(!custom_json.contains("\"black\""));
+        assert!(!custom_json.contains("\"gray\""));
+        assert!(custom_json.contains("\"orange\""));
+        assert!(custom_json.contains("\"white\""));
+        assert!(custom_json.contains("\"blue\""));
+    }
+}
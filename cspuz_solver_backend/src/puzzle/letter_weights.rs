@@ -1,13 +1,15 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::letter_weights;
 
 const ALPHA: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (sums, chars, nums) = letter_weights::deserialize_problem(url)?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (sums, chars, nums) =
+        letter_weights::deserialize_problem(url).map_err(|_| SolveError::InvalidUrl)?;
     let (chars, nums, ans) = letter_weights::solve_letter_weights(&sums, &chars, &nums);
-    let ans = ans.ok_or("no answer")?;
+    let ans = ans.ok_or(SolveError::NoAnswer)?;
 
     let mut board = Board::new(
         BoardKind::Grid,
@@ -19,7 +21,12 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
     for y in 0..chars.len() {
         assert!('A' <= chars[y] && chars[y] <= 'Z');
         let i = (chars[y] as u8 - 'A' as u8) as usize;
-        board.push(Item::cell(y + 1, 0, "black", ItemKind::Text(&ALPHA[i..=i])));
+        board.push(Item::cell(
+            y + 1,
+            0,
+            "black",
+            ItemKind::Text(ALPHA[i..=i].to_string()),
+        ));
     }
 
     for x in 0..nums.len() {
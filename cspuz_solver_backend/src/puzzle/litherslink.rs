@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::litherslink;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = litherslink::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = litherslink::solve_litherslink(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = litherslink::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = litherslink::solve_litherslink(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -23,7 +24,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -35,7 +36,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::lohkous;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = lohkous::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = lohkous::solve_lohkous(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = lohkous::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = lohkous::solve_lohkous(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -19,7 +20,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     }
                     board.push(Item::cell(y, x, "black", ItemKind::TapaClue(c)));
                 } else {
-                    board.push(Item::cell(y, x, "black", ItemKind::Text("...")));
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("...".to_string())));
                 }
             }
         }
@@ -32,7 +33,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -47,7 +48,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -58,7 +59,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -73,7 +74,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::loop_special::{self, LoopSpecialClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = loop_special::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = loop_special::solve_loop_special(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = loop_special::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = loop_special::solve_loop_special(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -35,7 +36,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         board.push(Item {
                             y: y * 2,
                             x: x * 2 + 1,
-                            color: "black",
+                            color: "black".into(),
                             kind: ItemKind::Line,
                         });
                     }
@@ -43,7 +44,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         board.push(Item {
                             y: y * 2 + 2,
                             x: x * 2 + 1,
-                            color: "black",
+                            color: "black".into(),
                             kind: ItemKind::Line,
                         });
                     }
@@ -51,7 +52,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         board.push(Item {
                             y: y * 2 + 1,
                             x: x * 2,
-                            color: "black",
+                            color: "black".into(),
                             kind: ItemKind::Line,
                         });
                     }
@@ -59,7 +60,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                         board.push(Item {
                             y: y * 2 + 1,
                             x: x * 2 + 2,
-                            color: "black",
+                            color: "black".into(),
                             kind: ItemKind::Line,
                         });
                     }
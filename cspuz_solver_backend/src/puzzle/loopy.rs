@@ -0,0 +1,33 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::loopy::{self, LoopyCell};
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (cell_spec, edge_spec) = loopy::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = loopy::solve_loopy(&cell_spec, &edge_spec).ok_or(SolveError::NoAnswer)?;
+
+    let height = cell_spec.len();
+    let width = cell_spec[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    let mut skip_line = vec![vec![false; width]; height];
+    for y in 0..height {
+        for x in 0..width {
+            match cell_spec[y][x] {
+                LoopyCell::Free => (),
+                LoopyCell::OnLoop => {
+                    board.push(Item::cell(y, x, "black", ItemKind::SmallCircle));
+                }
+                LoopyCell::OffLoop => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Fill));
+                    skip_line[y][x] = true;
+                }
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", Some(&skip_line));
+
+    Ok(board)
+}
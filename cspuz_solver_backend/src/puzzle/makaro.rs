@@ -0,0 +1,43 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs::items::Arrow;
+use cspuz_rs_puzzles::puzzles::makaro::{self, MakaroCell};
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, cells) = makaro::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = makaro::solve_makaro(&borders, &cells).ok_or(SolveError::NoAnswer)?;
+
+    let height = cells.len();
+    let width = cells[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&ans));
+
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            match cells[y][x] {
+                MakaroCell::Num(n) => board.push(Item::cell(y, x, "black", ItemKind::Num(n))),
+                MakaroCell::Black => board.push(Item::cell(y, x, "black", ItemKind::Fill)),
+                MakaroCell::Arrow(dir) => {
+                    board.push(Item::cell(y, x, "black", ItemKind::Fill));
+                    let kind = match dir {
+                        Arrow::Up => ItemKind::ArrowUp,
+                        Arrow::Down => ItemKind::ArrowDown,
+                        Arrow::Left => ItemKind::ArrowLeft,
+                        Arrow::Right => ItemKind::ArrowRight,
+                        Arrow::Unspecified => continue,
+                    };
+                    board.push(Item::cell(y, x, "white", kind));
+                }
+                MakaroCell::Empty => {
+                    if let Some(n) = ans[y][x] {
+                        board.push(Item::cell(y, x, "green", ItemKind::Num(n)));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::masyu;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     use masyu::MasyuClue;
 
-    let problem = masyu::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = masyu::solve_masyu(&problem).ok_or("no answer")?;
+    let problem = masyu::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = masyu::solve_masyu(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,4 +1,5 @@
-use crate::board::Board;
+use crate::board::{Board, Palette};
+use crate::SolveError;
 
 macro_rules! dispatch_enumerate {
     ( $mod:ident, $aliases:expr, $puzzle_kind:expr, $url:expr, $num_max_answers:expr ) => {};
@@ -19,24 +20,41 @@ macro_rules! dispatch_enumerate_list {
 }
 
 macro_rules! puzzle_list {
-    ( $mod_name:ident, $( ($mod:ident, $aliases: expr, $en_name:expr, $ja_name:expr $(, $enumerable:ident )? ) ),* $(,)? ) => {
+    ( $mod_name:ident, $( ($mod:ident, [$( $alias:literal ),+], $en_name:expr, $ja_name:expr $(, $enumerable:ident )? ) ),* $(,)? ) => {
         $(
             pub mod $mod;
         )*
 
         mod $mod_name {
-            pub fn dispatch(puzzle_kind: &str, url: &str) -> Option<Result<super::Board, &'static str>> {
+            /// All kind tokens (including aliases) registered in this list.
+            #[allow(unused)]
+            pub const KINDS: &[&str] = &[$( $( $alias, )+ )*];
+
+            /// Pairs of a kind token (including aliases) and the solver it is dispatched to.
+            /// Adding an alias only needs a new token in the list of its entry.
+            const SOLVERS: &[(&str, fn(&str) -> Result<super::Board, super::SolveError>)] =
+                &[$( $( ($alias, super::$mod::solve), )+ )*];
+
+            /// Returns the first kind token of the entry `puzzle_kind` belongs to, so that aliases can be
+            /// looked up in other tables keyed by that token.
+            #[allow(unused)]
+            pub fn canonical_kind(puzzle_kind: &str) -> Option<&'static str> {
                 $(
-                    for alias in $aliases {
-                        if puzzle_kind == alias {
-                            return Some(super::$mod::solve(url));
-                        }
+                    let aliases: &[&'static str] = &[$( $alias ),+];
+                    if aliases.contains(&puzzle_kind) {
+                        return Some(aliases[0]);
                     }
                 )*
-
                 None
             }
 
+            pub fn dispatch(puzzle_kind: &str, url: &str) -> Option<Result<super::Board, super::SolveError>> {
+                SOLVERS
+                    .iter()
+                    .find(|&&(alias, _)| alias == puzzle_kind)
+                    .map(|&(_, solve)| solve(url))
+            }
+
             #[allow(unused)]
             pub fn dispatch_enumerate(
                 #[allow(unused)]
@@ -45,9 +63,9 @@ macro_rules! puzzle_list {
                 url: &str,
                 #[allow(unused)]
                 num_max_answers: usize,
-            ) -> Option<Result<(super::Board, Vec<super::Board>), &'static str>> {
+            ) -> Option<Result<(super::Board, Vec<super::Board>), super::SolveError>> {
                 $(
-                    dispatch_enumerate!($mod, $aliases, puzzle_kind, url, num_max_answers $(, $enumerable)?);
+                    dispatch_enumerate!($mod, [$( $alias ),+], puzzle_kind, url, num_max_answers $(, $enumerable)?);
                 )*
 
                 None
@@ -161,6 +179,25 @@ puzzle_list!(puzz_link,
     (kurarin, ["kurarin"], "Kurarin", "クラリン"),
     (waterwalk, ["waterwalk"], "Waterwalk", "ウォーターウォーク"),
     (easyasabc, ["easyasabc"], "Easy as ABC", "ABCプレース"),
+    (nanro, ["nanro"], "Nanro", "ナンロー"),
+    (nanrosignpost, ["nanrosignpost"], "Nanro Signpost", "Nanro Signpost"),
+    (corral, ["corral", "bag"], "Corral", "Corral"),
+    (tontti, ["tontti"], "Tontti", "Tontti"),
+    (sukororoom, ["sukororoom"], "Sukoro-room", "数コロ部屋"),
+    (vertexloop, ["vertexloop"], "Vertex Loop", "Vertex Loop"),
+    (crossthestreams, ["crossthestreams"], "Cross the Streams", "Cross the Streams"),
+    (regionstar, ["regionstar"], "Region Star", "Region Star"),
+    (loopy, ["loopy"], "Loopy", "Loopy"),
+    (everysecondturn, ["everysecondturn"], "Every Second Turn", "Every Second Turn"),
+    (survo, ["survo"], "Survo", "Survo"),
+    (makaro, ["makaro"], "Makaro", "マカロ"),
+    (sashiko, ["sashiko"], "Sashiko", "刺し子"),
+    (arrowpath, ["arrowpath"], "Arrow Path", "Arrow Path"),
+    (rlits, ["rlits"], "Regional LITS", "Regional LITS"),
+    (grandtour, ["grandtour"], "Grand Tour", "Grand Tour"),
+    (hanare, ["hanare"], "Hanare-gumi", "ハナレグミ"),
+    (roma, ["roma"], "Roma", "ろーま"),
+    (heyawall, ["heyawall"], "Heyawall", "Heyawall"),
 );
 
 #[rustfmt::skip]
@@ -186,15 +223,41 @@ puzzle_list!(kudamono,
 
 pub mod double_lits;
 
-pub fn dispatch_puzz_link(puzzle_kind: &str, url: &str) -> Option<Result<Board, &'static str>> {
+/// Kind tokens of puzz.link URLs which `dispatch_puzz_link` can solve.
+pub const PUZZ_LINK_KINDS: &[&str] = puzz_link::KINDS;
+
+pub fn dispatch_puzz_link(puzzle_kind: &str, url: &str) -> Option<Result<Board, SolveError>> {
     puzz_link::dispatch(puzzle_kind, url)
 }
 
+type PaletteSolver = fn(&str, &Palette) -> Result<Board, SolveError>;
+
+/// Kind tokens of puzz.link URLs whose output colors can be customized, paired with their solvers.
+/// Each token must be the first one of its entry in `puzz_link`; aliases are resolved by `dispatch_puzz_link_with_palette`.
+const PALETTE_SOLVERS: &[(&str, PaletteSolver)] = &[
+    ("easyasabc", easyasabc::solve_with_palette),
+    ("kurarin", kurarin::solve_with_palette),
+    ("shugaku", shugaku::solve_with_palette),
+    ("waterwalk", waterwalk::solve_with_palette),
+];
+
+pub fn dispatch_puzz_link_with_palette(
+    puzzle_kind: &str,
+    url: &str,
+    palette: &Palette,
+) -> Option<Result<Board, SolveError>> {
+    let puzzle_kind = puzz_link::canonical_kind(puzzle_kind)?;
+    PALETTE_SOLVERS
+        .iter()
+        .find(|&&(kind, _)| kind == puzzle_kind)
+        .map(|&(_, solve)| solve(url, palette))
+}
+
 pub fn dispatch_puzz_link_enumerate(
     puzzle_kind: &str,
     url: &str,
     num_max_answers: usize,
-) -> Option<Result<(Board, Vec<Board>), &'static str>> {
+) -> Option<Result<(Board, Vec<Board>), SolveError>> {
     puzz_link::dispatch_enumerate(puzzle_kind, url, num_max_answers)
 }
 
@@ -202,7 +265,7 @@ pub fn dispatch_kudamono(
     puzzle_kind: &str,
     puzzle_variant: &str,
     url: &str,
-) -> Option<Result<Board, &'static str>> {
+) -> Option<Result<Board, SolveError>> {
     if let Some(res) = kudamono::dispatch(puzzle_kind, url) {
         return Some(res);
     }
@@ -236,3 +299,19 @@ pub fn list_puzzles_for_enumerate() -> Vec<(String, String)> {
 
     puzzles
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_kind() {
+        assert_eq!(puzz_link::canonical_kind("masyu"), Some("masyu"));
+        assert_eq!(puzz_link::canonical_kind("mashu"), Some("masyu"));
+        assert_eq!(puzz_link::canonical_kind("unknownpuzzle"), None);
+
+        for &(kind, _) in PALETTE_SOLVERS {
+            assert_eq!(puzz_link::canonical_kind(kind), Some(kind));
+        }
+    }
+}
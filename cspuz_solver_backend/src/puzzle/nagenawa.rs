@@ -1,11 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs_puzzles::puzzles::nagenawa;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = nagenawa::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = nagenawa::solve_nagenawa(&borders, &clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = nagenawa::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = nagenawa::solve_nagenawa(&borders, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = is_line.horizontal.len();
     let width = is_line.horizontal[0].len() + 1;
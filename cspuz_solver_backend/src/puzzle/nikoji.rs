@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::nikoji;
 
 const ALPHA: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = nikoji::deserialize_problem(url).ok_or("invalid url")?;
-    let border = nikoji::solve_nikoji(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = nikoji::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let border = nikoji::solve_nikoji(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -16,7 +17,12 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             if let Some(clue) = problem[y][x] {
                 if 1 <= clue && clue <= 26 {
                     let p = (clue - 1) as usize;
-                    board.push(Item::cell(y, x, "black", ItemKind::Text(&ALPHA[p..=p])));
+                    board.push(Item::cell(
+                        y,
+                        x,
+                        "black",
+                        ItemKind::Text(ALPHA[p..=p].to_string()),
+                    ));
                 } else {
                     board.push(Item::cell(y, x, "black", ItemKind::Num(clue - 26)));
                 }
@@ -32,7 +38,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -47,7 +53,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -58,7 +64,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -73,7 +79,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::nothree;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = nothree::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = nothree::solve_nothree(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = nothree::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = nothree::solve_nothree(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
@@ -15,7 +16,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: y * 2 + 1,
                 x: x * 2 + 2,
-                color: "black",
+                color: "black".into(),
                 kind: ItemKind::Wall,
             });
         }
@@ -25,7 +26,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: y * 2 + 2,
                 x: x * 2 + 1,
-                color: "black",
+                color: "black".into(),
                 kind: ItemKind::Wall,
             });
         }
@@ -50,13 +51,13 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y + 1,
                     x: x + 1,
-                    color: "white",
+                    color: "white".into(),
                     kind: ItemKind::SmallFilledCircle,
                 });
                 board.push(Item {
                     y: y + 1,
                     x: x + 1,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::SmallCircle,
                 });
             }
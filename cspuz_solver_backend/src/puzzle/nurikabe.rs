@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::{is_unique, Uniqueness};
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::nurikabe;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = nurikabe::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = nurikabe::solve_nurikabe(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = nurikabe::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = nurikabe::solve_nurikabe(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -15,7 +16,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 if clue > 0 {
                     board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
                 } else {
-                    board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?".to_string())));
                 }
             } else if let Some(a) = ans[y][x] {
                 board.push(Item::cell(
@@ -31,12 +32,9 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
     Ok(board)
 }
 
-pub fn enumerate(
-    url: &str,
-    num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let problem = nurikabe::deserialize_problem(url).ok_or("invalid url")?;
-    let ans_common = nurikabe::solve_nurikabe(&problem).ok_or("no answer")?;
+pub fn enumerate(url: &str, num_max_answers: usize) -> Result<(Board, Vec<Board>), SolveError> {
+    let problem = nurikabe::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans_common = nurikabe::solve_nurikabe(&problem).ok_or(SolveError::NoAnswer)?;
     let answers = nurikabe::enumerate_answers_nurikabe(&problem, num_max_answers);
 
     let height = problem.len();
@@ -49,7 +47,7 @@ pub fn enumerate(
                 if clue > 0 {
                     board_common.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
                 } else {
-                    board_common.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                    board_common.push(Item::cell(y, x, "black", ItemKind::Text("?".to_string())));
                 }
             } else if let Some(a) = ans_common[y][x] {
                 board_common.push(Item::cell(
@@ -75,7 +73,12 @@ pub fn enumerate(
                     if clue > 0 {
                         board_answer.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
                     } else {
-                        board_answer.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                        board_answer.push(Item::cell(
+                            y,
+                            x,
+                            "black",
+                            ItemKind::Text("?".to_string()),
+                        ));
                     }
                 } else {
                     let a = ans[y][x];
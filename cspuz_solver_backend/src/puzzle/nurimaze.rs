@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::nurimaze;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = nurimaze::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = nurimaze::solve_nurimaze(&borders, &clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = nurimaze::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = nurimaze::solve_nurimaze(&borders, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
@@ -13,8 +14,8 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
     for y in 0..height {
         for x in 0..width {
             match clues[y][x] {
-                1 => board.push(Item::cell(y, x, "black", ItemKind::Text("S"))),
-                2 => board.push(Item::cell(y, x, "black", ItemKind::Text("G"))),
+                1 => board.push(Item::cell(y, x, "black", ItemKind::Text("S".to_string()))),
+                2 => board.push(Item::cell(y, x, "black", ItemKind::Text("G".to_string()))),
                 3 => board.push(Item::cell(y, x, "black", ItemKind::Circle)),
                 4 => board.push(Item::cell(y, x, "black", ItemKind::Triangle)),
                 _ => (),
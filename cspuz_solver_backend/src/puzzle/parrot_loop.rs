@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::parrot_loop;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = parrot_loop::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = parrot_loop::solve_parrot_loop(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = parrot_loop::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = parrot_loop::solve_parrot_loop(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,15 +1,17 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs::graph::InnerGridEdges;
 use cspuz_rs_puzzles::puzzles::polyominous;
 
 const PENTOMINO_NAMES: [&'static str; 12] =
     ["F", "I", "L", "N", "P", "T", "U", "V", "W", "X", "Y", "Z"];
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     let (clues, default_borders) =
-        polyominous::deserialize_pentominous_problem(url).ok_or("invalid url")?;
-    let border = polyominous::solve_pentominous(&clues, &default_borders).ok_or("no answer")?;
+        polyominous::deserialize_pentominous_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let border =
+        polyominous::solve_pentominous(&clues, &default_borders).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -23,7 +25,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     x,
                     "black",
                     if n >= 0 {
-                        ItemKind::Text(PENTOMINO_NAMES[n as usize])
+                        ItemKind::Text(PENTOMINO_NAMES[n as usize].to_string())
                     } else {
                         ItemKind::Fill
                     },
@@ -36,78 +38,102 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         horizontal: vec![vec![false; width]; height - 1],
         vertical: vec![vec![false; width - 1]; height],
     });
+
+    // Edges touching a hole are not shown at all, and default borders are shown in black instead of
+    // the solver output. Edges which are not known to be borders get a thin gray wall on top.
+    let mut shown_default = default_borders.clone();
+    let mut shown_border = border.clone();
+    let mut thin_walls = vec![];
     for y in 0..height {
         for x in 0..width {
-            if y < height - 1 && clues[y][x] != Some(-1) && clues[y + 1][x] != Some(-1) {
-                let mut need_default_edge = true;
-                if default_borders.horizontal[y][x] {
-                    board.push(Item {
-                        y: y * 2 + 2,
-                        x: x * 2 + 1,
-                        color: "black",
-                        kind: ItemKind::BoldWall,
-                    });
-                    need_default_edge = false;
-                } else if let Some(b) = border.horizontal[y][x] {
-                    board.push(Item {
-                        y: y * 2 + 2,
-                        x: x * 2 + 1,
-                        color: "green",
-                        kind: if b {
-                            ItemKind::BoldWall
-                        } else {
-                            ItemKind::Cross
-                        },
-                    });
-                    if b {
-                        need_default_edge = false;
-                    }
-                }
-                if need_default_edge {
-                    board.push(Item {
-                        y: y * 2 + 2,
-                        x: x * 2 + 1,
-                        color: "#cccccc",
-                        kind: ItemKind::Wall,
-                    });
+            if y < height - 1 {
+                if clues[y][x] == Some(-1) || clues[y + 1][x] == Some(-1) {
+                    shown_default.horizontal[y][x] = false;
+                    shown_border.horizontal[y][x] = None;
+                } else if default_borders.horizontal[y][x] {
+                    shown_border.horizontal[y][x] = None;
+                } else if border.horizontal[y][x] != Some(true) {
+                    thin_walls.push((y * 2 + 2, x * 2 + 1));
                 }
             }
-            if x < width - 1 && clues[y][x] != Some(-1) && clues[y][x + 1] != Some(-1) {
-                let mut need_default_edge = true;
-                if default_borders.vertical[y][x] {
-                    board.push(Item {
-                        y: y * 2 + 1,
-                        x: x * 2 + 2,
-                        color: "black",
-                        kind: ItemKind::BoldWall,
-                    });
-                    need_default_edge = false;
-                } else if let Some(b) = border.vertical[y][x] {
-                    board.push(Item {
-                        y: y * 2 + 1,
-                        x: x * 2 + 2,
-                        color: "green",
-                        kind: if b {
-                            ItemKind::BoldWall
-                        } else {
-                            ItemKind::Cross
-                        },
-                    });
-                    if b {
-                        need_default_edge = false;
-                    }
-                }
-                if need_default_edge {
-                    board.push(Item {
-                        y: y * 2 + 1,
-                        x: x * 2 + 2,
-                        color: "#cccccc",
-                        kind: ItemKind::Wall,
-                    });
+            if x < width - 1 {
+                if clues[y][x] == Some(-1) || clues[y][x + 1] == Some(-1) {
+                    shown_default.vertical[y][x] = false;
+                    shown_border.vertical[y][x] = None;
+                } else if default_borders.vertical[y][x] {
+                    shown_border.vertical[y][x] = None;
+                } else if border.vertical[y][x] != Some(true) {
+                    thin_walls.push((y * 2 + 1, x * 2 + 2));
                 }
             }
         }
     }
+    board.add_borders(&shown_default, "black");
+    board.add_borders_irrefutable_facts(&shown_border, "green");
+    for (y, x) in thin_walls {
+        board.push(Item {
+            y,
+            x,
+            color: "#cccccc".into(),
+            kind: ItemKind::Wall,
+        });
+    }
 
     Ok(board)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pentominous_borders() {
+        let json = solve("https://puzz.link/p?pentominous/5/5/72zi")
+            .unwrap()
+            .to_json();
+
+        let horizontal = [
+            [0, 0, 1, 1, 1],
+            [0, 1, 1, 0, 1],
+            [1, 1, 1, 0, 0],
+            [0, 0, 1, 1, 0],
+        ];
+        let vertical = [
+            [1, 0, 0, 0],
+            [1, 1, 0, 0],
+            [0, 0, 1, 1],
+            [0, 0, 1, 1],
+            [0, 1, 0, 0],
+        ];
+        let mut edges = vec![];
+        for y in 0..4 {
+            for x in 0..5 {
+                edges.push((y * 2 + 2, x * 2 + 1, horizontal[y][x] == 1));
+            }
+        }
+        for y in 0..5 {
+            for x in 0..4 {
+                edges.push((y * 2 + 1, x * 2 + 2, vertical[y][x] == 1));
+            }
+        }
+
+        // The answer is unique, so every inner edge is either a border or a cross with a thin wall.
+        for (y, x, is_border) in edges {
+            let bold = format!(
+                "{{\"y\":{},\"x\":{},\"color\":\"green\",\"item\":\"boldWall\"}}",
+                y, x
+            );
+            let cross = format!(
+                "{{\"y\":{},\"x\":{},\"color\":\"green\",\"item\":\"cross\"}}",
+                y, x
+            );
+            let wall = format!(
+                "{{\"y\":{},\"x\":{},\"color\":\"#cccccc\",\"item\":\"wall\"}}",
+                y, x
+            );
+            assert_eq!(json.contains(&bold), is_border);
+            assert_eq!(json.contains(&cross), !is_border);
+            assert_eq!(json.contains(&wall), !is_border);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::regionstar;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (star_amount, borders) =
+        regionstar::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = regionstar::solve_regionstar(star_amount, &borders).ok_or(SolveError::NoAnswer)?;
+
+    let height = ans.len();
+    let width = ans[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&ans));
+
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(b) = ans[y][x] {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if b {
+                        ItemKind::FilledCircle
+                    } else {
+                        ItemKind::Dot
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::ringring;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = ringring::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_black) = ringring::solve_ringring(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = ringring::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (is_line, is_black) = ringring::solve_ringring(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::ripple;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = ripple::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = ripple::solve_ripple(&borders, &clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = ripple::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = ripple::solve_ripple(&borders, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -0,0 +1,45 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs::graph;
+use cspuz_rs_puzzles::puzzles::rlits;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = rlits::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = rlits::solve_rlits(&borders, &clues).ok_or(SolveError::NoAnswer)?;
+
+    let height = is_black.len();
+    let width = is_black[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_black));
+
+    board.add_borders(&borders, "black");
+
+    board.add_bool_grid(
+        &is_black,
+        Some(ItemKind::Block),
+        Some(ItemKind::Dot),
+        "green",
+    );
+    let rooms = graph::borders_to_rooms(&borders);
+    assert_eq!(rooms.len(), clues.len());
+    for i in 0..rooms.len() {
+        if let Some(n) = clues[i] {
+            let (y, x) = rooms[i][0];
+            let letter = match n {
+                0 => "L",
+                1 => "I",
+                2 => "T",
+                3 => "S",
+                _ => "?",
+            };
+            board.push(Item::cell(
+                y,
+                x,
+                "black",
+                ItemKind::Text(letter.to_string()),
+            ));
+        }
+    }
+
+    Ok(board)
+}
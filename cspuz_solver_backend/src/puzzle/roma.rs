@@ -0,0 +1,36 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::roma::{self, RomaCell};
+
+fn cell_to_item(cell: RomaCell) -> ItemKind {
+    match cell {
+        RomaCell::Up => ItemKind::ArrowUp,
+        RomaCell::Down => ItemKind::ArrowDown,
+        RomaCell::Left => ItemKind::ArrowLeft,
+        RomaCell::Right => ItemKind::ArrowRight,
+        RomaCell::Goal => ItemKind::Circle,
+    }
+}
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = roma::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer = roma::solve_roma(&borders, &clues).ok_or(SolveError::NoAnswer)?;
+
+    let height = clues.len();
+    let width = clues[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&answer));
+    board.add_borders(&borders, "black");
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(c) = clues[y][x] {
+                board.push(Item::cell(y, x, "black", cell_to_item(c)));
+            } else if let Some(c) = answer[y][x] {
+                board.push(Item::cell(y, x, "green", cell_to_item(c)));
+            }
+        }
+    }
+
+    Ok(board)
+}
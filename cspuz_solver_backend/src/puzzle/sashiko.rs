@@ -0,0 +1,28 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::sashiko;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = sashiko::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = sashiko::solve_sashiko(&problem).ok_or(SolveError::NoAnswer)?;
+
+    let height = problem.len();
+    let width = problem[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(clue) = problem[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Circle));
+                if clue > 0 {
+                    board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
+                }
+            }
+        }
+    }
+
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
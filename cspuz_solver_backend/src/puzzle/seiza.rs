@@ -1,10 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::seiza;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (absent_cell, num, borders) = seiza::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_star) = seiza::solve_seiza(&absent_cell, &num, &borders).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (absent_cell, num, borders) =
+        seiza::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (is_line, is_star) =
+        seiza::solve_seiza(&absent_cell, &num, &borders).ok_or(SolveError::NoAnswer)?;
 
     let height = absent_cell.len();
     let width = absent_cell[0].len();
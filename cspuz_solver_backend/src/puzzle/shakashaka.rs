@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::shakashaka::{self, ShakashakaCell};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = shakashaka::deserialize_problem(url).ok_or("invalid url")?;
-    let answer = shakashaka::solve_shakashaka(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = shakashaka::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer = shakashaka::solve_shakashaka(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,10 +1,15 @@
-use crate::board::{Board, BoardKind, Item, ItemKind};
-use crate::uniqueness::is_unique;
+use crate::board::{Board, BoardKind, Item, ItemKind, Palette};
+use crate::uniqueness::solution_multiplicity;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::shugaku::{self, ShugakuDirection, ShugakuKind};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = shugaku::deserialize_problem(url).ok_or("invalid url")?;
-    let (kind, direction) = shugaku::solve_shugaku(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    solve_with_palette(url, &Palette::default())
+}
+
+pub fn solve_with_palette(url: &str, palette: &Palette) -> Result<Board, SolveError> {
+    let problem = shugaku::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = shugaku::solve_shugaku(&problem);
 
     let height = problem.len();
     let width = problem[0].len();
@@ -12,38 +17,74 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         BoardKind::Grid,
         height,
         width,
-        is_unique(&(&kind, &direction)),
+        solution_multiplicity(
+            ans.as_ref()
+                .map(|(kind, direction)| (kind, direction))
+                .as_ref(),
+        ),
     );
+    if let Some((kind, direction)) = &ans {
+        // A cell is undecided if either its kind or its direction is.
+        let undecided_count = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (y, x)))
+            .filter(|&(y, x)| kind[y][x].is_none() || direction[y][x].is_none())
+            .count();
+        board.set_undecided_count(undecided_count);
+    }
 
     for y in 0..height {
         for x in 0..width {
             if let Some(n) = problem[y][x] {
-                board.push(Item::cell(y, x, "black", ItemKind::Circle));
-                if 0 <= n && n <= 4 {
-                    board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+                board.push(Item::cell(y, x, palette.clue.clone(), ItemKind::Circle));
+                if n >= 0 {
+                    board.push(Item::cell(y, x, palette.clue.clone(), ItemKind::Num(n)));
                 }
-            } else {
+            } else if let Some((kind, direction)) = &ans {
                 if let Some(k) = kind[y][x] {
                     match k {
-                        ShugakuKind::Aisle => board.push(Item::cell(y, x, "green", ItemKind::Fill)),
+                        ShugakuKind::Aisle => board.push_answer(Item::cell(
+                            y,
+                            x,
+                            palette.answer.clone(),
+                            ItemKind::Fill,
+                        )),
                         ShugakuKind::Pillow => {
-                            board.push(Item::cell(y, x, "green", ItemKind::ShugakuPillow));
+                            board.push_answer(Item::cell(
+                                y,
+                                x,
+                                palette.answer.clone(),
+                                ItemKind::ShugakuPillow,
+                            ));
                         }
-                        ShugakuKind::Futon => board.push(Item::cell(y, x, "green", ItemKind::ShugakuFuton)),
+                        ShugakuKind::Futon => board.push_answer(Item::cell(
+                            y,
+                            x,
+                            palette.answer.clone(),
+                            ItemKind::ShugakuFuton,
+                        )),
                         _ => (),
                     }
                 }
                 if let Some(d) = direction[y][x] {
                     match d {
-                        ShugakuDirection::West => {
-                            board.push(Item::cell(y, x, "green", ItemKind::ShugakuWest))
-                        }
-                        ShugakuDirection::East => {
-                            board.push(Item::cell(y, x, "green", ItemKind::ShugakuEast))
-                        }
-                        ShugakuDirection::South => {
-                            board.push(Item::cell(y, x, "green", ItemKind::ShugakuSouth))
-                        }
+                        ShugakuDirection::West => board.push_answer(Item::cell(
+                            y,
+                            x,
+                            palette.answer.clone(),
+                            ItemKind::ShugakuWest,
+                        )),
+                        ShugakuDirection::East => board.push_answer(Item::cell(
+                            y,
+                            x,
+                            palette.answer.clone(),
+                            ItemKind::ShugakuEast,
+                        )),
+                        ShugakuDirection::South => board.push_answer(Item::cell(
+                            y,
+                            x,
+                            palette.answer.clone(),
+                            ItemKind::ShugakuSouth,
+                        )),
                         ShugakuDirection::None => (),
                     }
                 }
@@ -53,3 +94,44 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
 
     Ok(board)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shugaku_undecided_count() {
+        let url = shugaku::serialize_problem(&vec![
+            vec![None, None, Some(0)],
+            vec![None, None, None],
+            vec![None, None, None],
+        ])
+        .unwrap();
+        let json = solve(&url).unwrap().to_json();
+        assert!(json.contains("\"isUnique\":false"));
+        assert!(json.contains("\"undecidedCount\":8"));
+
+        let json = solve("https://puzz.link/p?shugaku/4/4/a03e")
+            .unwrap()
+            .to_json();
+        assert!(json.contains("\"isUnique\":true"));
+        assert!(json.contains("\"undecidedCount\":0"));
+    }
+
+    #[test]
+    fn test_shugaku_no_solution() {
+        // A corner cell has only 2 neighbors, so it cannot be adjacent to 4 pillows.
+        let url = shugaku::serialize_problem(&vec![
+            vec![Some(4), None, None],
+            vec![None, None, None],
+            vec![None, None, None],
+        ])
+        .unwrap();
+        let board = solve(&url).unwrap();
+        let json = board.to_json();
+        assert!(json.contains("\"isUnique\":false,\"hasSolution\":false"));
+        assert!(!json.contains("undecidedCount"));
+        assert_eq!(board.items_at_cell(0, 0).count(), 2);
+        assert_eq!(board.items_at_cell(1, 1).count(), 0);
+    }
+}
@@ -1,13 +1,14 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::slalom;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     use slalom::{SlalomBlackCellDir, SlalomCell};
 
-    let problem = slalom::deserialize_problem_as_primitive(url).ok_or("invalid url")?;
+    let problem = slalom::deserialize_problem_as_primitive(url).ok_or(SolveError::InvalidUrl)?;
     let (is_black, gates, origin) = slalom::parse_primitive_problem(&problem);
-    let is_line = slalom::solve_slalom(origin, &is_black, &gates).ok_or("no answer")?;
+    let is_line = slalom::solve_slalom(origin, &is_black, &gates).ok_or(SolveError::NoAnswer)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
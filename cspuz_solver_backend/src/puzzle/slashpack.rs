@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::slashpack;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = slashpack::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = slashpack::solve_slashpack(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = slashpack::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = slashpack::solve_slashpack(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -15,7 +16,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 if clue > 0 {
                     board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
                 } else {
-                    board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?".to_string())));
                 }
             } else if let Some(a) = ans[y][x] {
                 board.push(Item::cell(
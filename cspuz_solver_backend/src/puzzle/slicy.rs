@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::slicy;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let borders = slicy::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = slicy::solve_slicy(&borders).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let borders = slicy::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = slicy::solve_slicy(&borders).ok_or(SolveError::NoAnswer)?;
 
     let (a, b, c, d) = ans.dims();
     let mut board = Board::new(
@@ -28,7 +29,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: ty * 2 + 1 + dy * 2,
                         x: tx * 2 + 1 + dx * 2,
-                        color: if b { "green" } else { "#cccccc" },
+                        color: if b { "green" } else { "#cccccc" }.into(),
                         kind: ItemKind::Fill,
                     });
                 }
@@ -47,7 +48,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         board.push(Item {
             y: ty * 2,
             x: tx * 2 + 1,
-            color: "black",
+            color: "black".into(),
             kind: if !ans.is_valid_coord_offset((y, x), (-1, -1))
                 || borders.to_bottom_right[(y - 1, x - 1)]
             {
@@ -59,7 +60,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         board.push(Item {
             y: ty * 2 + 4,
             x: tx * 2 + 3,
-            color: "black",
+            color: "black".into(),
             kind: if !ans.is_valid_coord_offset((y, x), (1, 1)) || borders.to_bottom_right[(y, x)] {
                 ItemKind::BoldWall
             } else {
@@ -69,7 +70,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         board.push(Item {
             y: ty * 2,
             x: tx * 2 + 3,
-            color: "black",
+            color: "black".into(),
             kind: if !ans.is_valid_coord_offset((y, x), (-1, 0))
                 || borders.to_bottom_left[(y - 1, x)]
             {
@@ -81,7 +82,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         board.push(Item {
             y: ty * 2 + 4,
             x: tx * 2 + 1,
-            color: "black",
+            color: "black".into(),
             kind: if !ans.is_valid_coord_offset((y, x), (1, 0)) || borders.to_bottom_left[(y, x)] {
                 ItemKind::BoldWall
             } else {
@@ -92,7 +93,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: ty * 2 + t,
                 x: tx * 2,
-                color: "black",
+                color: "black".into(),
                 kind: if !ans.is_valid_coord_offset((y, x), (0, -1)) || borders.to_right[(y, x - 1)]
                 {
                     ItemKind::BoldWall
@@ -103,7 +104,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: ty * 2 + t,
                 x: tx * 2 + 4,
-                color: "black",
+                color: "black".into(),
                 kind: if !ans.is_valid_coord_offset((y, x), (0, 1)) || borders.to_right[(y, x)] {
                     ItemKind::BoldWall
                 } else {
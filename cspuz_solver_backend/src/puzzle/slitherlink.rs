@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::{is_unique, Uniqueness};
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::slitherlink;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = slitherlink::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = slitherlink::solve_slitherlink(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = slitherlink::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = slitherlink::solve_slitherlink(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -23,7 +24,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -35,7 +36,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -45,12 +46,9 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
     Ok(board)
 }
 
-pub fn enumerate(
-    url: &str,
-    num_max_answers: usize,
-) -> Result<(Board, Vec<Board>), &'static str> {
-    let problem = slitherlink::deserialize_problem(url).ok_or("invalid url")?;
-    let answer_common = slitherlink::solve_slitherlink(&problem).ok_or("no answer")?;
+pub fn enumerate(url: &str, num_max_answers: usize) -> Result<(Board, Vec<Board>), SolveError> {
+    let problem = slitherlink::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer_common = slitherlink::solve_slitherlink(&problem).ok_or(SolveError::NoAnswer)?;
     let answers = slitherlink::enumerate_answers_slitherlink(&problem, num_max_answers);
 
     let height = problem.len();
@@ -70,7 +68,7 @@ pub fn enumerate(
                 board_common.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -82,7 +80,7 @@ pub fn enumerate(
                 board_common.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "black",
+                    color: "black".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 })
             }
@@ -103,7 +101,7 @@ pub fn enumerate(
                 board_answer.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 });
             }
@@ -117,7 +115,7 @@ pub fn enumerate(
                 board_answer.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 });
             }
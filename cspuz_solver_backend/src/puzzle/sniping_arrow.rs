@@ -1,5 +1,6 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs::items::Arrow;
 use cspuz_rs_puzzles::puzzles::sniping_arrow;
 
@@ -13,9 +14,10 @@ fn convert_arrow(arrow: Arrow) -> ItemKind {
     }
 }
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let clues = sniping_arrow::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, arrow) = sniping_arrow::solve_sniping_arrow(&clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let clues = sniping_arrow::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (is_line, arrow) =
+        sniping_arrow::solve_sniping_arrow(&clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
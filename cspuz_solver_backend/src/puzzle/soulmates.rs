@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::soulmates;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = soulmates::deserialize_problem(url).ok_or("invalid url")?;
-    let answer = soulmates::solve_soulmates(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = soulmates::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer = soulmates::solve_soulmates(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::spokes;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let clues = spokes::deserialize_problem(url).ok_or("invalid url")?;
-    let (lines, lines_dr, lines_dl) = spokes::solve_spokes(&clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let clues = spokes::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (lines, lines_dr, lines_dl) = spokes::solve_spokes(&clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -22,7 +23,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -31,7 +32,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::DottedWall,
                     });
                 }
@@ -45,7 +46,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -54,7 +55,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::DottedWall,
                     });
                 }
@@ -68,7 +69,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: ItemKind::Backslash,
                     });
                 }
@@ -77,7 +78,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::DottedBackslash,
                     });
                 }
@@ -88,7 +89,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: ItemKind::Slash,
                     });
                 }
@@ -97,7 +98,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::DottedSlash,
                     });
                 }
@@ -111,13 +112,13 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2,
-                    color: "white",
+                    color: "white".into(),
                     kind: ItemKind::FilledCircle,
                 });
                 board.push(Item {
                     y: y * 2,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::Circle,
                 });
 
@@ -125,7 +126,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2,
                         x: x * 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::Num(n),
                     });
                 }
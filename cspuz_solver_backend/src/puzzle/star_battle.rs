@@ -1,10 +1,12 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::star_battle;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (star_amount, borders) = star_battle::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = star_battle::solve_star_battle(star_amount, &borders).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (star_amount, borders) =
+        star_battle::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = star_battle::solve_star_battle(star_amount, &borders).ok_or(SolveError::NoAnswer)?;
     
     let height = ans.len();
     let width = ans[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::statue_park::{self, StatueParkClue};
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (problem, pieces) = statue_park::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = statue_park::solve_statue_park(&problem, &pieces).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (problem, pieces) = statue_park::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = statue_park::solve_statue_park(&problem, &pieces).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::Uniqueness;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::sudoku;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = sudoku::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = sudoku::solve_sudoku_as_cands(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = sudoku::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = sudoku::solve_sudoku_as_cands(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = ans.len();
     let width = ans[0].len();
@@ -25,7 +26,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
         9 => (3, 3),
         16 => (4, 4),
         25 => (5, 5),
-        _ => return Err("invalid size"),
+        _ => return Err(SolveError::MalformedProblem("invalid size")),
     };
 
     for y in 0..height {
@@ -57,7 +58,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: 2 * y + 1,
                 x: 2 * x * bw,
-                color: "black",
+                color: "black".into(),
                 kind: ItemKind::BoldWall,
             });
         }
@@ -67,7 +68,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
             board.push(Item {
                 y: 2 * y * bh,
                 x: 2 * x + 1,
-                color: "black",
+                color: "black".into(),
                 kind: ItemKind::BoldWall,
             });
         }
@@ -0,0 +1,44 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs::graph;
+use cspuz_rs_puzzles::puzzles::sukororoom;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = sukororoom::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = sukororoom::solve_sukororoom(&borders, &clues).ok_or(SolveError::NoAnswer)?;
+
+    let height = ans.len();
+    let width = ans[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&ans));
+
+    board.add_borders(&borders, "black");
+
+    let rooms = graph::borders_to_rooms(&borders);
+    assert_eq!(rooms.len(), clues.len());
+    for (room, &clue) in rooms.iter().zip(&clues) {
+        if let Some(n) = clue {
+            let (y, x) = room[0];
+            board.push(Item::cell(y, x, "black", ItemKind::NumUpperLeft(n)));
+        }
+    }
+
+    for (y, row) in ans.iter().enumerate() {
+        for (x, &n) in row.iter().enumerate() {
+            if let Some(n) = n {
+                board.push(Item::cell(
+                    y,
+                    x,
+                    "green",
+                    if n == 0 {
+                        ItemKind::Dot
+                    } else {
+                        ItemKind::Num(n)
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(board)
+}
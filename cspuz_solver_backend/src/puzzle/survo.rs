@@ -0,0 +1,66 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::survo;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (column_sums, row_sums, cells) =
+        survo::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer = survo::solve_survo(&column_sums, &row_sums, &cells).ok_or(SolveError::NoAnswer)?;
+
+    let height = row_sums.len();
+    let width = column_sums.len();
+    let mut board = Board::new(BoardKind::Empty, height + 1, width + 1, is_unique(&answer));
+
+    for y in 0..height {
+        if let Some(n) = row_sums[y] {
+            board.push(Item::cell(y, width, "black", ItemKind::Num(n)));
+        }
+    }
+    for x in 0..width {
+        if let Some(n) = column_sums[x] {
+            board.push(Item::cell(height, x, "black", ItemKind::Num(n)));
+        }
+    }
+
+    for y in 0..=height {
+        for x in 0..width {
+            board.push(Item {
+                y: y * 2,
+                x: x * 2 + 1,
+                color: "black".into(),
+                kind: if y == 0 || y == height {
+                    ItemKind::BoldWall
+                } else {
+                    ItemKind::Wall
+                },
+            })
+        }
+    }
+    for y in 0..height {
+        for x in 0..=width {
+            board.push(Item {
+                y: y * 2 + 1,
+                x: x * 2,
+                color: "black".into(),
+                kind: if x == 0 || x == width {
+                    ItemKind::BoldWall
+                } else {
+                    ItemKind::Wall
+                },
+            })
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(n) = cells[y][x] {
+                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+            } else if let Some(n) = answer[y][x] {
+                board.push(Item::cell(y, x, "green", ItemKind::Num(n)));
+            }
+        }
+    }
+
+    Ok(board)
+}
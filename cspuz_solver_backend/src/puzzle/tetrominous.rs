@@ -1,5 +1,6 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs::graph::InnerGridEdges;
 use cspuz_rs_puzzles::puzzles::polyominous;
 
@@ -7,10 +8,11 @@ const TETROMINO_NAMES: [&'static str; 5] =
     ["I", "L", "O", "S", "T"];
 
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     let (clues, default_borders) =
-        polyominous::deserialize_tetrominous_problem(url).ok_or("invalid url")?;
-    let border = polyominous::solve_tetrominous(&clues, &default_borders).ok_or("no answer")?;
+        polyominous::deserialize_tetrominous_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let border =
+        polyominous::solve_tetrominous(&clues, &default_borders).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
@@ -24,7 +26,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     x,
                     "black",
                     if n >= 0 {
-                        ItemKind::Text(TETROMINO_NAMES[n as usize])
+                        ItemKind::Text(TETROMINO_NAMES[n as usize].to_string())
                     } else {
                         ItemKind::Fill
                     },
@@ -45,7 +47,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::BoldWall,
                     });
                     need_default_edge = false;
@@ -53,7 +55,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -68,7 +70,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 2,
                         x: x * 2 + 1,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -79,7 +81,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "black",
+                        color: "black".into(),
                         kind: ItemKind::BoldWall,
                     });
                     need_default_edge = false;
@@ -87,7 +89,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "green",
+                        color: "green".into(),
                         kind: if b {
                             ItemKind::BoldWall
                         } else {
@@ -102,7 +104,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item {
                         y: y * 2 + 1,
                         x: x * 2 + 2,
-                        color: "#cccccc",
+                        color: "#cccccc".into(),
                         kind: ItemKind::Wall,
                     });
                 }
@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::the_longest;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = the_longest::deserialize_problem(url).ok_or("invalid url")?;
-    let ans = the_longest::solve_the_longest(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = the_longest::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let ans = the_longest::solve_the_longest(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = ans.vertical.len();
     let width = ans.vertical[0].len() + 1;
@@ -16,7 +17,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::BoldWall,
                 });
                 continue;
@@ -26,7 +27,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: ItemKind::Wall,
                 });
                 continue;
@@ -36,7 +37,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2,
                     x: x * 2 + 1,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 });
             }
@@ -48,7 +49,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "black",
+                    color: "black".into(),
                     kind: ItemKind::BoldWall,
                 });
                 continue;
@@ -58,7 +59,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: ItemKind::Wall,
                 });
                 continue;
@@ -68,7 +69,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                 board.push(Item {
                     y: y * 2 + 1,
                     x: x * 2,
-                    color: "green",
+                    color: "green".into(),
                     kind: if b { ItemKind::Wall } else { ItemKind::Cross },
                 });
             }
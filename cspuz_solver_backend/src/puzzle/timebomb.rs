@@ -1,10 +1,11 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::timebomb;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let problem = timebomb::deserialize_problem(url).ok_or("invalid url")?;
-    let (has_number, num) = timebomb::solve_timebomb(&problem).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = timebomb::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (has_number, num) = timebomb::solve_timebomb(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -22,7 +23,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     board.push(Item::cell(y, x, "black", ItemKind::Num(clue)));
                 } else if clue == -1 {
                     board.push(Item::cell(y, x, "black", ItemKind::Circle));
-                    board.push(Item::cell(y, x, "black", ItemKind::Text("?")));
+                    board.push(Item::cell(y, x, "black", ItemKind::Text("?".to_string())));
                 } else if clue == -2 {
                     board.push(Item::cell(y, x, "black", ItemKind::FilledCircle));
                 }
@@ -38,7 +39,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     },
                 ));
             } else if has_number[y][x] == Some(true) {
-                board.push(Item::cell(y, x, "green", ItemKind::Text("?")));
+                board.push(Item::cell(y, x, "green", ItemKind::Text("?".to_string())));
             }
         }
     }
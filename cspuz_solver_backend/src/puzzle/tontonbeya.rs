@@ -1,5 +1,6 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::tontonbeya;
 
 fn num_to_item(n: i32) -> ItemKind {
@@ -11,9 +12,9 @@ fn num_to_item(n: i32) -> ItemKind {
     }
 }
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = tontonbeya::deserialize_problem(url).ok_or("invalid url")?;
-    let answer = tontonbeya::solve_tontonbeya(&borders, &clues).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) = tontonbeya::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let answer = tontonbeya::solve_tontonbeya(&borders, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = clues.len();
     let width = clues[0].len();
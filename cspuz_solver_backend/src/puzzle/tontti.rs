@@ -0,0 +1,47 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs::graph;
+use cspuz_rs_puzzles::puzzles::tontti;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (given_lines, marked) = tontti::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let mut is_line = tontti::solve_tontti(&given_lines, &marked).ok_or(SolveError::NoAnswer)?;
+
+    let height = marked.len();
+    let width = marked[0].len();
+    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+
+    for (y, row) in marked.iter().enumerate() {
+        for (x, &m) in row.iter().enumerate() {
+            if m {
+                board.push(Item::cell(y, x, "black", ItemKind::SmallFilledCircle));
+            }
+        }
+    }
+
+    let mut given = graph::BoolGridEdgesIrrefutableFacts {
+        horizontal: vec![vec![None; width - 1]; height],
+        vertical: vec![vec![None; width]; height - 1],
+    };
+    for y in 0..height {
+        for x in 0..(width - 1) {
+            if given_lines.horizontal[y][x] {
+                given.horizontal[y][x] = Some(true);
+                is_line.horizontal[y][x] = None;
+            }
+        }
+    }
+    for y in 0..(height - 1) {
+        for x in 0..width {
+            if given_lines.vertical[y][x] {
+                given.vertical[y][x] = Some(true);
+                is_line.vertical[y][x] = None;
+            }
+        }
+    }
+    board.add_lines_irrefutable_facts(&given, "black", None);
+    board.add_lines_irrefutable_facts(&is_line, "green", None);
+
+    Ok(board)
+}
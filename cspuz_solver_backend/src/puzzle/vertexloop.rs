@@ -0,0 +1,53 @@
+use crate::board::{Board, BoardKind, Item, ItemKind};
+use crate::uniqueness::is_unique;
+use crate::SolveError;
+use cspuz_rs_puzzles::puzzles::vertexloop;
+
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let problem = vertexloop::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = vertexloop::solve_vertexloop(&problem).ok_or(SolveError::NoAnswer)?;
+
+    let height = problem.len() - 1;
+    let width = problem[0].len() - 1;
+    let mut board = Board::new(BoardKind::DotGrid, height, width, is_unique(&is_line));
+
+    for y in 0..=height {
+        for x in 0..=width {
+            if let Some(n) = problem[y][x] {
+                board.push(Item {
+                    y: y * 2,
+                    x: x * 2,
+                    color: "black".into(),
+                    kind: ItemKind::Num(n),
+                });
+            }
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..=width {
+            if let Some(b) = is_line.vertical[y][x] {
+                board.push(Item {
+                    y: y * 2 + 1,
+                    x: x * 2,
+                    color: "green".into(),
+                    kind: if b { ItemKind::Wall } else { ItemKind::Cross },
+                })
+            }
+        }
+    }
+    for y in 0..=height {
+        for x in 0..width {
+            if let Some(b) = is_line.horizontal[y][x] {
+                board.push(Item {
+                    y: y * 2,
+                    x: x * 2 + 1,
+                    color: "green".into(),
+                    kind: if b { ItemKind::Wall } else { ItemKind::Cross },
+                })
+            }
+        }
+    }
+
+    Ok(board)
+}
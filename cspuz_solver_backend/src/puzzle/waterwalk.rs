@@ -1,27 +1,133 @@
-use crate::board::{Board, BoardKind, Item, ItemKind};
-use crate::uniqueness::is_unique;
+use crate::board::{Board, BoardKind, Item, ItemKind, Palette};
+use crate::uniqueness::solution_multiplicity;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::waterwalk;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (water, num) = waterwalk::deserialize_problem(url).ok_or("invalid url")?;
-    let is_line = waterwalk::solve_waterwalk(&water, &num).ok_or("no answer")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    solve_with_palette(url, &Palette::default())
+}
+
+pub fn solve_with_palette(url: &str, palette: &Palette) -> Result<Board, SolveError> {
+    solve_with_options(url, palette, false)
+}
+
+/// If `show_line_directions` is `true`, each number is annotated with small arrows pointing to the
+/// directions in which the line leaves its cell (only for the line segments determined by the solver).
+pub fn solve_with_options(
+    url: &str,
+    palette: &Palette,
+    show_line_directions: bool,
+) -> Result<Board, SolveError> {
+    let (water, num) = waterwalk::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_line = waterwalk::solve_waterwalk(&water, &num);
 
     let height = water.len();
     let width = water[0].len();
-    let mut board = Board::new(BoardKind::Grid, height, width, is_unique(&is_line));
+    let mut board = Board::new(
+        BoardKind::Grid,
+        height,
+        width,
+        solution_multiplicity(is_line.as_ref()),
+    );
 
+    board.add_bool_grid(&water, Some(ItemKind::Fill), None, palette.shading.clone());
     for y in 0..height {
         for x in 0..width {
-            if water[y][x] {
-                board.push(Item::cell(y, x, "#e0e0ff", ItemKind::Fill));
-            }
             if let Some(n) = num[y][x] {
-                board.push(Item::cell(y, x, "black", ItemKind::Num(n)));
+                board.push(Item::cell(y, x, palette.clue.clone(), ItemKind::Num(n)));
+
+                if let (true, Some(is_line)) = (show_line_directions, &is_line) {
+                    let directions = [
+                        (
+                            y > 0 && is_line.vertical[y - 1][x] == Some(true),
+                            ItemKind::SideArrowUp,
+                        ),
+                        (
+                            y < height - 1 && is_line.vertical[y][x] == Some(true),
+                            ItemKind::SideArrowDown,
+                        ),
+                        (
+                            x > 0 && is_line.horizontal[y][x - 1] == Some(true),
+                            ItemKind::SideArrowLeft,
+                        ),
+                        (
+                            x < width - 1 && is_line.horizontal[y][x] == Some(true),
+                            ItemKind::SideArrowRight,
+                        ),
+                    ];
+                    for (has_line, kind) in directions {
+                        if has_line {
+                            board.push(Item::cell(y, x, palette.line.clone(), kind));
+                        }
+                    }
+                }
             }
         }
     }
 
-    board.add_lines_irrefutable_facts(&is_line, "green", None);
+    if let Some(is_line) = &is_line {
+        board.add_lines_irrefutable_facts(is_line, palette.line.clone(), None);
+    }
 
     Ok(board)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waterwalk_palette() {
+        let url = waterwalk::serialize_problem(&(
+            vec![vec![false, false], vec![false, true]],
+            vec![vec![None, None], vec![None, None]],
+        ))
+        .unwrap();
+        let palette = Palette {
+            answer: "red".into(),
+            line: "red".into(),
+            clue: "blue".into(),
+            secondary_clue: "blue".into(),
+            shading: "yellow".into(),
+        };
+
+        let default_json = solve(&url).unwrap().to_json();
+        let custom_json = solve_with_palette(&url, &palette).unwrap().to_json();
+        assert!(default_json.contains("\"#e0e0ff\""));
+        assert!(!custom_json.contains("\"#e0e0ff\""));
+        assert!(custom_json.contains("\"yellow\""));
+    }
+
+    #[test]
+    fn test_waterwalk_line_directions() {
+        let url = "https://puzz.link/p?waterwalk/5/4/4i0ho35l33g";
+
+        let json = solve_with_options(url, &Palette::default(), false)
+            .unwrap()
+            .to_json();
+        assert!(!json.contains("sideArrow"));
+
+        let json = solve_with_options(url, &Palette::default(), true)
+            .unwrap()
+            .to_json();
+        assert_eq!(json.matches("sideArrow").count(), 8);
+        for (y, x, dir) in [
+            (1, 4, "Up"),
+            (1, 4, "Left"),
+            (2, 0, "Down"),
+            (2, 0, "Right"),
+            (3, 2, "Left"),
+            (3, 2, "Right"),
+            (3, 3, "Left"),
+            (3, 3, "Right"),
+        ] {
+            let item = format!(
+                "{{\"y\":{},\"x\":{},\"color\":\"green\",\"item\":\"sideArrow{}\"}}",
+                y * 2 + 1,
+                x * 2 + 1,
+                dir
+            );
+            assert!(json.contains(&item), "{} not found", item);
+        }
+    }
+}
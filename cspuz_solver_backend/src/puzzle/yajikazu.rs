@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::yajikazu;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     use cspuz_rs::items::Arrow;
 
-    let problem = yajikazu::deserialize_problem(url).ok_or("invalid url")?;
-    let is_black = yajikazu::solve_yajikazu(&problem).ok_or("no answer")?;
+    let problem = yajikazu::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let is_black = yajikazu::solve_yajikazu(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -49,7 +50,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     if n >= 0 {
                         ItemKind::Num(n)
                     } else {
-                        ItemKind::Text("?")
+                        ItemKind::Text("?".to_string())
                     },
                 ));
             }
@@ -1,12 +1,13 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs_puzzles::puzzles::yajilin;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
+pub fn solve(url: &str) -> Result<Board, SolveError> {
     use cspuz_rs::items::Arrow;
 
-    let problem = yajilin::deserialize_problem(url).ok_or("invalid url")?;
-    let (is_line, is_black) = yajilin::solve_yajilin(&problem).ok_or("no answer")?;
+    let problem = yajilin::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
+    let (is_line, is_black) = yajilin::solve_yajilin(&problem).ok_or(SolveError::NoAnswer)?;
 
     let height = problem.len();
     let width = problem[0].len();
@@ -46,7 +47,7 @@ pub fn solve(url: &str) -> Result<Board, &'static str> {
                     if n >= 0 {
                         ItemKind::Num(n)
                     } else {
-                        ItemKind::Text("?")
+                        ItemKind::Text("?".to_string())
                     },
                 ));
             } else if let Some(b) = is_black[y][x] {
@@ -1,12 +1,14 @@
 use crate::board::{Board, BoardKind, Item, ItemKind};
 use crate::uniqueness::is_unique;
+use crate::SolveError;
 use cspuz_rs::graph;
 use cspuz_rs_puzzles::puzzles::yajilin_regions;
 
-pub fn solve(url: &str) -> Result<Board, &'static str> {
-    let (borders, clues) = yajilin_regions::deserialize_problem(url).ok_or("invalid url")?;
+pub fn solve(url: &str) -> Result<Board, SolveError> {
+    let (borders, clues) =
+        yajilin_regions::deserialize_problem(url).ok_or(SolveError::InvalidUrl)?;
     let (is_line, is_black) =
-        yajilin_regions::solve_yajilin_regions(&borders, &clues).ok_or("no answer")?;
+        yajilin_regions::solve_yajilin_regions(&borders, &clues).ok_or(SolveError::NoAnswer)?;
 
     let height = is_black.len();
     let width = is_black[0].len();
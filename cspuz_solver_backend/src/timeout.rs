@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+use crate::board::Board;
+use crate::SolveError;
+use cspuz_rs::solver::Config;
+
+struct DefaultConfigGuard {
+    original: Config,
+}
+
+impl Drop for DefaultConfigGuard {
+    fn drop(&mut self) {
+        Config::set_default(self.original);
+    }
+}
+
+/// Runs `solve` with the default solver config modified so that SAT solving gives up after `timeout`.
+/// A failure or a no-solution board reported after the deadline is turned into `SolveError::Timeout`.
+pub(crate) fn solve_with_timeout<F>(timeout: Duration, solve: F) -> Result<Board, SolveError>
+where
+    F: FnOnce() -> Result<Board, SolveError>,
+{
+    let original = Config::default();
+    let deadline = Instant::now() + timeout;
+
+    let _guard = DefaultConfigGuard { original };
+    Config::set_default(Config {
+        deadline: Some(deadline),
+        ..original
+    });
+
+    match solve() {
+        Err(SolveError::NoAnswer) if Instant::now() >= deadline => Err(SolveError::Timeout),
+        Ok(board) if !board.has_solution() && Instant::now() >= deadline => {
+            Err(SolveError::Timeout)
+        }
+        res => res,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cspuz_rs::solver::Solver;
+    use cspuz_rs_puzzles::puzzles::{easyasabc, shugaku, waterwalk};
+
+    fn is_timeout(res: Result<Board, SolveError>) -> bool {
+        matches!(res, Err(SolveError::Timeout))
+    }
+
+    #[test]
+    fn test_timeout_hard_instances() {
+        let height = 10;
+        let width = 10;
+
+        let url = waterwalk::serialize_problem(&(
+            vec![vec![false; width]; height],
+            vec![vec![None; width]; height],
+        ))
+        .unwrap();
+        assert!(is_timeout(crate::solve_with_timeout(&url, Duration::ZERO)));
+
+        let url = shugaku::serialize_problem(&vec![vec![None; width]; height]).unwrap();
+        assert!(is_timeout(crate::solve_with_timeout(&url, Duration::ZERO)));
+
+        let url = easyasabc::serialize_problem(&(
+            3,
+            vec![None; width],
+            vec![None; height],
+            vec![None; width],
+            vec![None; height],
+            vec![vec![None; width]; height],
+        ))
+        .unwrap();
+        assert!(is_timeout(crate::solve_with_timeout(&url, Duration::ZERO)));
+    }
+
+    #[test]
+    fn test_timeout_interrupts_search() {
+        // Putting n + 1 pigeons into n holes is unsatisfiable, but the pigeonhole principle is
+        // exponentially hard for CDCL solvers, so the deadline passes while the SAT solver is running.
+        let start = Instant::now();
+        let res = solve_with_timeout(Duration::from_secs(1), || {
+            let n = 14;
+            let mut solver = Solver::new();
+            let in_hole = &solver.bool_var_2d((n + 1, n));
+            for i in 0..=n {
+                solver.add_expr(in_hole.slice_fixed_y((i, ..)).any());
+            }
+            for j in 0..n {
+                for i1 in 0..=n {
+                    for i2 in 0..i1 {
+                        solver.add_expr(!(in_hole.at((i1, j)) & in_hole.at((i2, j))));
+                    }
+                }
+            }
+            assert!(solver.solve().is_none());
+            Err(SolveError::NoAnswer)
+        });
+        assert!(is_timeout(res));
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_timeout_restores_default_config() {
+        let url = waterwalk::serialize_problem(&(
+            vec![vec![false, false], vec![false, true]],
+            vec![vec![None, None], vec![None, None]],
+        ))
+        .unwrap();
+
+        assert!(is_timeout(crate::solve_with_timeout(&url, Duration::ZERO)));
+        assert!(Config::default().deadline.is_none());
+        assert!(crate::solve_with_timeout(&url, Duration::from_secs(60)).is_ok());
+        assert!(crate::decode_and_solve(url.as_bytes()).is_ok());
+    }
+}
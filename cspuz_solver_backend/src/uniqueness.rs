@@ -1,11 +1,32 @@
 use cspuz_rs::graph::{GridEdges, InnerGridEdges};
 
+#[derive(Clone)]
 pub enum Uniqueness {
     Unique,
     NonUnique,
+    /// The puzzle has no solution. Only the clues are drawn on such a board.
+    NoSolution,
     NotApplicable,
 }
 
+/// Number of solutions of a puzzle, distinguishing "no solution" from "multiple solutions".
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Multiplicity {
+    None,
+    Unique,
+    Multiple,
+}
+
+impl From<Multiplicity> for Uniqueness {
+    fn from(multiplicity: Multiplicity) -> Uniqueness {
+        match multiplicity {
+            Multiplicity::None => Uniqueness::NoSolution,
+            Multiplicity::Unique => Uniqueness::Unique,
+            Multiplicity::Multiple => Uniqueness::NonUnique,
+        }
+    }
+}
+
 pub trait UniquenessCheckable {
     fn is_unique(&self) -> bool;
 }
@@ -48,13 +69,50 @@ impl<T: UniquenessCheckable> UniquenessCheckable for InnerGridEdges<T> {
     }
 }
 
+/// Returns the multiplicity of the solutions from the irrefutable facts of a puzzle.
+///
+/// `answer` is `None` if the puzzle has no solution; otherwise the solution is unique if and only if
+/// every fact is decided.
+pub fn solution_multiplicity<T>(answer: Option<&T>) -> Multiplicity
+where
+    T: UniquenessCheckable,
+{
+    match answer {
+        None => Multiplicity::None,
+        Some(x) if x.is_unique() => Multiplicity::Unique,
+        Some(_) => Multiplicity::Multiple,
+    }
+}
+
 pub fn is_unique<T>(x: &T) -> Uniqueness
 where
     T: UniquenessCheckable,
 {
-    if x.is_unique() {
-        Uniqueness::Unique
-    } else {
-        Uniqueness::NonUnique
+    solution_multiplicity(Some(x)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cspuz_rs_puzzles::puzzles::vertexloop;
+
+    #[test]
+    fn test_solution_multiplicity() {
+        let unique = vec![
+            vec![Some(2), None, None, Some(0)],
+            vec![None, Some(0), Some(2), None],
+            vec![None, None, None, Some(0)],
+        ];
+        let ans = vertexloop::solve_vertexloop(&unique);
+        assert_eq!(solution_multiplicity(ans.as_ref()), Multiplicity::Unique);
+
+        let multiple = vec![vec![None; 3]; 3];
+        let ans = vertexloop::solve_vertexloop(&multiple);
+        assert_eq!(solution_multiplicity(ans.as_ref()), Multiplicity::Multiple);
+
+        // A vertex on a loop always has degree 2.
+        let none = vec![vec![Some(1), None], vec![None, None]];
+        let ans = vertexloop::solve_vertexloop(&none);
+        assert_eq!(solution_multiplicity(ans.as_ref()), Multiplicity::None);
     }
 }